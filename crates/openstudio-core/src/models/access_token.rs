@@ -0,0 +1,55 @@
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TokenScope {
+    #[serde(rename = "projects:read")]
+    ProjectsRead,
+    #[serde(rename = "projects:write")]
+    ProjectsWrite,
+    #[serde(rename = "issues:read")]
+    IssuesRead,
+    #[serde(rename = "issues:write")]
+    IssuesWrite,
+}
+
+impl TokenScope {
+    /// Une portée d'écriture inclut la lecture de la même ressource.
+    pub fn covers(self, required: TokenScope) -> bool {
+        self == required
+            || matches!(
+                (self, required),
+                (TokenScope::ProjectsWrite, TokenScope::ProjectsRead) | (TokenScope::IssuesWrite, TokenScope::IssuesRead)
+            )
+    }
+}
+
+/// Jeton d'accès personnel, destiné aux scripts et à la CI. Seule l'empreinte du jeton est
+/// conservée ; `prefix` permet à l'utilisateur de le reconnaître dans la liste.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersonalAccessToken {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    #[serde(skip_serializing)]
+    pub token_hash: String,
+    pub prefix: String,
+    pub scopes: Vec<TokenScope>,
+    /// Jeton limité à un seul projet.
+    pub project_id: Option<Uuid>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl PersonalAccessToken {
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.revoked_at.is_none() && self.expires_at.is_none_or(|at| at > now)
+    }
+
+    pub fn grants(&self, scope: TokenScope) -> bool {
+        self.scopes.iter().any(|s| s.covers(scope))
+    }
+}
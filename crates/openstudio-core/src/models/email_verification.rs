@@ -0,0 +1,12 @@
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+
+/// Email de vérification envoyé à un compte, conservé pour limiter les renvois.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerificationEmail {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub email: String,
+    pub sent_at: DateTime<Utc>,
+}
@@ -0,0 +1,41 @@
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+
+use crate::models::user::ProjectRole;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InvitationStatus {
+    Pending,
+    Accepted,
+    Declined,
+    Revoked,
+}
+
+/// Invitation à rejoindre un projet. L'invité est désigné par son compte ou, s'il n'en a pas
+/// encore, par son adresse email. Seule l'empreinte du jeton est conservée.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Invitation {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub inviter_id: Uuid,
+    pub invitee_id: Option<Uuid>,
+    pub email: Option<String>,
+    pub role: ProjectRole,
+    #[serde(skip_serializing)]
+    pub token_hash: String,
+    pub status: InvitationStatus,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub responded_at: Option<DateTime<Utc>>,
+}
+
+impl Invitation {
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at <= now
+    }
+
+    pub fn is_open(&self, now: DateTime<Utc>) -> bool {
+        self.status == InvitationStatus::Pending && !self.is_expired(now)
+    }
+}
@@ -1,6 +1,8 @@
 use serde::{Serialize, Deserialize};
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
+use std::fmt;
+use std::str::FromStr;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Issue {
@@ -9,13 +11,50 @@ pub struct Issue {
     pub title: String,
     pub description: String,
     pub status: IssueStatus,
+    #[serde(default)]
+    pub author_id: Option<Uuid>,
+    #[serde(default)]
+    pub assignee_id: Option<Uuid>,
+    /// Estimation en minutes, comparée au temps passé.
+    #[serde(default)]
+    pub estimate_minutes: Option<u32>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum IssueStatus {
     Open,
     Closed,
     InProgress,
 }
+
+impl fmt::Display for IssueStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            IssueStatus::Open => "Open",
+            IssueStatus::Closed => "Closed",
+            IssueStatus::InProgress => "InProgress",
+        };
+        f.write_str(s)
+    }
+}
+
+impl FromStr for IssueStatus {
+    type Err = String;
+
+    /// Accepte aussi les variantes écrites à la main dans un tableur ("in progress", "closed"...).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized: String = s
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != '_' && *c != '-')
+            .collect::<String>()
+            .to_lowercase();
+        match normalized.as_str() {
+            "open" => Ok(IssueStatus::Open),
+            "closed" => Ok(IssueStatus::Closed),
+            "inprogress" => Ok(IssueStatus::InProgress),
+            _ => Err(format!("unknown issue status '{}'", s)),
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub old_value: String,
+    pub new_value: String,
+}
+
+/// Une modification d'issue. La révision 0 correspond à l'issue telle qu'elle a été créée,
+/// chaque mise à jour ajoute une révision numérotée à partir de 1.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssueRevision {
+    pub issue_id: Uuid,
+    pub revision: u32,
+    pub actor_id: Option<Uuid>,
+    pub changes: Vec<FieldChange>,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,27 @@
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+
+use crate::models::user::ProjectRole;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum JoinRequestStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+/// Demande d'accès à un projet public, à l'initiative de l'utilisateur.
+/// `role` et `decided_by` ne sont renseignés qu'une fois la demande traitée.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JoinRequest {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub user_id: Uuid,
+    pub message: String,
+    pub status: JoinRequestStatus,
+    pub role: Option<ProjectRole>,
+    pub decided_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub decided_at: Option<DateTime<Utc>>,
+}
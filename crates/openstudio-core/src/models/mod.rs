@@ -1,4 +1,21 @@
 pub mod project;
 pub mod project_status;
+pub mod project_settings;
+pub mod planning;
 pub mod issue;
+pub mod issue_history;
 pub mod user;
+pub mod organization;
+pub mod comment;
+pub mod notification;
+pub mod subscription;
+pub mod time_entry;
+pub mod wiki;
+pub mod social;
+pub mod invitation;
+pub mod join_request;
+pub mod session;
+pub mod access_token;
+pub mod identity;
+pub mod password_reset;
+pub mod email_verification;
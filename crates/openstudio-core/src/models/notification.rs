@@ -0,0 +1,35 @@
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+
+/// Lien résolu entre un `@username` et l'utilisateur correspondant.
+/// `comment_id` est vide quand la mention vient de la description de l'issue.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Mention {
+    pub issue_id: Uuid,
+    pub comment_id: Option<Uuid>,
+    pub user_id: Uuid,
+    pub username: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NotificationKind {
+    Mention,
+    IssueUpdated,
+    IssueCommented,
+    JoinRequestApproved,
+    JoinRequestRejected,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Notification {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub kind: NotificationKind,
+    pub project_id: Uuid,
+    pub issue_id: Option<Uuid>,
+    pub message: String,
+    pub read: bool,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,50 @@
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+
+use crate::models::user::ProjectRole;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Organization {
+    pub id: Uuid,
+    /// Namespace de l'organisation, partagé avec les noms d'utilisateur.
+    pub slug: String,
+    pub name: String,
+    pub description: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Rôle dans l'organisation. `Owner` et `Admin` gèrent membres, équipes et projets ;
+/// ils reçoivent respectivement `Owner` et `Maintainer` sur tous les projets de l'organisation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OrgRole {
+    Owner,
+    Admin,
+    Member,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrgMember {
+    pub org_id: Uuid,
+    pub user_id: Uuid,
+    pub role: OrgRole,
+    pub joined_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Team {
+    pub id: Uuid,
+    pub org_id: Uuid,
+    pub slug: String,
+    pub name: String,
+    pub members: Vec<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Rôle accordé à tous les membres d'une équipe sur un projet de l'organisation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamGrant {
+    pub team_id: Uuid,
+    pub project_id: Uuid,
+    pub role: ProjectRole,
+}
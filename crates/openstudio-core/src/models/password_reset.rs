@@ -0,0 +1,24 @@
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+
+/// Demande de réinitialisation de mot de passe. Seule l'empreinte du jeton envoyé par email
+/// est conservée, et le jeton ne sert qu'une fois. Une demande remplacée reste enregistrée
+/// pour limiter le nombre d'envois.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PasswordResetToken {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub token_hash: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub used_at: Option<DateTime<Utc>>,
+    /// Date à laquelle une demande plus récente a annulé celle-ci.
+    pub replaced_at: Option<DateTime<Utc>>,
+}
+
+impl PasswordResetToken {
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at <= now
+    }
+}
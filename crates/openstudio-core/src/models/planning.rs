@@ -0,0 +1,39 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::models::issue::IssueStatus;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Label {
+    pub name: String,
+    /// Couleur hexadécimale, ex. `#d73a4a`.
+    pub color: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Milestone {
+    #[serde(default = "Uuid::new_v4")]
+    pub id: Uuid,
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub due_on: Option<NaiveDate>,
+}
+
+/// Modèle proposé à la création d'une issue.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssueTemplate {
+    pub name: String,
+    pub title: String,
+    #[serde(default)]
+    pub body: String,
+}
+
+/// Changement de statut autorisé. Un workflow vide autorise toutes les transitions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkflowTransition {
+    pub from: IssueStatus,
+    pub to: IssueStatus,
+}
@@ -1,14 +1,61 @@
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
+use crate::models::issue::IssueStatus;
+use crate::models::project_settings::ProjectSettings;
+use crate::models::planning::{IssueTemplate, Label, Milestone, WorkflowTransition};
 use crate::models::project_status::{ProjectStatus, Visibility};
 
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct Project {
     pub id: Uuid,
+    /// Propriétaire du namespace dans lequel `slug` est unique.
+    pub owner_id: Option<Uuid>,
+    pub owner_kind: OwnerKind,
     pub name: String,
+    pub slug: String,
     pub description: String,
+    /// Sujets normalisés (minuscules, chiffres et tirets), utilisés par `/explore`.
+    pub topics: Vec<String>,
+    /// Langage principal, tel que saisi par les mainteneurs.
+    pub language: Option<String>,
+    /// Projet proposé comme point de départ, copiable via `/fork`.
+    pub is_template: bool,
+    /// Projet source d'un fork.
+    pub forked_from: Option<Uuid>,
+    pub labels: Vec<Label>,
+    pub workflow: Vec<WorkflowTransition>,
+    pub issue_templates: Vec<IssueTemplate>,
+    pub milestones: Vec<Milestone>,
+    pub settings: ProjectSettings,
     pub created_at: DateTime<Utc>,
     pub visibility: Visibility,
     pub status: ProjectStatus,
-}
\ No newline at end of file
+}
+/// `owner_id` désigne un utilisateur ou une organisation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum OwnerKind {
+    #[default]
+    User,
+    Organization,
+}
+
+impl Project {
+    /// Un projet archivé n'accepte plus ni issues, ni commentaires, ni changements de membres.
+    pub fn is_read_only(&self) -> bool {
+        self.status == ProjectStatus::Archived
+    }
+
+    pub fn allows_transition(&self, from: &IssueStatus, to: &IssueStatus) -> bool {
+        from == to || self.workflow.is_empty() || self.workflow.iter().any(|t| t.from == *from && t.to == *to)
+    }
+}
+
+/// Ancienne adresse `owner/slug` d'un projet renommé ou déplacé.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ProjectRedirect {
+    pub owner_id: Uuid,
+    pub slug: String,
+    pub project_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
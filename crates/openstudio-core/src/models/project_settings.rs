@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::models::issue::IssueStatus;
+use crate::models::project_status::Visibility;
+
+/// Abonnements automatiques appliqués aux membres du projet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum NotificationPolicy {
+    /// Les membres suivent tout le projet dès leur arrivée.
+    Watching,
+    /// Auteurs, assignés et commentateurs suivent l'issue concernée.
+    #[default]
+    Participating,
+    /// Aucun abonnement automatique : seules les mentions notifient.
+    MentionsOnly,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProjectSettings {
+    /// Visibilité donnée au projet à sa création et à ses forks.
+    pub default_visibility: Visibility,
+    pub non_members_can_open_issues: bool,
+    /// Assigné des nouvelles issues créées sans assigné ; doit être membre.
+    pub default_assignee_id: Option<Uuid>,
+    /// États utilisables par les issues du projet, `Open` compris.
+    pub allowed_issue_states: Vec<IssueStatus>,
+    pub notification_policy: NotificationPolicy,
+}
+
+impl Default for ProjectSettings {
+    fn default() -> Self {
+        Self {
+            default_visibility: Visibility::Private,
+            non_members_can_open_issues: true,
+            default_assignee_id: None,
+            allowed_issue_states: vec![IssueStatus::Open, IssueStatus::InProgress, IssueStatus::Closed],
+            notification_policy: NotificationPolicy::default(),
+        }
+    }
+}
+
+impl ProjectSettings {
+    pub fn allows_state(&self, status: &IssueStatus) -> bool {
+        self.allowed_issue_states.contains(status)
+    }
+
+    /// Les nouvelles issues naissent `Open` : cet état ne peut pas être désactivé.
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.allows_state(&IssueStatus::Open) {
+            return Err("allowed_issue_states must include Open".to_string());
+        }
+        Ok(())
+    }
+}
@@ -5,7 +5,7 @@ pub enum Visibility {
     Unlisted,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum ProjectStatus {
     Draft,
     Active,
@@ -0,0 +1,17 @@
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Star {
+    pub user_id: Uuid,
+    pub project_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Follow {
+    pub follower_id: Uuid,
+    pub followee_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,31 @@
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "type", content = "id")]
+pub enum SubscriptionTarget {
+    Issue(Uuid),
+    Project(Uuid),
+}
+
+/// Pourquoi l'utilisateur suit la cible : `Manual` quand il l'a demandé lui-même,
+/// les autres raisons correspondent aux abonnements automatiques.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SubscriptionReason {
+    Manual,
+    Author,
+    Assignee,
+    Commenter,
+    /// A importé l'issue par-dessus une existante, ou l'a ramenée à une révision.
+    Editor,
+    Member,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Subscription {
+    pub user_id: Uuid,
+    pub target: SubscriptionTarget,
+    pub reason: SubscriptionReason,
+    pub created_at: DateTime<Utc>,
+}
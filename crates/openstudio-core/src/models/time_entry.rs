@@ -0,0 +1,23 @@
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use chrono::{DateTime, NaiveDate, Utc};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeEntry {
+    pub id: Uuid,
+    pub issue_id: Uuid,
+    pub project_id: Uuid,
+    pub user_id: Uuid,
+    pub minutes: u32,
+    pub date: NaiveDate,
+    pub note: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Chronomètre en cours. Un utilisateur n'en a qu'un à la fois.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunningTimer {
+    pub user_id: Uuid,
+    pub issue_id: Uuid,
+    pub started_at: DateTime<Utc>,
+}
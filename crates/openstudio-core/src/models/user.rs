@@ -7,6 +7,9 @@ pub struct User {
     pub id: Uuid,
     pub username: String,
     pub email: String,
+    /// L'adresse a été confirmée par le lien envoyé à l'inscription.
+    #[serde(default)]
+    pub email_verified: bool,
     pub password: String,
     pub first_name: Option<String>,
     pub last_name: Option<String>,
@@ -22,7 +25,7 @@ pub struct ProjectMember {
     pub joined_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ProjectRole {
     Owner,
     Maintainer,
@@ -0,0 +1,30 @@
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+
+/// Page de wiki en markdown. `slug` est unique dans le projet, `parent_id` permet de
+/// ranger les pages en arborescence.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WikiPage {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub parent_id: Option<Uuid>,
+    pub slug: String,
+    pub title: String,
+    pub content: String,
+    /// Numéro de la dernière révision, la première vaut 1.
+    pub revision: u32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// État complet d'une page après une modification.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WikiRevision {
+    pub page_id: Uuid,
+    pub revision: u32,
+    pub title: String,
+    pub content: String,
+    pub author_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,28 @@
+use crate::models::access_token::PersonalAccessToken;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+#[derive(Default)]
+pub struct InMemoryAccessTokenRepo {
+    tokens: Arc<Mutex<Vec<PersonalAccessToken>>>,
+}
+
+impl InMemoryAccessTokenRepo {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn save(&self, token: PersonalAccessToken) {
+        let mut tokens = self.tokens.lock().unwrap();
+        tokens.retain(|t| t.id != token.id);
+        tokens.push(token);
+    }
+    pub fn get(&self, id: Uuid) -> Option<PersonalAccessToken> {
+        self.tokens.lock().unwrap().iter().find(|t| t.id == id).cloned()
+    }
+    pub fn find_by_token_hash(&self, token_hash: &str) -> Option<PersonalAccessToken> {
+        self.tokens.lock().unwrap().iter().find(|t| t.token_hash == token_hash).cloned()
+    }
+    pub fn list_by_user(&self, user_id: Uuid) -> Vec<PersonalAccessToken> {
+        self.tokens.lock().unwrap().iter().filter(|t| t.user_id == user_id).cloned().collect()
+    }
+}
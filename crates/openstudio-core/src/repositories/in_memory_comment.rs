@@ -0,0 +1,23 @@
+use crate::models::comment::Comment;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+#[derive(Default)]
+pub struct InMemoryCommentRepo {
+    comments: Arc<Mutex<Vec<Comment>>>,
+}
+
+impl InMemoryCommentRepo {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn save(&self, comment: Comment) {
+        self.comments.lock().unwrap().push(comment);
+    }
+    pub fn list_by_issue(&self, issue_id: Uuid) -> Vec<Comment> {
+        self.comments.lock().unwrap().iter().filter(|c| c.issue_id == issue_id).cloned().collect()
+    }
+    pub fn delete_for_issue(&self, issue_id: Uuid) {
+        self.comments.lock().unwrap().retain(|c| c.issue_id != issue_id);
+    }
+}
@@ -0,0 +1,26 @@
+use crate::models::email_verification::VerificationEmail;
+use chrono::{DateTime, Utc};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+#[derive(Default)]
+pub struct InMemoryEmailVerificationRepo {
+    sent: Arc<Mutex<Vec<VerificationEmail>>>,
+}
+
+impl InMemoryEmailVerificationRepo {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn save(&self, email: VerificationEmail) {
+        let mut sent = self.sent.lock().unwrap();
+        sent.retain(|e| e.id != email.id);
+        sent.push(email);
+    }
+    /// Envois faits à l'utilisateur depuis `since`, du plus ancien au plus récent.
+    pub fn sent_since(&self, user_id: Uuid, since: DateTime<Utc>) -> Vec<VerificationEmail> {
+        let mut sent: Vec<_> = self.sent.lock().unwrap().iter().filter(|e| e.user_id == user_id && e.sent_at >= since).cloned().collect();
+        sent.sort_by_key(|e| e.sent_at);
+        sent
+    }
+}
@@ -0,0 +1,25 @@
+use crate::models::identity::ExternalIdentity;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+#[derive(Default)]
+pub struct InMemoryIdentityRepo {
+    identities: Arc<Mutex<Vec<ExternalIdentity>>>,
+}
+
+impl InMemoryIdentityRepo {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn save(&self, identity: ExternalIdentity) {
+        let mut identities = self.identities.lock().unwrap();
+        identities.retain(|i| i.id != identity.id);
+        identities.push(identity);
+    }
+    pub fn find(&self, provider: &str, subject: &str) -> Option<ExternalIdentity> {
+        self.identities.lock().unwrap().iter().find(|i| i.provider == provider && i.subject == subject).cloned()
+    }
+    pub fn list_by_user(&self, user_id: Uuid) -> Vec<ExternalIdentity> {
+        self.identities.lock().unwrap().iter().filter(|i| i.user_id == user_id).cloned().collect()
+    }
+}
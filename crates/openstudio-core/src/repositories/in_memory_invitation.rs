@@ -0,0 +1,34 @@
+use crate::models::invitation::Invitation;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+#[derive(Default)]
+pub struct InMemoryInvitationRepo {
+    invitations: Arc<Mutex<Vec<Invitation>>>,
+}
+
+impl InMemoryInvitationRepo {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn save(&self, invitation: Invitation) {
+        let mut invitations = self.invitations.lock().unwrap();
+        invitations.retain(|i| i.id != invitation.id);
+        invitations.push(invitation);
+    }
+    pub fn get(&self, id: Uuid) -> Option<Invitation> {
+        self.invitations.lock().unwrap().iter().find(|i| i.id == id).cloned()
+    }
+    pub fn find_by_token_hash(&self, token_hash: &str) -> Option<Invitation> {
+        self.invitations.lock().unwrap().iter().find(|i| i.token_hash == token_hash).cloned()
+    }
+    pub fn list_by_project(&self, project_id: Uuid) -> Vec<Invitation> {
+        self.invitations.lock().unwrap().iter().filter(|i| i.project_id == project_id).cloned().collect()
+    }
+    pub fn list(&self) -> Vec<Invitation> {
+        self.invitations.lock().unwrap().clone()
+    }
+    pub fn delete_for_project(&self, project_id: Uuid) {
+        self.invitations.lock().unwrap().retain(|i| i.project_id != project_id);
+    }
+}
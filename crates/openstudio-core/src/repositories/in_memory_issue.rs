@@ -4,15 +4,14 @@ use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use uuid::Uuid;
 
+#[derive(Default)]
 pub struct InMemoryIssueRepo {
     issues: Arc<Mutex<HashMap<Uuid, Issue>>>,
 }
 
 impl InMemoryIssueRepo {
     pub fn new() -> Self {
-        Self {
-            issues: Arc::new(Mutex::new(HashMap::new())),
-        }
+        Self::default()
     }
 }
 
@@ -32,11 +31,12 @@ impl IssueRepository for InMemoryIssueRepo {
     }
     fn update(&self, issue: Issue) -> Result<bool, String> {
         let mut issues = self.issues.lock().unwrap();
-        if issues.contains_key(&issue.id) {
-            issues.insert(issue.id, issue);
-            Ok(true)
-        } else {
-            Ok(false)
+        match issues.get_mut(&issue.id) {
+            Some(existing) => {
+                *existing = issue;
+                Ok(true)
+            }
+            None => Ok(false),
         }
     }
     fn delete(&self, id: Uuid) -> Result<bool, String> {
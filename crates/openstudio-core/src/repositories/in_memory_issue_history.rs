@@ -0,0 +1,38 @@
+use crate::models::issue_history::{FieldChange, IssueRevision};
+use chrono::Utc;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+#[derive(Default)]
+pub struct InMemoryIssueHistoryRepo {
+    revisions: Arc<Mutex<HashMap<Uuid, Vec<IssueRevision>>>>,
+}
+
+impl InMemoryIssueHistoryRepo {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&self, issue_id: Uuid, actor_id: Option<Uuid>, changes: Vec<FieldChange>) -> IssueRevision {
+        let mut revisions = self.revisions.lock().unwrap();
+        let history = revisions.entry(issue_id).or_default();
+        let revision = IssueRevision {
+            issue_id,
+            revision: history.len() as u32 + 1,
+            actor_id,
+            changes,
+            created_at: Utc::now(),
+        };
+        history.push(revision.clone());
+        revision
+    }
+
+    pub fn list(&self, issue_id: Uuid) -> Vec<IssueRevision> {
+        self.revisions.lock().unwrap().get(&issue_id).cloned().unwrap_or_default()
+    }
+
+    pub fn delete_for_issue(&self, issue_id: Uuid) {
+        self.revisions.lock().unwrap().remove(&issue_id);
+    }
+}
@@ -0,0 +1,31 @@
+use crate::models::join_request::JoinRequest;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+#[derive(Default)]
+pub struct InMemoryJoinRequestRepo {
+    requests: Arc<Mutex<Vec<JoinRequest>>>,
+}
+
+impl InMemoryJoinRequestRepo {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn save(&self, request: JoinRequest) {
+        let mut requests = self.requests.lock().unwrap();
+        requests.retain(|r| r.id != request.id);
+        requests.push(request);
+    }
+    pub fn get(&self, id: Uuid) -> Option<JoinRequest> {
+        self.requests.lock().unwrap().iter().find(|r| r.id == id).cloned()
+    }
+    pub fn list_by_project(&self, project_id: Uuid) -> Vec<JoinRequest> {
+        self.requests.lock().unwrap().iter().filter(|r| r.project_id == project_id).cloned().collect()
+    }
+    pub fn list_by_user(&self, user_id: Uuid) -> Vec<JoinRequest> {
+        self.requests.lock().unwrap().iter().filter(|r| r.user_id == user_id).cloned().collect()
+    }
+    pub fn delete_for_project(&self, project_id: Uuid) {
+        self.requests.lock().unwrap().retain(|r| r.project_id != project_id);
+    }
+}
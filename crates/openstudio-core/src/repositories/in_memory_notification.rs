@@ -0,0 +1,51 @@
+use crate::models::notification::{Mention, Notification};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+#[derive(Default)]
+pub struct InMemoryMentionRepo {
+    mentions: Arc<Mutex<Vec<Mention>>>,
+}
+
+impl InMemoryMentionRepo {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn add(&self, mention: Mention) {
+        self.mentions.lock().unwrap().push(mention);
+    }
+    pub fn list_by_issue(&self, issue_id: Uuid) -> Vec<Mention> {
+        self.mentions.lock().unwrap().iter().filter(|m| m.issue_id == issue_id).cloned().collect()
+    }
+    pub fn delete_for_issue(&self, issue_id: Uuid) {
+        self.mentions.lock().unwrap().retain(|m| m.issue_id != issue_id);
+    }
+}
+
+#[derive(Default)]
+pub struct InMemoryNotificationRepo {
+    notifications: Arc<Mutex<Vec<Notification>>>,
+}
+
+impl InMemoryNotificationRepo {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn push(&self, notification: Notification) {
+        self.notifications.lock().unwrap().push(notification);
+    }
+    pub fn list_for_user(&self, user_id: Uuid) -> Vec<Notification> {
+        self.notifications.lock().unwrap().iter().filter(|n| n.user_id == user_id).cloned().collect()
+    }
+    /// Renvoie `false` si la notification n'existe pas ou appartient à quelqu'un d'autre.
+    pub fn mark_read(&self, user_id: Uuid, id: Uuid) -> bool {
+        let mut notifications = self.notifications.lock().unwrap();
+        match notifications.iter_mut().find(|n| n.id == id && n.user_id == user_id) {
+            Some(notification) => {
+                notification.read = true;
+                true
+            }
+            None => false,
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use crate::models::organization::{OrgMember, OrgRole, Organization, Team, TeamGrant};
+use crate::models::user::ProjectRole;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+#[derive(Default)]
+pub struct InMemoryOrgRepo {
+    orgs: Arc<Mutex<HashMap<Uuid, Organization>>>,
+    members: Arc<Mutex<Vec<OrgMember>>>,
+    teams: Arc<Mutex<Vec<Team>>>,
+    grants: Arc<Mutex<Vec<TeamGrant>>>,
+}
+
+impl InMemoryOrgRepo {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn save(&self, org: Organization) {
+        self.orgs.lock().unwrap().insert(org.id, org);
+    }
+    pub fn get(&self, id: Uuid) -> Option<Organization> {
+        self.orgs.lock().unwrap().get(&id).cloned()
+    }
+    pub fn find_by_slug(&self, slug: &str) -> Option<Organization> {
+        self.orgs.lock().unwrap().values().find(|o| o.slug == slug).cloned()
+    }
+    /// Ajoute le membre ou change son rôle.
+    pub fn set_member(&self, member: OrgMember) {
+        let mut members = self.members.lock().unwrap();
+        match members.iter_mut().find(|m| m.org_id == member.org_id && m.user_id == member.user_id) {
+            Some(existing) => existing.role = member.role,
+            None => members.push(member),
+        }
+    }
+    /// Retire le membre de l'organisation et de toutes ses équipes.
+    pub fn remove_member(&self, org_id: Uuid, user_id: Uuid) -> bool {
+        let mut members = self.members.lock().unwrap();
+        let len_before = members.len();
+        members.retain(|m| !(m.org_id == org_id && m.user_id == user_id));
+        for team in self.teams.lock().unwrap().iter_mut().filter(|t| t.org_id == org_id) {
+            team.members.retain(|id| *id != user_id);
+        }
+        members.len() < len_before
+    }
+    pub fn list_members(&self, org_id: Uuid) -> Vec<OrgMember> {
+        self.members.lock().unwrap().iter().filter(|m| m.org_id == org_id).cloned().collect()
+    }
+    pub fn member_role(&self, org_id: Uuid, user_id: Uuid) -> Option<OrgRole> {
+        self.members
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|m| m.org_id == org_id && m.user_id == user_id)
+            .map(|m| m.role)
+    }
+    pub fn save_team(&self, team: Team) {
+        let mut teams = self.teams.lock().unwrap();
+        teams.retain(|t| t.id != team.id);
+        teams.push(team);
+    }
+    pub fn list_teams(&self, org_id: Uuid) -> Vec<Team> {
+        self.teams.lock().unwrap().iter().filter(|t| t.org_id == org_id).cloned().collect()
+    }
+    pub fn find_team(&self, org_id: Uuid, slug: &str) -> Option<Team> {
+        self.teams.lock().unwrap().iter().find(|t| t.org_id == org_id && t.slug == slug).cloned()
+    }
+    /// Accorde ou remplace le rôle de l'équipe sur le projet.
+    pub fn grant(&self, grant: TeamGrant) {
+        let mut grants = self.grants.lock().unwrap();
+        grants.retain(|g| !(g.team_id == grant.team_id && g.project_id == grant.project_id));
+        grants.push(grant);
+    }
+    pub fn revoke(&self, team_id: Uuid, project_id: Uuid) -> bool {
+        let mut grants = self.grants.lock().unwrap();
+        let len_before = grants.len();
+        grants.retain(|g| !(g.team_id == team_id && g.project_id == project_id));
+        grants.len() < len_before
+    }
+    pub fn grants_for_team(&self, team_id: Uuid) -> Vec<TeamGrant> {
+        self.grants.lock().unwrap().iter().filter(|g| g.team_id == team_id).cloned().collect()
+    }
+    /// Rôles accordés sur le projet aux équipes dont l'utilisateur fait partie.
+    pub fn team_roles(&self, project_id: Uuid, user_id: Uuid) -> Vec<ProjectRole> {
+        let teams = self.teams.lock().unwrap();
+        self.grants
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|g| g.project_id == project_id)
+            .filter(|g| teams.iter().any(|t| t.id == g.team_id && t.members.contains(&user_id)))
+            .map(|g| g.role)
+            .collect()
+    }
+    pub fn delete_grants_for_project(&self, project_id: Uuid) {
+        self.grants.lock().unwrap().retain(|g| g.project_id != project_id);
+    }
+}
@@ -0,0 +1,47 @@
+use crate::models::password_reset::PasswordResetToken;
+use chrono::{DateTime, Utc};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+#[derive(Default)]
+pub struct InMemoryPasswordResetRepo {
+    tokens: Arc<Mutex<Vec<PasswordResetToken>>>,
+}
+
+impl InMemoryPasswordResetRepo {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn save(&self, token: PasswordResetToken) {
+        let mut tokens = self.tokens.lock().unwrap();
+        tokens.retain(|t| t.id != token.id);
+        tokens.push(token);
+    }
+    pub fn find_by_token_hash(&self, token_hash: &str) -> Option<PasswordResetToken> {
+        self.tokens.lock().unwrap().iter().find(|t| t.token_hash == token_hash).cloned()
+    }
+    /// Pose `used_at` sous le verrou si le jeton n'est ni utilisé ni remplacé : de deux
+    /// réinitialisations concurrentes, une seule réussit. En cas d'échec, renvoie le jeton
+    /// tel qu'il est stocké.
+    pub fn mark_used(&self, id: Uuid, now: DateTime<Utc>) -> Result<PasswordResetToken, Option<PasswordResetToken>> {
+        let mut tokens = self.tokens.lock().unwrap();
+        let token = tokens.iter_mut().find(|t| t.id == id).ok_or(None)?;
+        if token.used_at.is_some() || token.replaced_at.is_some() {
+            return Err(Some(token.clone()));
+        }
+        token.used_at = Some(now);
+        Ok(token.clone())
+    }
+    /// Annule les demandes encore utilisables de l'utilisateur.
+    pub fn replace_unused_for_user(&self, user_id: Uuid, now: DateTime<Utc>) {
+        for token in self.tokens.lock().unwrap().iter_mut() {
+            if token.user_id == user_id && token.used_at.is_none() && token.replaced_at.is_none() {
+                token.replaced_at = Some(now);
+            }
+        }
+    }
+    /// Demandes de l'utilisateur créées après `since`, quel que soit leur état.
+    pub fn created_since(&self, user_id: Uuid, since: DateTime<Utc>) -> Vec<PasswordResetToken> {
+        self.tokens.lock().unwrap().iter().filter(|t| t.user_id == user_id && t.created_at > since).cloned().collect()
+    }
+}
@@ -0,0 +1,40 @@
+use crate::models::project::ProjectRedirect;
+use chrono::Utc;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+#[derive(Default)]
+pub struct InMemoryProjectRedirectRepo {
+    redirects: Arc<Mutex<Vec<ProjectRedirect>>>,
+}
+
+impl InMemoryProjectRedirectRepo {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Fait pointer `owner/slug` vers le projet, en remplaçant une éventuelle redirection existante.
+    pub fn add(&self, owner_id: Uuid, slug: &str, project_id: Uuid) {
+        let mut redirects = self.redirects.lock().unwrap();
+        redirects.retain(|r| !(r.owner_id == owner_id && r.slug == slug));
+        redirects.push(ProjectRedirect {
+            owner_id,
+            slug: slug.to_string(),
+            project_id,
+            created_at: Utc::now(),
+        });
+    }
+    pub fn find(&self, owner_id: Uuid, slug: &str) -> Option<ProjectRedirect> {
+        self.redirects
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|r| r.owner_id == owner_id && r.slug == slug)
+            .cloned()
+    }
+    pub fn remove(&self, owner_id: Uuid, slug: &str) {
+        self.redirects.lock().unwrap().retain(|r| !(r.owner_id == owner_id && r.slug == slug));
+    }
+    pub fn delete_for_project(&self, project_id: Uuid) {
+        self.redirects.lock().unwrap().retain(|r| r.project_id != project_id);
+    }
+}
@@ -0,0 +1,59 @@
+use crate::models::session::RefreshToken;
+use chrono::{DateTime, Utc};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+#[derive(Default)]
+pub struct InMemoryRefreshTokenRepo {
+    tokens: Arc<Mutex<Vec<RefreshToken>>>,
+}
+
+impl InMemoryRefreshTokenRepo {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn save(&self, token: RefreshToken) {
+        let mut tokens = self.tokens.lock().unwrap();
+        tokens.retain(|t| t.id != token.id);
+        tokens.push(token);
+    }
+    pub fn get(&self, id: Uuid) -> Option<RefreshToken> {
+        self.tokens.lock().unwrap().iter().find(|t| t.id == id).cloned()
+    }
+    /// Pose `rotated_at` sous le verrou si le jeton ne l'a pas déjà : de deux échanges
+    /// concurrents, un seul réussit. En cas d'échec, renvoie le jeton tel qu'il est stocké.
+    pub fn mark_rotated(&self, id: Uuid, now: DateTime<Utc>) -> Result<RefreshToken, Option<RefreshToken>> {
+        let mut tokens = self.tokens.lock().unwrap();
+        let token = tokens.iter_mut().find(|t| t.id == id).ok_or(None)?;
+        if token.rotated_at.is_some() {
+            return Err(Some(token.clone()));
+        }
+        token.rotated_at = Some(now);
+        Ok(token.clone())
+    }
+    pub fn list_by_family(&self, family_id: Uuid) -> Vec<RefreshToken> {
+        self.tokens.lock().unwrap().iter().filter(|t| t.family_id == family_id).cloned().collect()
+    }
+    /// Une révocation porte toujours sur la famille entière.
+    pub fn is_family_revoked(&self, family_id: Uuid) -> bool {
+        self.tokens.lock().unwrap().iter().any(|t| t.family_id == family_id && t.revoked_at.is_some())
+    }
+    pub fn revoke_family(&self, family_id: Uuid, now: DateTime<Utc>) {
+        for token in self.tokens.lock().unwrap().iter_mut().filter(|t| t.family_id == family_id) {
+            token.revoked_at.get_or_insert(now);
+        }
+    }
+    /// Révoque toutes les sessions de l'utilisateur et renvoie le nombre de familles touchées.
+    pub fn revoke_for_user(&self, user_id: Uuid, now: DateTime<Utc>) -> usize {
+        let mut families = Vec::new();
+        for token in self.tokens.lock().unwrap().iter_mut().filter(|t| t.user_id == user_id) {
+            if token.revoked_at.is_none() {
+                token.revoked_at = Some(now);
+                if !families.contains(&token.family_id) {
+                    families.push(token.family_id);
+                }
+            }
+        }
+        families.len()
+    }
+}
@@ -0,0 +1,129 @@
+use crate::models::issue::Issue;
+use crate::models::wiki::WikiPage;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchKind {
+    Issue,
+    WikiPage,
+}
+
+#[derive(Debug, Clone)]
+pub struct SearchDocument {
+    pub kind: SearchKind,
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub title: String,
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchHit {
+    pub kind: SearchKind,
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub title: String,
+    pub score: u32,
+}
+
+/// Un mot du titre compte plus qu'un mot du corps.
+const TITLE_WEIGHT: u32 = 3;
+
+struct IndexedDocument {
+    document: SearchDocument,
+    /// Poids de chaque terme dans le document.
+    terms: HashMap<String, u32>,
+}
+
+/// Index plein texte minimal : les documents sont découpés en mots en minuscules et une
+/// recherche renvoie ceux qui contiennent tous les mots de la requête.
+#[derive(Default)]
+pub struct InMemorySearchIndex {
+    documents: Arc<Mutex<HashMap<(SearchKind, Uuid), IndexedDocument>>>,
+}
+
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(|t| t.to_lowercase())
+        .collect()
+}
+
+impl InMemorySearchIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn upsert(&self, document: SearchDocument) {
+        let mut terms: HashMap<String, u32> = HashMap::new();
+        for term in tokenize(&document.title) {
+            *terms.entry(term).or_default() += TITLE_WEIGHT;
+        }
+        for term in tokenize(&document.body) {
+            *terms.entry(term).or_default() += 1;
+        }
+        let key = (document.kind, document.id);
+        self.documents.lock().unwrap().insert(key, IndexedDocument { document, terms });
+    }
+
+    pub fn remove(&self, kind: SearchKind, id: Uuid) {
+        self.documents.lock().unwrap().remove(&(kind, id));
+    }
+
+    /// Retire les documents d'un projet supprimé.
+    pub fn remove_project(&self, project_id: Uuid) {
+        self.documents.lock().unwrap().retain(|_, indexed| indexed.document.project_id != project_id);
+    }
+
+    pub fn search(&self, query: &str, project_id: Option<Uuid>) -> Vec<SearchHit> {
+        let query = tokenize(query);
+        if query.is_empty() {
+            return Vec::new();
+        }
+        let documents = self.documents.lock().unwrap();
+        let mut hits: Vec<SearchHit> = documents
+            .values()
+            .filter(|d| project_id.is_none_or(|id| d.document.project_id == id))
+            .filter_map(|d| {
+                let weights: Option<Vec<u32>> = query.iter().map(|t| d.terms.get(t).copied()).collect();
+                weights.map(|w| SearchHit {
+                    kind: d.document.kind,
+                    id: d.document.id,
+                    project_id: d.document.project_id,
+                    title: d.document.title.clone(),
+                    score: w.iter().sum(),
+                })
+            })
+            .collect();
+        hits.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.title.cmp(&b.title)));
+        hits
+    }
+}
+
+impl From<&Issue> for SearchDocument {
+    fn from(issue: &Issue) -> Self {
+        Self {
+            kind: SearchKind::Issue,
+            id: issue.id,
+            project_id: issue.project_id,
+            title: issue.title.clone(),
+            body: issue.description.clone(),
+        }
+    }
+}
+
+impl From<&WikiPage> for SearchDocument {
+    fn from(page: &WikiPage) -> Self {
+        Self {
+            kind: SearchKind::WikiPage,
+            id: page.id,
+            project_id: page.project_id,
+            title: page.title.clone(),
+            body: page.content.clone(),
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use crate::models::social::{Follow, Star};
+use chrono::Utc;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+#[derive(Default)]
+pub struct InMemorySocialRepo {
+    stars: Arc<Mutex<Vec<Star>>>,
+    follows: Arc<Mutex<Vec<Follow>>>,
+}
+
+impl InMemorySocialRepo {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Renvoie `false` si le projet était déjà étoilé.
+    pub fn star(&self, user_id: Uuid, project_id: Uuid) -> bool {
+        let mut stars = self.stars.lock().unwrap();
+        if stars.iter().any(|s| s.user_id == user_id && s.project_id == project_id) {
+            return false;
+        }
+        stars.push(Star { user_id, project_id, created_at: Utc::now() });
+        true
+    }
+    pub fn unstar(&self, user_id: Uuid, project_id: Uuid) -> bool {
+        let mut stars = self.stars.lock().unwrap();
+        let len_before = stars.len();
+        stars.retain(|s| !(s.user_id == user_id && s.project_id == project_id));
+        stars.len() < len_before
+    }
+    pub fn stargazers(&self, project_id: Uuid) -> Vec<Star> {
+        self.stars.lock().unwrap().iter().filter(|s| s.project_id == project_id).cloned().collect()
+    }
+    pub fn star_count(&self, project_id: Uuid) -> usize {
+        self.stars.lock().unwrap().iter().filter(|s| s.project_id == project_id).count()
+    }
+    pub fn starred_by(&self, user_id: Uuid) -> Vec<Star> {
+        self.stars.lock().unwrap().iter().filter(|s| s.user_id == user_id).cloned().collect()
+    }
+    pub fn delete_stars_for_project(&self, project_id: Uuid) {
+        self.stars.lock().unwrap().retain(|s| s.project_id != project_id);
+    }
+    /// Renvoie `false` si l'abonnement existait déjà.
+    pub fn follow(&self, follower_id: Uuid, followee_id: Uuid) -> bool {
+        let mut follows = self.follows.lock().unwrap();
+        if follows.iter().any(|f| f.follower_id == follower_id && f.followee_id == followee_id) {
+            return false;
+        }
+        follows.push(Follow { follower_id, followee_id, created_at: Utc::now() });
+        true
+    }
+    pub fn unfollow(&self, follower_id: Uuid, followee_id: Uuid) -> bool {
+        let mut follows = self.follows.lock().unwrap();
+        let len_before = follows.len();
+        follows.retain(|f| !(f.follower_id == follower_id && f.followee_id == followee_id));
+        follows.len() < len_before
+    }
+    pub fn followers(&self, user_id: Uuid) -> Vec<Follow> {
+        self.follows.lock().unwrap().iter().filter(|f| f.followee_id == user_id).cloned().collect()
+    }
+    pub fn following(&self, user_id: Uuid) -> Vec<Follow> {
+        self.follows.lock().unwrap().iter().filter(|f| f.follower_id == user_id).cloned().collect()
+    }
+}
@@ -0,0 +1,40 @@
+use crate::usecases::stats::ProjectStats;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+/// Statistiques calculées par projet. Chaque écriture sur les issues ou les membres d'un
+/// projet invalide son entrée, qui est recalculée à la lecture suivante.
+#[derive(Default)]
+pub struct InMemoryStatsCache {
+    entries: Arc<Mutex<HashMap<Uuid, ProjectStats>>>,
+    /// Nombre d'invalidations par projet, pour écarter un calcul commencé avant l'une d'elles.
+    generations: Arc<Mutex<HashMap<Uuid, u64>>>,
+}
+
+impl InMemoryStatsCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn get(&self, project_id: Uuid) -> Option<ProjectStats> {
+        self.entries.lock().unwrap().get(&project_id).cloned()
+    }
+    pub fn generation(&self, project_id: Uuid) -> u64 {
+        self.generations.lock().unwrap().get(&project_id).copied().unwrap_or(0)
+    }
+    /// Met en cache des statistiques calculées à partir de `generation`. Ignoré si le projet a
+    /// été invalidé entre-temps : le calcul a pu manquer l'écriture.
+    pub fn put(&self, stats: ProjectStats, generation: u64) -> bool {
+        let generations = self.generations.lock().unwrap();
+        if generations.get(&stats.project_id).copied().unwrap_or(0) != generation {
+            return false;
+        }
+        self.entries.lock().unwrap().insert(stats.project_id, stats);
+        true
+    }
+    pub fn invalidate(&self, project_id: Uuid) {
+        let mut generations = self.generations.lock().unwrap();
+        *generations.entry(project_id).or_default() += 1;
+        self.entries.lock().unwrap().remove(&project_id);
+    }
+}
@@ -0,0 +1,40 @@
+use crate::models::subscription::{Subscription, SubscriptionReason, SubscriptionTarget};
+use chrono::Utc;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+#[derive(Default)]
+pub struct InMemorySubscriptionRepo {
+    subscriptions: Arc<Mutex<Vec<Subscription>>>,
+}
+
+impl InMemorySubscriptionRepo {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Abonne l'utilisateur. Renvoie `false` s'il suivait déjà la cible (la raison
+    /// d'origine est alors conservée).
+    pub fn subscribe(&self, user_id: Uuid, target: SubscriptionTarget, reason: SubscriptionReason) -> bool {
+        let mut subscriptions = self.subscriptions.lock().unwrap();
+        if subscriptions.iter().any(|s| s.user_id == user_id && s.target == target) {
+            return false;
+        }
+        subscriptions.push(Subscription { user_id, target, reason, created_at: Utc::now() });
+        true
+    }
+    pub fn unsubscribe(&self, user_id: Uuid, target: SubscriptionTarget) -> bool {
+        let mut subscriptions = self.subscriptions.lock().unwrap();
+        let len_before = subscriptions.len();
+        subscriptions.retain(|s| !(s.user_id == user_id && s.target == target));
+        subscriptions.len() < len_before
+    }
+    pub fn watchers(&self, target: SubscriptionTarget) -> Vec<Subscription> {
+        self.subscriptions.lock().unwrap().iter().filter(|s| s.target == target).cloned().collect()
+    }
+    pub fn list_for_user(&self, user_id: Uuid) -> Vec<Subscription> {
+        self.subscriptions.lock().unwrap().iter().filter(|s| s.user_id == user_id).cloned().collect()
+    }
+    pub fn delete_target(&self, target: SubscriptionTarget) {
+        self.subscriptions.lock().unwrap().retain(|s| s.target != target);
+    }
+}
@@ -0,0 +1,57 @@
+use crate::models::time_entry::{RunningTimer, TimeEntry};
+use chrono::Utc;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+#[derive(Default)]
+pub struct InMemoryTimeRepo {
+    entries: Arc<Mutex<Vec<TimeEntry>>>,
+    timers: Arc<Mutex<HashMap<Uuid, RunningTimer>>>,
+}
+
+impl InMemoryTimeRepo {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn save_entry(&self, entry: TimeEntry) {
+        self.entries.lock().unwrap().push(entry);
+    }
+    pub fn get_entry(&self, id: Uuid) -> Option<TimeEntry> {
+        self.entries.lock().unwrap().iter().find(|e| e.id == id).cloned()
+    }
+    pub fn delete_entry(&self, id: Uuid) -> bool {
+        let mut entries = self.entries.lock().unwrap();
+        let len_before = entries.len();
+        entries.retain(|e| e.id != id);
+        entries.len() < len_before
+    }
+    pub fn list_by_issue(&self, issue_id: Uuid) -> Vec<TimeEntry> {
+        self.entries.lock().unwrap().iter().filter(|e| e.issue_id == issue_id).cloned().collect()
+    }
+    pub fn list_by_project(&self, project_id: Uuid) -> Vec<TimeEntry> {
+        self.entries.lock().unwrap().iter().filter(|e| e.project_id == project_id).cloned().collect()
+    }
+    /// Démarre un chronomètre. S'il y en a déjà un, il est renvoyé en `Err` et rien ne change.
+    pub fn start_timer(&self, user_id: Uuid, issue_id: Uuid) -> Result<RunningTimer, RunningTimer> {
+        let mut timers = self.timers.lock().unwrap();
+        if let Some(running) = timers.get(&user_id) {
+            return Err(running.clone());
+        }
+        let timer = RunningTimer { user_id, issue_id, started_at: Utc::now() };
+        timers.insert(user_id, timer.clone());
+        Ok(timer)
+    }
+    /// Arrête le chronomètre de l'utilisateur s'il tourne sur `issue_id`, et le renvoie.
+    /// Entre deux arrêts concurrents, un seul le récupère.
+    pub fn stop_timer(&self, user_id: Uuid, issue_id: Uuid) -> Option<RunningTimer> {
+        let mut timers = self.timers.lock().unwrap();
+        match timers.get(&user_id) {
+            Some(running) if running.issue_id == issue_id => timers.remove(&user_id),
+            _ => None,
+        }
+    }
+    pub fn timer_for(&self, user_id: Uuid) -> Option<RunningTimer> {
+        self.timers.lock().unwrap().get(&user_id).cloned()
+    }
+}
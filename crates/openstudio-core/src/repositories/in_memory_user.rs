@@ -1,8 +1,9 @@
-use crate::models::user::{User, ProjectMember, ProjectRole};
+use crate::models::user::{User, ProjectMember};
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use uuid::Uuid;
 
+#[derive(Default)]
 pub struct InMemoryUserRepo {
     users: Arc<Mutex<HashMap<Uuid, User>>>,
     members: Arc<Mutex<Vec<ProjectMember>>>,
@@ -10,10 +11,7 @@ pub struct InMemoryUserRepo {
 
 impl InMemoryUserRepo {
     pub fn new() -> Self {
-        Self {
-            users: Arc::new(Mutex::new(HashMap::new())),
-            members: Arc::new(Mutex::new(Vec::new())),
-        }
+        Self::default()
     }
     pub fn list_users(&self) -> Vec<User> {
         self.users.lock().unwrap().values().cloned().collect()
@@ -34,6 +32,9 @@ impl InMemoryUserRepo {
         let mut members = self.members.lock().unwrap();
         members.retain(|m| !(m.project_id == project_id && m.user_id == user_id));
     }
+    pub fn delete_members_for_project(&self, project_id: Uuid) {
+        self.members.lock().unwrap().retain(|m| m.project_id != project_id);
+    }
     
     pub fn create_user(&self, user: User) -> User {
         self.users.lock().unwrap().insert(user.id, user.clone());
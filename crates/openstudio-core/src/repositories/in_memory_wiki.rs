@@ -0,0 +1,60 @@
+use crate::models::wiki::{WikiPage, WikiRevision};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+#[derive(Default)]
+pub struct InMemoryWikiRepo {
+    pages: Arc<Mutex<HashMap<Uuid, WikiPage>>>,
+    revisions: Arc<Mutex<HashMap<Uuid, Vec<WikiRevision>>>>,
+}
+
+impl InMemoryWikiRepo {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn save_page(&self, page: WikiPage) {
+        self.pages.lock().unwrap().insert(page.id, page);
+    }
+    pub fn get_page(&self, id: Uuid) -> Option<WikiPage> {
+        self.pages.lock().unwrap().get(&id).cloned()
+    }
+    pub fn find_by_slug(&self, project_id: Uuid, slug: &str) -> Option<WikiPage> {
+        self.pages
+            .lock()
+            .unwrap()
+            .values()
+            .find(|p| p.project_id == project_id && p.slug == slug)
+            .cloned()
+    }
+    pub fn list_by_project(&self, project_id: Uuid) -> Vec<WikiPage> {
+        self.pages.lock().unwrap().values().filter(|p| p.project_id == project_id).cloned().collect()
+    }
+    pub fn children(&self, page_id: Uuid) -> Vec<WikiPage> {
+        self.pages.lock().unwrap().values().filter(|p| p.parent_id == Some(page_id)).cloned().collect()
+    }
+    pub fn delete_page(&self, id: Uuid) -> bool {
+        self.revisions.lock().unwrap().remove(&id);
+        self.pages.lock().unwrap().remove(&id).is_some()
+    }
+    /// Retire toutes les pages du projet et leurs révisions. Renvoie les identifiants des pages retirées.
+    pub fn delete_for_project(&self, project_id: Uuid) -> Vec<Uuid> {
+        let mut pages = self.pages.lock().unwrap();
+        let ids: Vec<Uuid> = pages.values().filter(|p| p.project_id == project_id).map(|p| p.id).collect();
+        let mut revisions = self.revisions.lock().unwrap();
+        for id in &ids {
+            pages.remove(id);
+            revisions.remove(id);
+        }
+        ids
+    }
+    pub fn add_revision(&self, revision: WikiRevision) {
+        self.revisions.lock().unwrap().entry(revision.page_id).or_default().push(revision);
+    }
+    pub fn list_revisions(&self, page_id: Uuid) -> Vec<WikiRevision> {
+        self.revisions.lock().unwrap().get(&page_id).cloned().unwrap_or_default()
+    }
+    pub fn get_revision(&self, page_id: Uuid, revision: u32) -> Option<WikiRevision> {
+        self.list_revisions(page_id).into_iter().find(|r| r.revision == revision)
+    }
+}
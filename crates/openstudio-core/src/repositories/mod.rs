@@ -1,5 +1,23 @@
 pub mod project_repository;
 pub mod in_memory;
+pub mod in_memory_redirect;
 pub mod issue_repository;
 pub mod in_memory_issue;
+pub mod in_memory_issue_history;
 pub mod in_memory_user;
+pub mod in_memory_org;
+pub mod in_memory_comment;
+pub mod in_memory_notification;
+pub mod in_memory_subscription;
+pub mod in_memory_time;
+pub mod in_memory_stats;
+pub mod in_memory_wiki;
+pub mod in_memory_search;
+pub mod in_memory_social;
+pub mod in_memory_invitation;
+pub mod in_memory_join_request;
+pub mod in_memory_refresh_token;
+pub mod in_memory_access_token;
+pub mod in_memory_identity;
+pub mod in_memory_password_reset;
+pub mod in_memory_email_verification;
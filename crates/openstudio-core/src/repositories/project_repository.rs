@@ -6,4 +6,11 @@ pub trait ProjectRepository {
     fn get_by_id(&self, id: uuid::Uuid) -> anyhow::Result<Option<Project>>;
     fn delete(&self, id: uuid::Uuid) -> anyhow::Result<bool>;
     fn update(&self, project: Project) -> anyhow::Result<bool>;
+
+    fn find_by_slug(&self, owner_id: uuid::Uuid, slug: &str) -> anyhow::Result<Option<Project>> {
+        Ok(self
+            .list()?
+            .into_iter()
+            .find(|p| p.owner_id == Some(owner_id) && p.slug == slug))
+    }
 }
@@ -0,0 +1,104 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::models::organization::OrgRole;
+use crate::models::project::{OwnerKind, Project};
+use crate::models::project_status::Visibility;
+use crate::models::user::{ProjectRole, User};
+use crate::repositories::in_memory_org::InMemoryOrgRepo;
+use crate::repositories::in_memory_user::InMemoryUserRepo;
+use crate::usecases::policy::{self, rank, Action};
+
+/// Règles de lecture d'un projet selon sa visibilité et l'appartenance de l'appelant.
+///
+/// - `Public` : visible et listé pour tout le monde ;
+/// - `Unlisted` : accessible par lien direct, listé seulement pour ses membres ;
+/// - `Private` : réservé aux membres, y compris pour ses issues et ses membres.
+///
+/// Sur un projet d'organisation, le rôle retenu est le plus élevé entre l'appartenance
+/// directe, le rôle dans l'organisation et les rôles accordés aux équipes.
+#[derive(Clone)]
+pub struct ProjectAccess {
+    users: Arc<InMemoryUserRepo>,
+    orgs: Arc<InMemoryOrgRepo>,
+}
+
+impl ProjectAccess {
+    pub fn new(users: Arc<InMemoryUserRepo>) -> Self {
+        Self::with_orgs(users, Arc::new(InMemoryOrgRepo::new()))
+    }
+
+    pub fn with_orgs(users: Arc<InMemoryUserRepo>, orgs: Arc<InMemoryOrgRepo>) -> Self {
+        Self { users, orgs }
+    }
+
+    /// Rôle de l'utilisateur sur le projet. Le propriétaire du namespace est toujours `Owner`.
+    pub fn role(&self, project: &Project, user_id: Uuid) -> Option<ProjectRole> {
+        if project.owner_kind == OwnerKind::User && project.owner_id == Some(user_id) {
+            return Some(ProjectRole::Owner);
+        }
+        let mut roles: Vec<ProjectRole> = self
+            .users
+            .list_members(project.id)
+            .into_iter()
+            .filter(|m| m.user_id == user_id)
+            .map(|m| m.role)
+            .collect();
+        if let (OwnerKind::Organization, Some(org_id)) = (project.owner_kind, project.owner_id) {
+            match self.orgs.member_role(org_id, user_id) {
+                Some(OrgRole::Owner) => roles.push(ProjectRole::Owner),
+                Some(OrgRole::Admin) => roles.push(ProjectRole::Maintainer),
+                Some(OrgRole::Member) | None => {},
+            }
+            roles.extend(self.orgs.team_roles(project.id, user_id));
+        }
+        roles.into_iter().min_by_key(|role| rank(*role))
+    }
+
+    pub fn is_member(&self, project: &Project, user_id: Option<Uuid>) -> bool {
+        user_id.is_some_and(|id| self.role(project, id).is_some())
+    }
+
+    pub fn can_view(&self, project: &Project, user_id: Option<Uuid>) -> bool {
+        match project.visibility {
+            Visibility::Public | Visibility::Unlisted => true,
+            Visibility::Private => self.is_member(project, user_id),
+        }
+    }
+
+    /// Autorisation d'une action selon le rôle de l'appelant (voir `policy::minimum_role`).
+    /// Hors membres, seuls la lecture, les commentaires et, si le projet l'accepte,
+    /// l'ouverture d'issues restent possibles.
+    pub fn can(&self, project: &Project, user_id: Option<Uuid>, action: Action) -> bool {
+        if let Some(role) = user_id.and_then(|id| self.role(project, id)) {
+            return policy::allows(role, action);
+        }
+        match action {
+            Action::ViewProject => self.can_view(project, user_id),
+            Action::Comment => user_id.is_some() && self.can_view(project, user_id),
+            Action::OpenIssue => {
+                user_id.is_some() && self.can_view(project, user_id) && project.settings.non_members_can_open_issues
+            },
+            _ => false,
+        }
+    }
+
+    /// Gestion des membres ; seul un owner accorde le rôle `Owner`.
+    pub fn can_grant(&self, project: &Project, actor_id: Uuid, role: ProjectRole) -> bool {
+        self.can(project, Some(actor_id), Action::ManageMembers)
+            && (role != ProjectRole::Owner || self.can(project, Some(actor_id), Action::GrantOwner))
+    }
+
+    /// Compte de l'appelant authentifié.
+    pub fn user(&self, user_id: Uuid) -> Option<User> {
+        self.users.get_user(user_id)
+    }
+
+    pub fn is_listed(&self, project: &Project, user_id: Option<Uuid>) -> bool {
+        match project.visibility {
+            Visibility::Public => true,
+            Visibility::Unlisted | Visibility::Private => self.is_member(project, user_id),
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use crate::models::access_token::{PersonalAccessToken, TokenScope};
+use crate::repositories::in_memory_access_token::InMemoryAccessTokenRepo;
+use crate::usecases::token_hash::hash_token;
+
+/// Préfixe des jetons personnels, qui les distingue d'un JWT (et facilite leur détection
+/// dans un dépôt de code).
+pub const TOKEN_PREFIX: &str = "osp_";
+pub const MAX_TOKEN_NAME_LEN: usize = 100;
+pub const MAX_TOKEN_DAYS: i64 = 365;
+
+#[derive(Debug, PartialEq)]
+pub enum AccessTokenError {
+    NotFound,
+    InvalidName,
+    NoScopes,
+    InvalidExpiry,
+    /// L'utilisateur a déjà un jeton actif de ce nom.
+    DuplicateName,
+}
+
+pub struct AccessTokenContext<'a> {
+    pub tokens: &'a InMemoryAccessTokenRepo,
+}
+
+impl AccessTokenContext<'_> {
+    /// Crée un jeton et le renvoie en clair : il n'est plus récupérable ensuite.
+    pub fn create(
+        &self,
+        user_id: Uuid,
+        name: &str,
+        scopes: &[TokenScope],
+        expires_in_days: Option<i64>,
+        project_id: Option<Uuid>,
+    ) -> Result<(PersonalAccessToken, String), AccessTokenError> {
+        let now = Utc::now();
+        let name = name.trim();
+        if name.is_empty() || name.chars().count() > MAX_TOKEN_NAME_LEN {
+            return Err(AccessTokenError::InvalidName);
+        }
+        if scopes.is_empty() {
+            return Err(AccessTokenError::NoScopes);
+        }
+        if expires_in_days.is_some_and(|days| !(1..=MAX_TOKEN_DAYS).contains(&days)) {
+            return Err(AccessTokenError::InvalidExpiry);
+        }
+        if self.tokens.list_by_user(user_id).iter().any(|t| t.is_active(now) && t.name == name) {
+            return Err(AccessTokenError::DuplicateName);
+        }
+        let mut unique_scopes = Vec::new();
+        for scope in scopes {
+            if !unique_scopes.contains(scope) {
+                unique_scopes.push(*scope);
+            }
+        }
+        let raw = format!("{}{}{}", TOKEN_PREFIX, Uuid::new_v4().simple(), Uuid::new_v4().simple());
+        let token = PersonalAccessToken {
+            id: Uuid::new_v4(),
+            user_id,
+            name: name.to_string(),
+            token_hash: hash_token(&raw),
+            prefix: raw[..TOKEN_PREFIX.len() + 8].to_string(),
+            scopes: unique_scopes,
+            project_id,
+            expires_at: expires_in_days.map(|days| now + Duration::days(days)),
+            created_at: now,
+            last_used_at: None,
+            revoked_at: None,
+        };
+        self.tokens.save(token.clone());
+        Ok((token, raw))
+    }
+
+    pub fn list(&self, user_id: Uuid) -> Vec<PersonalAccessToken> {
+        let mut tokens = self.tokens.list_by_user(user_id);
+        tokens.sort_by_key(|t| t.created_at);
+        tokens
+    }
+
+    pub fn revoke(&self, user_id: Uuid, id: Uuid) -> Result<PersonalAccessToken, AccessTokenError> {
+        let mut token = self.tokens.get(id).filter(|t| t.user_id == user_id).ok_or(AccessTokenError::NotFound)?;
+        token.revoked_at.get_or_insert(Utc::now());
+        self.tokens.save(token.clone());
+        Ok(token)
+    }
+
+    /// Jeton actif correspondant à `raw`, dont la date de dernière utilisation est mise à jour.
+    pub fn authenticate(&self, raw: &str) -> Option<PersonalAccessToken> {
+        let now = Utc::now();
+        if !raw.starts_with(TOKEN_PREFIX) {
+            return None;
+        }
+        let mut token = self.tokens.find_by_token_hash(&hash_token(raw)).filter(|t| t.is_active(now))?;
+        token.last_used_at = Some(now);
+        self.tokens.save(token.clone());
+        Some(token)
+    }
+}
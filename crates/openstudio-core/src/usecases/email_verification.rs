@@ -0,0 +1,70 @@
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use crate::models::email_verification::VerificationEmail;
+use crate::models::user::User;
+use crate::repositories::in_memory_email_verification::InMemoryEmailVerificationRepo;
+use crate::repositories::in_memory_user::InMemoryUserRepo;
+use crate::usecases::throttle::check_send_rate;
+
+pub const VERIFICATION_LINK_HOURS: i64 = 24;
+
+#[derive(Debug, PartialEq)]
+pub enum EmailVerificationError {
+    NotFound,
+    AlreadyVerified,
+    /// L'adresse du compte n'est plus celle à laquelle le lien a été envoyé.
+    EmailChanged,
+    /// Trop d'envois récents : réessayer dans `retry_after` secondes.
+    TooSoon { retry_after: i64 },
+}
+
+/// Forme minimale d'une adresse : une partie locale, un `@`, un domaine avec au moins un point.
+pub fn is_valid_email(email: &str) -> bool {
+    let Some((local, domain)) = email.split_once('@') else {
+        return false;
+    };
+    email.len() <= 254
+        && !local.is_empty()
+        && !domain.contains('@')
+        && domain.contains('.')
+        && !domain.starts_with('.')
+        && !domain.ends_with('.')
+        && !email.chars().any(|c| c.is_whitespace() || c.is_control())
+}
+
+pub struct EmailVerificationContext<'a> {
+    pub sends: &'a InMemoryEmailVerificationRepo,
+    pub users: &'a InMemoryUserRepo,
+}
+
+impl EmailVerificationContext<'_> {
+    /// Enregistre un envoi du lien de vérification si le compte y a droit, et renvoie le compte
+    /// à qui écrire.
+    pub fn record_send(&self, user_id: Uuid) -> Result<User, EmailVerificationError> {
+        let now = Utc::now();
+        let user = self.users.get_user(user_id).ok_or(EmailVerificationError::NotFound)?;
+        if user.email_verified {
+            return Err(EmailVerificationError::AlreadyVerified);
+        }
+        let sent: Vec<_> = self.sends.sent_since(user_id, now - Duration::hours(24)).iter().map(|e| e.sent_at).collect();
+        check_send_rate(&sent, now).map_err(|retry_after| EmailVerificationError::TooSoon { retry_after })?;
+        self.sends.save(VerificationEmail { id: Uuid::new_v4(), user_id, email: user.email.clone(), sent_at: now });
+        Ok(user)
+    }
+
+    /// Marque l'adresse `email` du compte comme vérifiée. Ouvrir le lien une seconde fois
+    /// n'est pas une erreur.
+    pub fn confirm(&self, user_id: Uuid, email: &str) -> Result<User, EmailVerificationError> {
+        let mut user = self.users.get_user(user_id).ok_or(EmailVerificationError::NotFound)?;
+        if !user.email.eq_ignore_ascii_case(email) {
+            return Err(EmailVerificationError::EmailChanged);
+        }
+        if !user.email_verified {
+            user.email_verified = true;
+            user.updated_at = Utc::now();
+            self.users.save_user(user.clone());
+        }
+        Ok(user)
+    }
+}
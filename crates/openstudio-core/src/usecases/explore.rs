@@ -0,0 +1,116 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::models::issue::IssueStatus;
+use crate::models::project::Project;
+use crate::models::project_status::{ProjectStatus, Visibility};
+use crate::repositories::in_memory_comment::InMemoryCommentRepo;
+use crate::repositories::in_memory_issue_history::InMemoryIssueHistoryRepo;
+use crate::repositories::in_memory_user::InMemoryUserRepo;
+use crate::repositories::issue_repository::IssueRepository;
+
+pub const DEFAULT_WINDOW_DAYS: i64 = 30;
+pub const MAX_WINDOW_DAYS: i64 = 365;
+
+/// Poids de chaque signal dans le score de tendance.
+const ACTIVITY_WEIGHT: f64 = 1.0;
+const NEW_MEMBER_WEIGHT: f64 = 3.0;
+const CLOSED_ISSUE_WEIGHT: f64 = 2.0;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ProjectActivity {
+    /// Issues créées ou modifiées et commentaires publiés sur la fenêtre.
+    pub recent_activity: usize,
+    pub new_members: usize,
+    /// Issues passées à `Closed` sur la fenêtre.
+    pub closed_issues: usize,
+}
+
+impl ProjectActivity {
+    pub fn score(&self) -> f64 {
+        self.recent_activity as f64 * ACTIVITY_WEIGHT
+            + self.new_members as f64 * NEW_MEMBER_WEIGHT
+            + self.closed_issues as f64 * CLOSED_ISSUE_WEIGHT
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TrendingProject {
+    #[serde(flatten)]
+    pub project: Project,
+    pub activity: ProjectActivity,
+    pub score: f64,
+}
+
+pub struct ExploreContext<'a> {
+    pub issues: &'a dyn IssueRepository,
+    pub history: &'a InMemoryIssueHistoryRepo,
+    pub comments: &'a InMemoryCommentRepo,
+    pub users: &'a InMemoryUserRepo,
+}
+
+impl ExploreContext<'_> {
+    pub fn activity(&self, project_id: Uuid, since: DateTime<Utc>) -> Result<ProjectActivity, String> {
+        let mut activity = ProjectActivity::default();
+        let closed = IssueStatus::Closed.to_string();
+        for issue in self.issues.list_by_project(project_id)? {
+            if issue.updated_at >= since {
+                activity.recent_activity += 1;
+            }
+            activity.recent_activity += self
+                .comments
+                .list_by_issue(issue.id)
+                .iter()
+                .filter(|c| c.created_at >= since)
+                .count();
+            let closed_in_window = self.history.list(issue.id).iter().any(|revision| {
+                revision.created_at >= since
+                    && revision.changes.iter().any(|c| c.field == "status" && c.new_value == closed)
+            });
+            if closed_in_window {
+                activity.closed_issues += 1;
+            }
+        }
+        activity.new_members = self
+            .users
+            .list_members(project_id)
+            .iter()
+            .filter(|m| m.joined_at >= since)
+            .count();
+        Ok(activity)
+    }
+
+    /// Projets publics et actifs, éventuellement filtrés par sujet, du plus au moins actif
+    /// sur les `window_days` derniers jours (borné à [1, MAX_WINDOW_DAYS]).
+    pub fn explore(
+        &self,
+        projects: Vec<Project>,
+        topic: Option<&str>,
+        window_days: i64,
+    ) -> Result<Vec<TrendingProject>, String> {
+        let since = Utc::now() - Duration::days(window_days.clamp(1, MAX_WINDOW_DAYS));
+        let topic = topic.map(|t| t.trim().to_lowercase());
+        let mut trending = Vec::new();
+        for project in projects {
+            if project.visibility != Visibility::Public || project.status != ProjectStatus::Active {
+                continue;
+            }
+            if let Some(topic) = &topic
+                && !project.topics.contains(topic)
+            {
+                continue;
+            }
+            let activity = self.activity(project.id, since)?;
+            let score = activity.score();
+            trending.push(TrendingProject { project, activity, score });
+        }
+        // À score égal, les projets les plus récents d'abord
+        trending.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| b.project.created_at.cmp(&a.project.created_at))
+        });
+        Ok(trending)
+    }
+}
@@ -0,0 +1,115 @@
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::models::identity::ExternalIdentity;
+use crate::models::user::User;
+use crate::repositories::in_memory_identity::InMemoryIdentityRepo;
+use crate::repositories::in_memory_org::InMemoryOrgRepo;
+use crate::repositories::in_memory_user::InMemoryUserRepo;
+use crate::usecases::organization::namespace_taken;
+
+/// Profil renvoyé par le fournisseur après authentification.
+#[derive(Debug, Clone)]
+pub struct ExternalProfile {
+    pub subject: String,
+    pub email: Option<String>,
+    pub email_verified: bool,
+    /// Nom d'utilisateur suggéré (login GitHub, `preferred_username` OIDC…).
+    pub username: Option<String>,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum IdentityError {
+    /// Première connexion sans adresse email : impossible de créer le compte.
+    MissingEmail,
+    /// Un compte existe déjà avec cette adresse, mais le fournisseur ne l'a pas vérifiée.
+    UnverifiedEmail,
+    /// Le compte portant cette adresse ne l'a jamais confirmée : il a pu être créé par un tiers.
+    UnverifiedAccount,
+}
+
+#[derive(Debug)]
+pub struct SignIn {
+    pub user: User,
+    pub identity: ExternalIdentity,
+    /// Le compte vient d'être créé.
+    pub created: bool,
+}
+
+pub struct IdentityContext<'a> {
+    pub identities: &'a InMemoryIdentityRepo,
+    pub users: &'a InMemoryUserRepo,
+    pub orgs: &'a InMemoryOrgRepo,
+}
+
+impl IdentityContext<'_> {
+    /// Retrouve l'utilisateur d'une identité externe. À la première connexion, l'identité est
+    /// rattachée au compte portant la même adresse si le fournisseur et le compte l'ont vérifiée ; à défaut
+    /// de compte, un compte sans mot de passe est créé.
+    pub fn sign_in(&self, provider: &str, profile: &ExternalProfile) -> Result<SignIn, IdentityError> {
+        let now = Utc::now();
+        let known = self.identities.find(provider, &profile.subject);
+        if let Some(mut identity) = known.clone()
+            && let Some(user) = self.users.get_user(identity.user_id)
+        {
+            identity.last_login_at = now;
+            identity.email = profile.email.clone().or(identity.email);
+            self.identities.save(identity.clone());
+            return Ok(SignIn { user, identity, created: false });
+        }
+        let email = profile.email.as_deref().map(|e| e.trim().to_lowercase()).filter(|e| !e.is_empty());
+        let email = email.ok_or(IdentityError::MissingEmail)?;
+        let existing = self.users.list_users().into_iter().find(|u| u.email.eq_ignore_ascii_case(&email));
+        let (user, created) = match existing {
+            Some(_) if !profile.email_verified => return Err(IdentityError::UnverifiedEmail),
+            Some(user) if !user.email_verified => return Err(IdentityError::UnverifiedAccount),
+            Some(user) => (user, false),
+            None => {
+                let user = User {
+                    id: Uuid::new_v4(),
+                    username: self.available_username(profile.username.as_deref().unwrap_or(&email)),
+                    email: email.clone(),
+                    email_verified: profile.email_verified,
+                    // Aucun hash valide : la connexion par mot de passe reste impossible
+                    password: String::new(),
+                    first_name: profile.first_name.clone(),
+                    last_name: profile.last_name.clone(),
+                    created_at: now,
+                    updated_at: now,
+                };
+                (self.users.create_user(user), true)
+            },
+        };
+        let identity = ExternalIdentity {
+            // Une identité dont le compte a disparu est rattachée au nouveau compte
+            id: known.map_or_else(Uuid::new_v4, |i| i.id),
+            user_id: user.id,
+            provider: provider.to_string(),
+            subject: profile.subject.clone(),
+            email: Some(email),
+            created_at: now,
+            last_login_at: now,
+        };
+        self.identities.save(identity.clone());
+        Ok(SignIn { user, identity, created })
+    }
+
+    /// Nom d'utilisateur libre dérivé de `hint` : caractères sûrs uniquement, suffixé s'il est
+    /// pris par un utilisateur ou une organisation.
+    fn available_username(&self, hint: &str) -> String {
+        let hint = hint.split('@').next().unwrap_or(hint);
+        let mut base: String = hint.chars().filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_').collect();
+        if base.is_empty() {
+            base = "user".to_string();
+        }
+        let mut candidate = base.clone();
+        let mut suffix = 1;
+        while namespace_taken(self.users, self.orgs, &candidate) {
+            suffix += 1;
+            candidate = format!("{}{}", base, suffix);
+        }
+        candidate
+    }
+}
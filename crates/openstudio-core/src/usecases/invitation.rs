@@ -0,0 +1,175 @@
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use crate::models::invitation::{Invitation, InvitationStatus};
+use crate::models::project::Project;
+use crate::models::user::{ProjectMember, ProjectRole, User};
+use crate::repositories::in_memory_invitation::InMemoryInvitationRepo;
+use crate::repositories::in_memory_user::InMemoryUserRepo;
+use crate::usecases::access::ProjectAccess;
+use crate::usecases::member::{self, MemberError};
+use crate::usecases::policy::Action;
+use crate::usecases::token_hash::hash_token;
+
+pub const DEFAULT_INVITATION_DAYS: i64 = 7;
+pub const MAX_INVITATION_DAYS: i64 = 30;
+
+#[derive(Debug, PartialEq)]
+pub enum InvitationError {
+    NotFound,
+    /// Seuls owners et maintainers invitent, et seul un owner propose le rôle `Owner`.
+    Forbidden,
+    UnknownUser,
+    AlreadyMember,
+    AlreadyInvited,
+    Expired,
+    /// L'invitation a déjà été acceptée, déclinée ou révoquée.
+    AlreadyAnswered,
+    /// L'appelant n'est pas la personne invitée.
+    NotInvitee,
+    Member(MemberError),
+}
+
+/// Personne invitée : un compte existant, ou une adresse email.
+#[derive(Debug, Clone)]
+pub enum Invitee {
+    Username(String),
+    Email(String),
+}
+
+fn generate_token() -> String {
+    format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple())
+}
+
+pub struct InvitationContext<'a> {
+    pub invitations: &'a InMemoryInvitationRepo,
+    pub users: &'a InMemoryUserRepo,
+    pub access: &'a ProjectAccess,
+}
+
+impl InvitationContext<'_> {
+    /// Crée une invitation et renvoie le jeton en clair, qui n'est plus récupérable ensuite.
+    pub fn invite(
+        &self,
+        project: &Project,
+        inviter_id: Uuid,
+        invitee: &Invitee,
+        role: ProjectRole,
+        ttl_days: i64,
+    ) -> Result<(Invitation, String), InvitationError> {
+        if !self.access.can_grant(project, inviter_id, role) {
+            return Err(InvitationError::Forbidden);
+        }
+        let (invitee_id, email) = match invitee {
+            Invitee::Username(username) => {
+                let user = self.users.find_by_username(username).ok_or(InvitationError::UnknownUser)?;
+                (Some(user.id), None)
+            },
+            Invitee::Email(email) => {
+                let email = email.trim().to_lowercase();
+                let user = self.users.find_by_email(&email);
+                (user.map(|u| u.id), Some(email))
+            },
+        };
+        if invitee_id.is_some_and(|id| self.access.role(project, id).is_some()) {
+            return Err(InvitationError::AlreadyMember);
+        }
+        let now = Utc::now();
+        let duplicate = self.invitations.list_by_project(project.id).into_iter().any(|i| {
+            i.is_open(now) && ((invitee_id.is_some() && i.invitee_id == invitee_id) || (email.is_some() && i.email == email))
+        });
+        if duplicate {
+            return Err(InvitationError::AlreadyInvited);
+        }
+        let token = generate_token();
+        let invitation = Invitation {
+            id: Uuid::new_v4(),
+            project_id: project.id,
+            inviter_id,
+            invitee_id,
+            email,
+            role,
+            token_hash: hash_token(&token),
+            status: InvitationStatus::Pending,
+            expires_at: now + Duration::days(ttl_days.clamp(1, MAX_INVITATION_DAYS)),
+            created_at: now,
+            responded_at: None,
+        };
+        self.invitations.save(invitation.clone());
+        Ok((invitation, token))
+    }
+
+    /// Une invitation par email ne vaut que pour un compte qui a confirmé cette adresse.
+    fn is_invitee(invitation: &Invitation, user: &User) -> bool {
+        invitation.invitee_id == Some(user.id)
+            || (user.email_verified
+                && invitation.email.as_deref().is_some_and(|email| email.eq_ignore_ascii_case(&user.email)))
+    }
+
+    /// Invitations encore valables adressées à l'utilisateur, par compte ou par email.
+    pub fn pending_for(&self, user: &User) -> Vec<Invitation> {
+        let now = Utc::now();
+        self.invitations
+            .list()
+            .into_iter()
+            .filter(|i| i.is_open(now) && Self::is_invitee(i, user))
+            .collect()
+    }
+
+    pub fn find_by_token(&self, token: &str) -> Option<Invitation> {
+        self.invitations.find_by_token_hash(&hash_token(token))
+    }
+
+    fn answerable(&self, invitation: &Invitation, user: &User) -> Result<(), InvitationError> {
+        if !Self::is_invitee(invitation, user) {
+            return Err(InvitationError::NotInvitee);
+        }
+        if invitation.status != InvitationStatus::Pending {
+            return Err(InvitationError::AlreadyAnswered);
+        }
+        if invitation.is_expired(Utc::now()) {
+            return Err(InvitationError::Expired);
+        }
+        Ok(())
+    }
+
+    /// Accepte l'invitation : l'utilisateur devient membre avec le rôle proposé.
+    pub fn accept(&self, mut invitation: Invitation, project: &Project, user: &User) -> Result<ProjectMember, InvitationError> {
+        self.answerable(&invitation, user)?;
+        if self.access.role(project, user.id).is_some() {
+            return Err(InvitationError::AlreadyMember);
+        }
+        let member = member::add_member(self.users, project, user.id, invitation.role).map_err(InvitationError::Member)?;
+        invitation.invitee_id = Some(user.id);
+        invitation.status = InvitationStatus::Accepted;
+        invitation.responded_at = Some(Utc::now());
+        self.invitations.save(invitation);
+        Ok(member)
+    }
+
+    pub fn decline(&self, mut invitation: Invitation, user: &User) -> Result<Invitation, InvitationError> {
+        self.answerable(&invitation, user)?;
+        invitation.status = InvitationStatus::Declined;
+        invitation.responded_at = Some(Utc::now());
+        self.invitations.save(invitation.clone());
+        Ok(invitation)
+    }
+
+    pub fn revoke(&self, project: &Project, actor_id: Uuid, invitation_id: Uuid) -> Result<Invitation, InvitationError> {
+        if !self.access.can(project, Some(actor_id), Action::ManageMembers) {
+            return Err(InvitationError::Forbidden);
+        }
+        let mut invitation = self
+            .invitations
+            .get(invitation_id)
+            .filter(|i| i.project_id == project.id)
+            .ok_or(InvitationError::NotFound)?;
+        if invitation.status != InvitationStatus::Pending {
+            return Err(InvitationError::AlreadyAnswered);
+        }
+        invitation.status = InvitationStatus::Revoked;
+        invitation.responded_at = Some(Utc::now());
+        self.invitations.save(invitation.clone());
+        Ok(invitation)
+    }
+}
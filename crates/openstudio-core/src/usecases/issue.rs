@@ -0,0 +1,111 @@
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::models::issue::Issue;
+use crate::models::issue_history::{FieldChange, IssueRevision};
+use crate::repositories::in_memory_issue_history::InMemoryIssueHistoryRepo;
+use crate::repositories::issue_repository::IssueRepository;
+
+fn optional_id(id: Option<Uuid>) -> String {
+    id.map(|id| id.to_string()).unwrap_or_default()
+}
+
+fn optional_number(value: Option<u32>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
+/// Liste les champs qui diffèrent entre deux versions d'une même issue.
+pub fn diff_issue(old: &Issue, new: &Issue) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+    let mut push = |field: &str, old_value: String, new_value: String| {
+        if old_value != new_value {
+            changes.push(FieldChange { field: field.to_string(), old_value, new_value });
+        }
+    };
+    push("title", old.title.clone(), new.title.clone());
+    push("description", old.description.clone(), new.description.clone());
+    push("status", old.status.to_string(), new.status.to_string());
+    push("assignee_id", optional_id(old.assignee_id), optional_id(new.assignee_id));
+    push("estimate_minutes", optional_number(old.estimate_minutes), optional_number(new.estimate_minutes));
+    changes
+}
+
+/// Enregistre `updated` à la place de la version courante et trace les champs modifiés.
+/// Renvoie `Ok(None)` si l'issue n'existe pas. `updated_at` est laissé à l'appelant.
+pub fn update_issue(
+    repo: &dyn IssueRepository,
+    history: &InMemoryIssueHistoryRepo,
+    updated: Issue,
+    actor_id: Option<Uuid>,
+) -> Result<Option<Issue>, String> {
+    let Some(existing) = repo.get_by_id(updated.id)? else {
+        return Ok(None);
+    };
+    let changes = diff_issue(&existing, &updated);
+    if changes.is_empty() {
+        return Ok(Some(existing));
+    }
+    if !repo.update(updated.clone())? {
+        return Ok(None);
+    }
+    history.record(updated.id, actor_id, changes);
+    Ok(Some(updated))
+}
+
+/// Reconstitue l'issue telle qu'elle était après `revision`, en annulant une à une
+/// les révisions suivantes à partir de la version courante.
+pub fn issue_at_revision(current: &Issue, revisions: &[IssueRevision], revision: u32) -> Result<Issue, String> {
+    let mut snapshot = current.clone();
+    let mut later: Vec<&IssueRevision> = revisions.iter().filter(|r| r.revision > revision).collect();
+    later.sort_by_key(|r| std::cmp::Reverse(r.revision));
+    for rev in later {
+        for change in &rev.changes {
+            match change.field.as_str() {
+                "title" => snapshot.title = change.old_value.clone(),
+                "description" => snapshot.description = change.old_value.clone(),
+                "status" => snapshot.status = change.old_value.parse()?,
+                "assignee_id" if change.old_value.is_empty() => snapshot.assignee_id = None,
+                "assignee_id" => {
+                    snapshot.assignee_id = Some(change.old_value.parse().map_err(|e: uuid::Error| e.to_string())?)
+                }
+                "estimate_minutes" if change.old_value.is_empty() => snapshot.estimate_minutes = None,
+                "estimate_minutes" => {
+                    snapshot.estimate_minutes = Some(change.old_value.parse().map_err(|e: std::num::ParseIntError| e.to_string())?)
+                }
+                other => return Err(format!("unknown field '{}' in revision {}", other, rev.revision)),
+            }
+        }
+    }
+    Ok(snapshot)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum RevertError {
+    IssueNotFound,
+    RevisionNotFound,
+    Storage(String),
+}
+
+/// Remet l'issue dans l'état de `revision`. Le retour en arrière est lui-même une
+/// nouvelle révision : l'historique n'est jamais réécrit.
+pub fn revert_issue(
+    repo: &dyn IssueRepository,
+    history: &InMemoryIssueHistoryRepo,
+    issue_id: Uuid,
+    revision: u32,
+    actor_id: Option<Uuid>,
+) -> Result<Issue, RevertError> {
+    let current = repo
+        .get_by_id(issue_id)
+        .map_err(RevertError::Storage)?
+        .ok_or(RevertError::IssueNotFound)?;
+    let revisions = history.list(issue_id);
+    if revision > revisions.len() as u32 {
+        return Err(RevertError::RevisionNotFound);
+    }
+    let mut target = issue_at_revision(&current, &revisions, revision).map_err(RevertError::Storage)?;
+    target.updated_at = Utc::now();
+    update_issue(repo, history, target, actor_id)
+        .map_err(RevertError::Storage)?
+        .ok_or(RevertError::IssueNotFound)
+}
@@ -0,0 +1,247 @@
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::models::issue::{Issue, IssueStatus};
+use crate::models::project::Project;
+use crate::repositories::in_memory_issue_history::InMemoryIssueHistoryRepo;
+use crate::repositories::issue_repository::IssueRepository;
+use crate::usecases::issue::update_issue;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExchangeFormat {
+    Csv,
+    #[default]
+    Json,
+}
+
+/// Une ligne d'export/import. Les colonnes portent le nom des champs de `Issue`,
+/// seul `title` est obligatoire à l'import.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssueRecord {
+    #[serde(default)]
+    pub id: Option<Uuid>,
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub author_id: Option<Uuid>,
+    #[serde(default)]
+    pub assignee_id: Option<Uuid>,
+    #[serde(default)]
+    pub estimate_minutes: Option<u32>,
+    #[serde(default)]
+    pub created_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl From<&Issue> for IssueRecord {
+    fn from(issue: &Issue) -> Self {
+        Self {
+            id: Some(issue.id),
+            title: issue.title.clone(),
+            description: issue.description.clone(),
+            status: Some(issue.status.to_string()),
+            author_id: issue.author_id,
+            assignee_id: issue.assignee_id,
+            estimate_minutes: issue.estimate_minutes,
+            created_at: Some(issue.created_at),
+            updated_at: Some(issue.updated_at),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RowError {
+    /// Numéro de la ligne de données, en partant de 1 (l'en-tête CSV n'est pas compté).
+    pub row: usize,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    pub created: usize,
+    pub updated: usize,
+    pub errors: Vec<RowError>,
+    /// Issues écrites par l'import, vide en `dry_run`.
+    #[serde(skip)]
+    pub written: Vec<(Issue, ImportedAs)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImportedAs {
+    Created,
+    Updated,
+}
+
+impl ImportReport {
+    pub fn is_ok(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+pub fn export_issues(issues: &[Issue], format: ExchangeFormat) -> Result<String, String> {
+    let mut sorted: Vec<&Issue> = issues.iter().collect();
+    sorted.sort_by_key(|i| (i.created_at, i.id));
+    let records: Vec<IssueRecord> = sorted.into_iter().map(IssueRecord::from).collect();
+    match format {
+        ExchangeFormat::Json => serde_json::to_string_pretty(&records).map_err(|e| e.to_string()),
+        ExchangeFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(Vec::new());
+            for record in &records {
+                writer.serialize(record).map_err(|e| e.to_string())?;
+            }
+            let bytes = writer.into_inner().map_err(|e| e.to_string())?;
+            String::from_utf8(bytes).map_err(|e| e.to_string())
+        }
+    }
+}
+
+/// Découpe le fichier en lignes. Une erreur globale (JSON illisible, CSV sans en-tête)
+/// est renvoyée en `Err`, une ligne invalide est conservée avec son message.
+pub fn parse_records(input: &str, format: ExchangeFormat) -> Result<Vec<Result<IssueRecord, String>>, String> {
+    match format {
+        ExchangeFormat::Json => {
+            let rows: Vec<serde_json::Value> =
+                serde_json::from_str(input).map_err(|e| format!("invalid JSON: {}", e))?;
+            Ok(rows
+                .into_iter()
+                .map(|row| serde_json::from_value::<IssueRecord>(row).map_err(|e| e.to_string()))
+                .collect())
+        }
+        ExchangeFormat::Csv => {
+            let mut reader = csv::ReaderBuilder::new().trim(csv::Trim::All).from_reader(input.as_bytes());
+            let headers = reader.headers().map_err(|e| format!("invalid CSV: {}", e))?;
+            if !headers.iter().any(|h| h == "title") {
+                return Err("invalid CSV: missing 'title' column".to_string());
+            }
+            Ok(reader
+                .deserialize::<IssueRecord>()
+                .map(|row| row.map_err(|e| e.to_string()))
+                .collect())
+        }
+    }
+}
+
+/// Importe les issues d'un projet. L'import est tout ou rien : si une ligne est invalide,
+/// rien n'est écrit et le rapport liste les erreurs. En `dry_run`, rien n'est jamais écrit.
+///
+/// Une ligne dont l'`id` existe déjà dans le projet met l'issue à jour (et passe donc par
+/// l'historique), ce qui permet de ré-importer un export sans doublons. Un `id` appartenant
+/// à un autre projet est remplacé. Les règles du projet s'appliquent comme à l'édition : un
+/// statut désactivé ne peut pas être donné, et une mise à jour doit suivre le workflow.
+pub fn import_issues(
+    repo: &dyn IssueRepository,
+    history: &InMemoryIssueHistoryRepo,
+    actor_id: Option<Uuid>,
+    project: &Project,
+    input: &str,
+    format: ExchangeFormat,
+    dry_run: bool,
+) -> Result<ImportReport, String> {
+    let project_id = project.id;
+    let rows = parse_records(input, format)?;
+    let now = Utc::now();
+    let mut report = ImportReport { dry_run, created: 0, updated: 0, errors: Vec::new(), written: Vec::new() };
+    let mut seen_ids = HashSet::new();
+    let mut to_save = Vec::new();
+    let mut to_update = Vec::new();
+
+    for (index, row) in rows.into_iter().enumerate() {
+        let row_number = index + 1;
+        let record = match row.and_then(validate_record) {
+            Ok(record) => record,
+            Err(message) => {
+                report.errors.push(RowError { row: row_number, message });
+                continue;
+            }
+        };
+        let existing = match record.id {
+            Some(id) => repo.get_by_id(id)?.filter(|i| i.project_id == project_id),
+            None => None,
+        };
+        let id = match (&existing, record.id) {
+            (Some(issue), _) => issue.id,
+            (None, Some(id)) if repo.get_by_id(id)?.is_none() => id,
+            _ => Uuid::new_v4(),
+        };
+        if !seen_ids.insert(id) {
+            report.errors.push(RowError { row: row_number, message: format!("duplicate id {}", id) });
+            continue;
+        }
+        let status = match record.status.as_deref() {
+            Some(s) if !s.is_empty() => s.parse::<IssueStatus>()?,
+            _ => IssueStatus::Open,
+        };
+        let current = existing.as_ref().map(|i| &i.status);
+        if current != Some(&status) && !project.settings.allows_state(&status) {
+            let message = format!("status {} is disabled for this project", status);
+            report.errors.push(RowError { row: row_number, message });
+            continue;
+        }
+        if let Some(current) = current
+            && !project.allows_transition(current, &status)
+        {
+            let message = format!("the project workflow does not allow moving from {} to {}", current, status);
+            report.errors.push(RowError { row: row_number, message });
+            continue;
+        }
+        let created_at = record.created_at.or(existing.as_ref().map(|i| i.created_at)).unwrap_or(now);
+        let issue = Issue {
+            id,
+            project_id,
+            title: record.title,
+            description: record.description,
+            status,
+            author_id: record.author_id,
+            assignee_id: record.assignee_id,
+            estimate_minutes: record.estimate_minutes,
+            created_at,
+            updated_at: record.updated_at.unwrap_or(now),
+        };
+        if existing.is_some() {
+            to_update.push(issue);
+        } else {
+            to_save.push(issue);
+        }
+    }
+
+    report.created = to_save.len();
+    report.updated = to_update.len();
+    if dry_run || !report.is_ok() {
+        return Ok(report);
+    }
+    for issue in to_save {
+        repo.save(issue.clone())?;
+        report.written.push((issue, ImportedAs::Created));
+    }
+    for issue in to_update {
+        if let Some(issue) = update_issue(repo, history, issue, actor_id)? {
+            report.written.push((issue, ImportedAs::Updated));
+        }
+    }
+    Ok(report)
+}
+
+fn validate_record(mut record: IssueRecord) -> Result<IssueRecord, String> {
+    record.title = record.title.trim().to_string();
+    if record.title.is_empty() {
+        return Err("title must not be empty".to_string());
+    }
+    if let Some(status) = record.status.as_deref().filter(|s| !s.is_empty()) {
+        status.parse::<IssueStatus>()?;
+    }
+    if let (Some(created), Some(updated)) = (record.created_at, record.updated_at)
+        && updated < created
+    {
+        return Err("updated_at is before created_at".to_string());
+    }
+    Ok(record)
+}
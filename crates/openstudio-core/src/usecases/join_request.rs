@@ -0,0 +1,163 @@
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::models::join_request::{JoinRequest, JoinRequestStatus};
+use crate::models::notification::{Notification, NotificationKind};
+use crate::models::project::Project;
+use crate::models::project_status::Visibility;
+use crate::models::user::{ProjectMember, ProjectRole};
+use crate::repositories::in_memory_join_request::InMemoryJoinRequestRepo;
+use crate::repositories::in_memory_notification::InMemoryNotificationRepo;
+use crate::repositories::in_memory_user::InMemoryUserRepo;
+use crate::usecases::access::ProjectAccess;
+use crate::usecases::member::{self, MemberError};
+use crate::usecases::policy::Action;
+
+pub const MAX_JOIN_MESSAGE_LEN: usize = 1000;
+
+#[derive(Debug, PartialEq)]
+pub enum JoinRequestError {
+    NotFound,
+    /// Seuls les projets publics acceptent des demandes.
+    NotPublic,
+    MessageTooLong,
+    AlreadyMember,
+    AlreadyRequested,
+    /// Seuls owners et maintainers décident, et seul un owner accorde le rôle `Owner`.
+    Forbidden,
+    AlreadyDecided,
+    Member(MemberError),
+}
+
+pub struct JoinRequestContext<'a> {
+    pub requests: &'a InMemoryJoinRequestRepo,
+    pub users: &'a InMemoryUserRepo,
+    pub notifications: &'a InMemoryNotificationRepo,
+    pub access: &'a ProjectAccess,
+}
+
+impl JoinRequestContext<'_> {
+    pub fn submit(&self, project: &Project, user_id: Uuid, message: &str) -> Result<JoinRequest, JoinRequestError> {
+        if project.visibility != Visibility::Public {
+            return Err(JoinRequestError::NotPublic);
+        }
+        let message = message.trim();
+        if message.chars().count() > MAX_JOIN_MESSAGE_LEN {
+            return Err(JoinRequestError::MessageTooLong);
+        }
+        if self.access.role(project, user_id).is_some() {
+            return Err(JoinRequestError::AlreadyMember);
+        }
+        let pending = self
+            .requests
+            .list_by_project(project.id)
+            .iter()
+            .any(|r| r.user_id == user_id && r.status == JoinRequestStatus::Pending);
+        if pending {
+            return Err(JoinRequestError::AlreadyRequested);
+        }
+        let request = JoinRequest {
+            id: Uuid::new_v4(),
+            project_id: project.id,
+            user_id,
+            message: message.to_string(),
+            status: JoinRequestStatus::Pending,
+            role: None,
+            decided_by: None,
+            created_at: Utc::now(),
+            decided_at: None,
+        };
+        self.requests.save(request.clone());
+        Ok(request)
+    }
+
+    fn ensure_can_decide(&self, project: &Project, actor_id: Uuid, role: Option<ProjectRole>) -> Result<(), JoinRequestError> {
+        let allowed = match role {
+            Some(role) => self.access.can_grant(project, actor_id, role),
+            None => self.access.can(project, Some(actor_id), Action::ManageMembers),
+        };
+        if allowed { Ok(()) } else { Err(JoinRequestError::Forbidden) }
+    }
+
+    /// Demandes du projet, les plus anciennes d'abord, éventuellement filtrées par statut.
+    pub fn list(
+        &self,
+        project: &Project,
+        actor_id: Uuid,
+        status: Option<JoinRequestStatus>,
+    ) -> Result<Vec<JoinRequest>, JoinRequestError> {
+        self.ensure_can_decide(project, actor_id, None)?;
+        let mut requests: Vec<JoinRequest> = self
+            .requests
+            .list_by_project(project.id)
+            .into_iter()
+            .filter(|r| status.is_none_or(|s| r.status == s))
+            .collect();
+        requests.sort_by_key(|r| r.created_at);
+        Ok(requests)
+    }
+
+    fn pending(&self, project: &Project, request_id: Uuid) -> Result<JoinRequest, JoinRequestError> {
+        let request = self
+            .requests
+            .get(request_id)
+            .filter(|r| r.project_id == project.id)
+            .ok_or(JoinRequestError::NotFound)?;
+        if request.status != JoinRequestStatus::Pending {
+            return Err(JoinRequestError::AlreadyDecided);
+        }
+        Ok(request)
+    }
+
+    fn decide(&self, mut request: JoinRequest, actor_id: Uuid, status: JoinRequestStatus, role: Option<ProjectRole>) -> JoinRequest {
+        request.status = status;
+        request.role = role;
+        request.decided_by = Some(actor_id);
+        request.decided_at = Some(Utc::now());
+        self.requests.save(request.clone());
+        request
+    }
+
+    fn notify(&self, project: &Project, request: &JoinRequest, kind: NotificationKind, message: String) {
+        self.notifications.push(Notification {
+            id: Uuid::new_v4(),
+            user_id: request.user_id,
+            kind,
+            project_id: project.id,
+            issue_id: None,
+            message,
+            read: false,
+            created_at: Utc::now(),
+        });
+    }
+
+    /// Accepte la demande : le demandeur devient membre via `member::add_member`.
+    pub fn approve(
+        &self,
+        project: &Project,
+        actor_id: Uuid,
+        request_id: Uuid,
+        role: ProjectRole,
+    ) -> Result<ProjectMember, JoinRequestError> {
+        self.ensure_can_decide(project, actor_id, Some(role))?;
+        let request = self.pending(project, request_id)?;
+        // Entré entre-temps par une invitation : son rôle actuel est conservé
+        if self.access.role(project, request.user_id).is_some() {
+            return Err(JoinRequestError::AlreadyMember);
+        }
+        let member = member::add_member(self.users, project, request.user_id, role).map_err(JoinRequestError::Member)?;
+        let request = self.decide(request, actor_id, JoinRequestStatus::Approved, Some(role));
+        let message = format!("Your request to join \"{}\" was approved as {:?}", project.name, role);
+        self.notify(project, &request, NotificationKind::JoinRequestApproved, message);
+        Ok(member)
+    }
+
+    pub fn reject(&self, project: &Project, actor_id: Uuid, request_id: Uuid) -> Result<JoinRequest, JoinRequestError> {
+        self.ensure_can_decide(project, actor_id, None)?;
+        let request = self.pending(project, request_id)?;
+        let request = self.decide(request, actor_id, JoinRequestStatus::Rejected, None);
+        let message = format!("Your request to join \"{}\" was declined", project.name);
+        self.notify(project, &request, NotificationKind::JoinRequestRejected, message);
+        Ok(request)
+    }
+}
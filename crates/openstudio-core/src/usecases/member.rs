@@ -0,0 +1,115 @@
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::models::project::{OwnerKind, Project};
+use crate::models::user::{ProjectMember, ProjectRole};
+use crate::repositories::in_memory_redirect::InMemoryProjectRedirectRepo;
+use crate::repositories::in_memory_user::InMemoryUserRepo;
+use crate::repositories::project_repository::ProjectRepository;
+use crate::usecases::organization::OrgContext;
+use crate::usecases::project::move_project;
+
+#[derive(Debug, PartialEq)]
+pub enum MemberError {
+    /// L'opération laisserait le projet sans propriétaire.
+    LastOwner,
+    /// Le propriétaire du namespace doit d'abord transférer le projet.
+    NamespaceOwner,
+    /// Seul le titulaire du namespace peut transférer le projet.
+    Forbidden,
+    Storage(String),
+}
+
+pub fn owners(users: &InMemoryUserRepo, project_id: Uuid) -> Vec<Uuid> {
+    users
+        .list_members(project_id)
+        .into_iter()
+        .filter(|m| m.role == ProjectRole::Owner)
+        .map(|m| m.user_id)
+        .collect()
+}
+
+fn is_owner(users: &InMemoryUserRepo, project_id: Uuid, user_id: Uuid) -> bool {
+    owners(users, project_id).contains(&user_id)
+}
+
+/// Ajoute un membre, ou change son rôle s'il l'est déjà.
+pub fn add_member(
+    users: &InMemoryUserRepo,
+    project: &Project,
+    user_id: Uuid,
+    role: ProjectRole,
+) -> Result<ProjectMember, MemberError> {
+    if role != ProjectRole::Owner && is_owner(users, project.id, user_id) {
+        ensure_can_lose_owner(users, project, user_id)?;
+    }
+    let joined_at = users
+        .list_members(project.id)
+        .into_iter()
+        .find(|m| m.user_id == user_id)
+        .map(|m| m.joined_at)
+        .unwrap_or_else(Utc::now);
+    let member = ProjectMember { user_id, project_id: project.id, role, joined_at };
+    users.remove_member(project.id, user_id);
+    users.add_member(member.clone());
+    Ok(member)
+}
+
+/// Retire un membre. S'il était l'assigné par défaut des nouvelles issues, le projet n'en a
+/// plus.
+pub fn remove_member(
+    repo: &dyn ProjectRepository,
+    users: &InMemoryUserRepo,
+    project: &Project,
+    user_id: Uuid,
+) -> Result<(), MemberError> {
+    if is_owner(users, project.id, user_id) {
+        ensure_can_lose_owner(users, project, user_id)?;
+    }
+    users.remove_member(project.id, user_id);
+    if project.settings.default_assignee_id == Some(user_id) {
+        let mut project = project.clone();
+        project.settings.default_assignee_id = None;
+        repo.update(project).map_err(|e| MemberError::Storage(e.to_string()))?;
+    }
+    Ok(())
+}
+
+fn ensure_can_lose_owner(users: &InMemoryUserRepo, project: &Project, user_id: Uuid) -> Result<(), MemberError> {
+    if project.owner_id == Some(user_id) {
+        return Err(MemberError::NamespaceOwner);
+    }
+    if owners(users, project.id).len() <= 1 {
+        return Err(MemberError::LastOwner);
+    }
+    Ok(())
+}
+
+/// Transfère le projet à `new_owner_id` : il devient propriétaire et le projet passe dans son
+/// namespace, l'ancien propriétaire reste membre en tant que `Maintainer`. Seul le titulaire du
+/// namespace transfère un projet personnel, un co-propriétaire ne peut pas le lui retirer. Un
+/// projet d'organisation n'en sort que par un `Owner` ou un `Admin` de celle-ci.
+pub fn transfer_ownership(
+    repo: &dyn ProjectRepository,
+    redirects: &InMemoryProjectRedirectRepo,
+    orgs: &OrgContext<'_>,
+    project: Project,
+    actor_id: Uuid,
+    new_owner_id: Uuid,
+) -> Result<Project, MemberError> {
+    let users = orgs.users;
+    let allowed = match project.owner_kind {
+        OwnerKind::Organization => orgs.can_release(&project, actor_id),
+        OwnerKind::User => project.owner_id == Some(actor_id),
+    };
+    if !allowed {
+        return Err(MemberError::Forbidden);
+    }
+    if actor_id == new_owner_id {
+        return Ok(project);
+    }
+    let project = move_project(repo, redirects, project, new_owner_id).map_err(|e| MemberError::Storage(e.to_string()))?;
+    add_member(users, &project, new_owner_id, ProjectRole::Owner)?;
+    add_member(users, &project, actor_id, ProjectRole::Maintainer)?;
+    Ok(project)
+}
@@ -0,0 +1,96 @@
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::models::issue::Issue;
+use crate::models::notification::{Mention, Notification, NotificationKind};
+use crate::models::project::Project;
+use crate::repositories::in_memory_notification::{InMemoryMentionRepo, InMemoryNotificationRepo};
+use crate::repositories::in_memory_user::InMemoryUserRepo;
+use crate::usecases::access::ProjectAccess;
+
+fn is_username_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '-' || c == '.'
+}
+
+/// Extrait les `@username` d'un texte, sans doublons et dans l'ordre d'apparition.
+/// Un `@` collé à un mot (adresse e-mail) n'est pas une mention.
+pub fn parse_mentions(text: &str) -> Vec<String> {
+    let mut usernames: Vec<String> = Vec::new();
+    let mut previous: Option<char> = None;
+    for (i, c) in text.char_indices() {
+        if c == '@' && !previous.is_some_and(|p| is_username_char(p) || p == '@') {
+            let rest = &text[i + 1..];
+            let end = rest.find(|ch: char| !is_username_char(ch)).unwrap_or(rest.len());
+            // Un point final appartient à la phrase, pas au nom
+            let name = rest[..end].trim_end_matches('.');
+            if !name.is_empty() && !usernames.iter().any(|u| u == name) {
+                usernames.push(name.to_string());
+            }
+        }
+        previous = Some(c);
+    }
+    usernames
+}
+
+pub struct MentionContext<'a> {
+    pub users: &'a InMemoryUserRepo,
+    pub access: &'a ProjectAccess,
+    pub mentions: &'a InMemoryMentionRepo,
+    pub notifications: &'a InMemoryNotificationRepo,
+}
+
+impl MentionContext<'_> {
+    /// Résout les mentions de `text`, enregistre les liens et notifie chaque utilisateur
+    /// mentionné pour la première fois sur cette issue. L'auteur ne se notifie pas lui-même.
+    /// Renvoie les mentions nouvellement enregistrées.
+    pub fn process(
+        &self,
+        project: Option<&Project>,
+        issue: &Issue,
+        comment_id: Option<Uuid>,
+        text: &str,
+        author_id: Option<Uuid>,
+    ) -> Vec<Mention> {
+        let existing = self.mentions.list_by_issue(issue.id);
+        let mut recorded = Vec::new();
+        for username in parse_mentions(text) {
+            let Some(user) = self.users.find_by_username(&username) else {
+                continue;
+            };
+            // Mentionner quelqu'un qui n'a pas accès à un projet privé ne doit rien lui révéler
+            if project.is_some_and(|p| !self.access.can_view(p, Some(user.id))) {
+                continue;
+            }
+            let already_linked = existing
+                .iter()
+                .any(|m| m.user_id == user.id && m.comment_id == comment_id);
+            if already_linked {
+                continue;
+            }
+            let already_notified = existing.iter().any(|m| m.user_id == user.id);
+            let mention = Mention {
+                issue_id: issue.id,
+                comment_id,
+                user_id: user.id,
+                username: user.username.clone(),
+                created_at: Utc::now(),
+            };
+            self.mentions.add(mention.clone());
+            if !already_notified && author_id != Some(user.id) {
+                let source = if comment_id.is_some() { "a comment on" } else { "issue" };
+                self.notifications.push(Notification {
+                    id: Uuid::new_v4(),
+                    user_id: user.id,
+                    kind: NotificationKind::Mention,
+                    project_id: issue.project_id,
+                    issue_id: Some(issue.id),
+                    message: format!("You were mentioned in {} \"{}\"", source, issue.title),
+                    read: false,
+                    created_at: Utc::now(),
+                });
+            }
+            recorded.push(mention);
+        }
+        recorded
+    }
+}
@@ -1 +1,23 @@
-pub mod project;
\ No newline at end of file
+pub mod project;
+pub mod access;
+pub mod policy;
+pub mod explore;
+pub mod member;
+pub mod organization;
+pub mod issue;
+pub mod issue_io;
+pub mod invitation;
+pub mod join_request;
+pub mod mention;
+pub mod stats;
+pub mod subscription;
+pub mod time_tracking;
+pub mod wiki;
+pub mod timeline;
+pub mod session;
+pub mod access_token;
+pub mod identity;
+pub mod password_reset;
+pub mod email_verification;
+pub mod throttle;
+pub mod token_hash;
@@ -0,0 +1,239 @@
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::models::organization::{OrgMember, OrgRole, Organization, Team, TeamGrant};
+use crate::models::project::{OwnerKind, Project};
+use crate::models::user::ProjectRole;
+use crate::repositories::in_memory_org::InMemoryOrgRepo;
+use crate::repositories::in_memory_redirect::InMemoryProjectRedirectRepo;
+use crate::repositories::in_memory_user::InMemoryUserRepo;
+use crate::repositories::project_repository::ProjectRepository;
+use crate::usecases::project::{create_project, slugify, unique_slug};
+
+const MAX_ORG_SLUG_LEN: usize = 39;
+
+#[derive(Debug, PartialEq)]
+pub enum OrgError {
+    NotFound,
+    /// Le slug doit déjà être sous forme normalisée (minuscules, chiffres et tirets).
+    InvalidSlug,
+    /// Slug déjà pris par une organisation, une équipe ou un nom d'utilisateur.
+    SlugTaken,
+    Forbidden,
+    /// L'opération laisserait l'organisation sans propriétaire.
+    LastOwner,
+    /// Seuls les membres de l'organisation peuvent rejoindre une équipe.
+    NotOrgMember,
+    /// Une équipe ne reçoit des droits que sur les projets de son organisation.
+    ProjectNotInOrg,
+    Storage(String),
+}
+
+fn validate_slug(slug: &str) -> Result<(), OrgError> {
+    if slug.is_empty() || slug.len() > MAX_ORG_SLUG_LEN || slugify(slug) != slug {
+        return Err(OrgError::InvalidSlug);
+    }
+    Ok(())
+}
+
+/// Utilisateurs et organisations partagent les mêmes namespaces (`/projects/{namespace}/…`) :
+/// un nom pris par l'un ne peut pas servir à l'autre.
+pub fn namespace_taken(users: &InMemoryUserRepo, orgs: &InMemoryOrgRepo, name: &str) -> bool {
+    users.find_by_username(name).is_some() || orgs.find_by_slug(&name.to_lowercase()).is_some()
+}
+
+pub struct OrgContext<'a> {
+    pub orgs: &'a InMemoryOrgRepo,
+    pub users: &'a InMemoryUserRepo,
+}
+
+impl OrgContext<'_> {
+    /// Crée l'organisation ; son créateur en devient `Owner`.
+    pub fn create_organization(
+        &self,
+        creator_id: Uuid,
+        slug: &str,
+        name: &str,
+        description: &str,
+    ) -> Result<Organization, OrgError> {
+        validate_slug(slug)?;
+        if namespace_taken(self.users, self.orgs, slug) {
+            return Err(OrgError::SlugTaken);
+        }
+        let org = Organization {
+            id: Uuid::new_v4(),
+            slug: slug.to_string(),
+            name: name.to_string(),
+            description: description.to_string(),
+            created_at: Utc::now(),
+        };
+        self.orgs.save(org.clone());
+        self.orgs.set_member(OrgMember {
+            org_id: org.id,
+            user_id: creator_id,
+            role: OrgRole::Owner,
+            joined_at: org.created_at,
+        });
+        Ok(org)
+    }
+
+    pub fn is_admin(&self, org_id: Uuid, user_id: Uuid) -> bool {
+        matches!(self.orgs.member_role(org_id, user_id), Some(OrgRole::Owner | OrgRole::Admin))
+    }
+
+    /// Sortir un projet d'une organisation (déplacement ou transfert) est réservé à ses
+    /// `Owner` et `Admin`, quel que soit le rôle obtenu sur le projet lui-même.
+    pub fn can_release(&self, project: &Project, actor_id: Uuid) -> bool {
+        match (project.owner_kind, project.owner_id) {
+            (OwnerKind::Organization, Some(org_id)) => self.is_admin(org_id, actor_id),
+            _ => true,
+        }
+    }
+
+    fn ensure_admin(&self, org: &Organization, actor_id: Uuid) -> Result<(), OrgError> {
+        if self.is_admin(org.id, actor_id) { Ok(()) } else { Err(OrgError::Forbidden) }
+    }
+
+    fn is_last_owner(&self, org_id: Uuid, user_id: Uuid) -> bool {
+        let owners: Vec<Uuid> = self
+            .orgs
+            .list_members(org_id)
+            .iter()
+            .filter(|m| m.role == OrgRole::Owner)
+            .map(|m| m.user_id)
+            .collect();
+        owners == [user_id]
+    }
+
+    /// Ajoute un membre ou change son rôle. Seul un `Owner` peut nommer ou modifier un `Owner`.
+    pub fn set_member(&self, org: &Organization, actor_id: Uuid, user_id: Uuid, role: OrgRole) -> Result<(), OrgError> {
+        self.ensure_admin(org, actor_id)?;
+        if self.users.get_user(user_id).is_none() {
+            return Err(OrgError::NotFound);
+        }
+        let current = self.orgs.member_role(org.id, user_id);
+        let touches_owner = role == OrgRole::Owner || current == Some(OrgRole::Owner);
+        if touches_owner && self.orgs.member_role(org.id, actor_id) != Some(OrgRole::Owner) {
+            return Err(OrgError::Forbidden);
+        }
+        if current == Some(OrgRole::Owner) && role != OrgRole::Owner && self.is_last_owner(org.id, user_id) {
+            return Err(OrgError::LastOwner);
+        }
+        self.orgs.set_member(OrgMember { org_id: org.id, user_id, role, joined_at: Utc::now() });
+        Ok(())
+    }
+
+    /// Retire un membre, ou permet à un membre de partir de lui-même.
+    pub fn remove_member(&self, org: &Organization, actor_id: Uuid, user_id: Uuid) -> Result<(), OrgError> {
+        let Some(current) = self.orgs.member_role(org.id, user_id) else {
+            return Err(OrgError::NotFound);
+        };
+        if actor_id != user_id {
+            self.ensure_admin(org, actor_id)?;
+            if current == OrgRole::Owner && self.orgs.member_role(org.id, actor_id) != Some(OrgRole::Owner) {
+                return Err(OrgError::Forbidden);
+            }
+        }
+        if current == OrgRole::Owner && self.is_last_owner(org.id, user_id) {
+            return Err(OrgError::LastOwner);
+        }
+        self.orgs.remove_member(org.id, user_id);
+        Ok(())
+    }
+
+    pub fn create_team(&self, org: &Organization, actor_id: Uuid, slug: &str, name: &str) -> Result<Team, OrgError> {
+        self.ensure_admin(org, actor_id)?;
+        validate_slug(slug)?;
+        if self.orgs.find_team(org.id, slug).is_some() {
+            return Err(OrgError::SlugTaken);
+        }
+        let team = Team {
+            id: Uuid::new_v4(),
+            org_id: org.id,
+            slug: slug.to_string(),
+            name: name.to_string(),
+            members: Vec::new(),
+            created_at: Utc::now(),
+        };
+        self.orgs.save_team(team.clone());
+        Ok(team)
+    }
+
+    pub fn add_team_member(&self, org: &Organization, actor_id: Uuid, mut team: Team, user_id: Uuid) -> Result<Team, OrgError> {
+        self.ensure_admin(org, actor_id)?;
+        if self.orgs.member_role(org.id, user_id).is_none() {
+            return Err(OrgError::NotOrgMember);
+        }
+        if !team.members.contains(&user_id) {
+            team.members.push(user_id);
+            self.orgs.save_team(team.clone());
+        }
+        Ok(team)
+    }
+
+    pub fn remove_team_member(&self, org: &Organization, actor_id: Uuid, mut team: Team, user_id: Uuid) -> Result<Team, OrgError> {
+        self.ensure_admin(org, actor_id)?;
+        team.members.retain(|id| *id != user_id);
+        self.orgs.save_team(team.clone());
+        Ok(team)
+    }
+
+    /// Accorde `role` à l'équipe sur plusieurs projets à la fois. Tous les projets doivent
+    /// appartenir à l'organisation, sinon rien n'est accordé. Comme pour `set_member`, seul un
+    /// `Owner` de l'organisation peut accorder `Owner`.
+    pub fn grant_team(
+        &self,
+        projects: &dyn ProjectRepository,
+        org: &Organization,
+        actor_id: Uuid,
+        team: &Team,
+        project_ids: &[Uuid],
+        role: ProjectRole,
+    ) -> Result<Vec<TeamGrant>, OrgError> {
+        self.ensure_admin(org, actor_id)?;
+        if role == ProjectRole::Owner && self.orgs.member_role(org.id, actor_id) != Some(OrgRole::Owner) {
+            return Err(OrgError::Forbidden);
+        }
+        for project_id in project_ids {
+            let project = projects.get_by_id(*project_id).map_err(|e| OrgError::Storage(e.to_string()))?;
+            if !project.is_some_and(|p| p.owner_kind == OwnerKind::Organization && p.owner_id == Some(org.id)) {
+                return Err(OrgError::ProjectNotInOrg);
+            }
+        }
+        let grants: Vec<TeamGrant> = project_ids
+            .iter()
+            .map(|project_id| TeamGrant { team_id: team.id, project_id: *project_id, role })
+            .collect();
+        for grant in &grants {
+            self.orgs.grant(grant.clone());
+        }
+        Ok(grants)
+    }
+
+    pub fn revoke_team(&self, org: &Organization, actor_id: Uuid, team: &Team, project_id: Uuid) -> Result<(), OrgError> {
+        self.ensure_admin(org, actor_id)?;
+        if self.orgs.revoke(team.id, project_id) { Ok(()) } else { Err(OrgError::NotFound) }
+    }
+
+    /// Crée un projet dans le namespace de l'organisation. Son créateur n'y reçoit pas de rôle
+    /// direct : ses droits découlent de son rôle dans l'organisation.
+    pub fn create_project(
+        &self,
+        repo: &dyn ProjectRepository,
+        redirects: &InMemoryProjectRedirectRepo,
+        org: &Organization,
+        actor_id: Uuid,
+        name: &str,
+        description: &str,
+    ) -> Result<Project, OrgError> {
+        self.ensure_admin(org, actor_id)?;
+        let storage = |e: anyhow::Error| OrgError::Storage(e.to_string());
+        let mut project = create_project(name, description);
+        project.owner_id = Some(org.id);
+        project.owner_kind = OwnerKind::Organization;
+        project.slug = unique_slug(repo, org.id, &project.slug, None).map_err(storage)?;
+        redirects.remove(org.id, &project.slug);
+        repo.save(project.clone()).map_err(storage)?;
+        Ok(project)
+    }
+}
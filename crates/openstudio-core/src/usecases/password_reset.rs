@@ -0,0 +1,93 @@
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use crate::models::password_reset::PasswordResetToken;
+use crate::models::user::User;
+use crate::repositories::in_memory_password_reset::InMemoryPasswordResetRepo;
+use crate::repositories::in_memory_user::InMemoryUserRepo;
+use crate::usecases::throttle::check_send_rate;
+use crate::usecases::token_hash::hash_token;
+
+pub const RESET_TOKEN_MINUTES: i64 = 60;
+pub const MIN_PASSWORD_LEN: usize = 8;
+
+#[derive(Debug, PartialEq)]
+pub enum PasswordResetError {
+    /// Jeton inconnu ou remplacé par une demande plus récente, ou compte supprimé depuis.
+    InvalidToken,
+    Expired,
+    AlreadyUsed,
+    PasswordTooShort,
+    /// Le hachage du nouveau mot de passe a échoué : ni le jeton ni le compte n'ont changé.
+    HashFailed,
+}
+
+pub struct PasswordResetContext<'a> {
+    pub resets: &'a InMemoryPasswordResetRepo,
+    pub users: &'a InMemoryUserRepo,
+}
+
+impl PasswordResetContext<'_> {
+    /// Crée une demande pour le compte portant cette adresse et renvoie le jeton en clair, à
+    /// envoyer par email. Une nouvelle demande annule les précédentes. `None` si aucun compte
+    /// ne correspond, ou si le compte a reçu trop de liens récemment (mêmes limites que pour la
+    /// vérification d'adresse) : l'appelant ne doit pas le révéler.
+    pub fn request(&self, email: &str) -> Option<(User, String)> {
+        let email = email.trim();
+        let user = self.users.list_users().into_iter().find(|u| !email.is_empty() && u.email.eq_ignore_ascii_case(email))?;
+        let now = Utc::now();
+        let sent: Vec<_> = self.resets.created_since(user.id, now - Duration::hours(24)).iter().map(|t| t.created_at).collect();
+        check_send_rate(&sent, now).ok()?;
+        let raw = format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+        self.resets.replace_unused_for_user(user.id, now);
+        self.resets.save(PasswordResetToken {
+            id: Uuid::new_v4(),
+            user_id: user.id,
+            token_hash: hash_token(&raw),
+            created_at: now,
+            expires_at: now + Duration::minutes(RESET_TOKEN_MINUTES),
+            used_at: None,
+            replaced_at: None,
+        });
+        Some((user, raw))
+    }
+
+    /// Consomme le jeton et remplace le mot de passe par `hash_password(new_password)`.
+    /// L'adresse du compte est du même coup considérée comme vérifiée. Si `hash_password`
+    /// échoue, le jeton reste utilisable.
+    pub fn reset(
+        &self,
+        raw: &str,
+        new_password: &str,
+        hash_password: impl FnOnce(&str) -> Option<String>,
+    ) -> Result<User, PasswordResetError> {
+        let now = Utc::now();
+        if new_password.chars().count() < MIN_PASSWORD_LEN {
+            return Err(PasswordResetError::PasswordTooShort);
+        }
+        let token = self
+            .resets
+            .find_by_token_hash(&hash_token(raw))
+            .filter(|t| t.replaced_at.is_none())
+            .ok_or(PasswordResetError::InvalidToken)?;
+        if token.used_at.is_some() {
+            return Err(PasswordResetError::AlreadyUsed);
+        }
+        if token.is_expired(now) {
+            return Err(PasswordResetError::Expired);
+        }
+        let mut user = self.users.get_user(token.user_id).ok_or(PasswordResetError::InvalidToken)?;
+        let password = hash_password(new_password).ok_or(PasswordResetError::HashFailed)?;
+        match self.resets.mark_used(token.id, now) {
+            Ok(_) => {},
+            Err(Some(stored)) if stored.replaced_at.is_none() => return Err(PasswordResetError::AlreadyUsed),
+            Err(_) => return Err(PasswordResetError::InvalidToken),
+        }
+        user.password = password;
+        // Le lien a été reçu à cette adresse
+        user.email_verified = true;
+        user.updated_at = now;
+        self.users.save_user(user.clone());
+        Ok(user)
+    }
+}
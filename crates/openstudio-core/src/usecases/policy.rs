@@ -0,0 +1,58 @@
+use serde::Serialize;
+
+use crate::models::user::ProjectRole;
+
+/// Actions soumises à autorisation sur un projet et ses contenus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    ViewProject,
+    OpenIssue,
+    Comment,
+    EditIssue,
+    LogTime,
+    EditWiki,
+    DeleteIssue,
+    ImportIssues,
+    EditProject,
+    ManageSettings,
+    ManageMembers,
+    ChangeStatus,
+    TransferProject,
+    DeleteProject,
+    /// Accorder le rôle `Owner`, par ajout direct, invitation ou demande d'accès.
+    GrantOwner,
+}
+
+/// Rôle minimal requis pour une action ; chaque rôle hérite des droits des rôles inférieurs.
+///
+/// - `Viewer` : voir le projet, ouvrir une issue, commenter ;
+/// - `Contributor` : modifier une issue, saisir du temps, éditer le wiki ;
+/// - `Maintainer` : supprimer ou importer des issues, modifier le projet, gérer les membres ;
+/// - `Owner` : changer le statut, transférer ou supprimer le projet, nommer un owner.
+pub fn minimum_role(action: Action) -> ProjectRole {
+    match action {
+        Action::ViewProject | Action::OpenIssue | Action::Comment => ProjectRole::Viewer,
+        Action::EditIssue | Action::LogTime | Action::EditWiki => ProjectRole::Contributor,
+        Action::DeleteIssue
+        | Action::ImportIssues
+        | Action::EditProject
+        | Action::ManageSettings
+        | Action::ManageMembers => ProjectRole::Maintainer,
+        Action::ChangeStatus | Action::TransferProject | Action::DeleteProject | Action::GrantOwner => ProjectRole::Owner,
+    }
+}
+
+pub fn allows(role: ProjectRole, action: Action) -> bool {
+    rank(role) <= rank(minimum_role(action))
+}
+
+/// Du rôle le plus élevé (0) au plus faible.
+pub(crate) fn rank(role: ProjectRole) -> u8 {
+    match role {
+        ProjectRole::Owner => 0,
+        ProjectRole::Maintainer => 1,
+        ProjectRole::Contributor => 2,
+        ProjectRole::Viewer => 3,
+    }
+}
@@ -1,14 +1,354 @@
-use crate::models::{project::Project, project_status::{ProjectStatus, Visibility}};
+use crate::models::{project::{OwnerKind, Project}, project_status::{ProjectStatus, Visibility}, user::{ProjectMember, ProjectRole}};
+use crate::models::issue::{Issue, IssueStatus};
+use crate::models::planning::Milestone;
+use crate::models::project_settings::ProjectSettings;
+use crate::repositories::in_memory_redirect::InMemoryProjectRedirectRepo;
+use crate::repositories::in_memory_user::InMemoryUserRepo;
+use crate::repositories::issue_repository::IssueRepository;
+use crate::repositories::project_repository::ProjectRepository;
+use crate::usecases::access::ProjectAccess;
+use crate::usecases::policy::Action;
 use chrono::Utc;
 use uuid::Uuid;
 
+/// Segments déjà utilisés par des routes `/projects/{id}/...` : un projet portant ce slug
+/// serait masqué par la route.
+pub const RESERVED_SLUGS: &[&str] = &[
+    "issues", "watch", "watchers", "time-report", "move",
+    "publish", "archive", "unarchive", "transfer", "fork", "stats", "wiki",
+    "star", "stargazers", "settings", "invitations", "join-requests",
+];
+
+const MAX_SLUG_LEN: usize = 64;
+const MAX_TOPICS: usize = 20;
+const MAX_TOPIC_LEN: usize = 35;
+
 pub fn create_project(name: &str, description: &str) -> Project {
     Project {
         id: Uuid::new_v4(),
+        owner_id: None,
+        owner_kind: OwnerKind::User,
         name: name.to_string(),
+        slug: slugify(name),
         description: description.to_string(),
+        topics: Vec::new(),
+        language: None,
+        is_template: false,
+        forked_from: None,
+        labels: Vec::new(),
+        workflow: Vec::new(),
+        issue_templates: Vec::new(),
+        milestones: Vec::new(),
+        settings: ProjectSettings::default(),
         created_at: Utc::now(),
         visibility: Visibility::Private,
         status: ProjectStatus::Draft,
     }
 }
+
+fn fold_accent(c: char) -> char {
+    match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' => 'a',
+        'ç' => 'c',
+        'è' | 'é' | 'ê' | 'ë' => 'e',
+        'ì' | 'í' | 'î' | 'ï' => 'i',
+        'ñ' => 'n',
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' => 'o',
+        'ù' | 'ú' | 'û' | 'ü' => 'u',
+        'ý' | 'ÿ' => 'y',
+        other => other,
+    }
+}
+
+/// Transforme un nom en slug utilisable dans une URL : minuscules ASCII, chiffres et tirets.
+pub fn slugify(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.to_lowercase().chars().map(fold_accent) {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.truncate(MAX_SLUG_LEN);
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() { "project".to_string() } else { slug.to_string() }
+}
+
+/// Normalise une liste de sujets : minuscules, espaces remplacés par des tirets, doublons retirés.
+/// Un sujet vide, trop long ou contenant d'autres caractères est refusé.
+pub fn normalize_topics(topics: &[String]) -> Result<Vec<String>, String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for topic in topics {
+        let topic = topic.trim().to_lowercase().replace(' ', "-");
+        let valid = !topic.is_empty()
+            && topic.len() <= MAX_TOPIC_LEN
+            && !topic.starts_with('-')
+            && topic.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+        if !valid {
+            return Err(format!("invalid topic '{}'", topic));
+        }
+        if !normalized.contains(&topic) {
+            normalized.push(topic);
+        }
+    }
+    if normalized.len() > MAX_TOPICS {
+        return Err(format!("a project can have at most {} topics", MAX_TOPICS));
+    }
+    Ok(normalized)
+}
+
+/// Premier slug libre dans le namespace de `owner_id` : `base`, puis `base-2`, `base-3`...
+/// `project_id` permet à un projet de conserver son propre slug.
+pub fn unique_slug(
+    repo: &dyn ProjectRepository,
+    owner_id: Uuid,
+    base: &str,
+    project_id: Option<Uuid>,
+) -> anyhow::Result<String> {
+    let mut candidate = base.to_string();
+    let mut n = 2;
+    loop {
+        let taken = RESERVED_SLUGS.contains(&candidate.as_str())
+            || repo
+                .find_by_slug(owner_id, &candidate)?
+                .is_some_and(|p| Some(p.id) != project_id);
+        if !taken {
+            return Ok(candidate);
+        }
+        candidate = format!("{}-{}", base, n);
+        n += 1;
+    }
+}
+
+/// Crée un projet dans le namespace de `owner_id`, avec un slug unique. Le créateur en
+/// devient membre avec le rôle `Owner`.
+pub fn create_owned_project(
+    repo: &dyn ProjectRepository,
+    redirects: &InMemoryProjectRedirectRepo,
+    users: &InMemoryUserRepo,
+    owner_id: Uuid,
+    name: &str,
+    description: &str,
+) -> anyhow::Result<Project> {
+    let mut project = create_project(name, description);
+    project.owner_id = Some(owner_id);
+    project.slug = unique_slug(repo, owner_id, &project.slug, None)?;
+    // Le nouveau projet prend le pas sur une ancienne adresse
+    redirects.remove(owner_id, &project.slug);
+    repo.save(project.clone())?;
+    users.add_member(ProjectMember {
+        user_id: owner_id,
+        project_id: project.id,
+        role: ProjectRole::Owner,
+        joined_at: project.created_at,
+    });
+    Ok(project)
+}
+
+/// Recalcule le slug après un changement de nom ou de propriétaire et laisse une
+/// redirection depuis l'ancienne adresse.
+fn relocate(
+    repo: &dyn ProjectRepository,
+    redirects: &InMemoryProjectRedirectRepo,
+    mut project: Project,
+    owner_id: Uuid,
+    owner_kind: OwnerKind,
+) -> anyhow::Result<Project> {
+    let previous = project.owner_id.map(|owner| (owner, project.slug.clone()));
+    project.slug = unique_slug(repo, owner_id, &slugify(&project.name), Some(project.id))?;
+    project.owner_id = Some(owner_id);
+    project.owner_kind = owner_kind;
+    if previous.as_ref() != Some(&(owner_id, project.slug.clone())) {
+        if let Some((old_owner, old_slug)) = previous {
+            redirects.add(old_owner, &old_slug, project.id);
+        }
+        redirects.remove(owner_id, &project.slug);
+    }
+    repo.update(project.clone())?;
+    Ok(project)
+}
+
+pub fn rename_project(
+    repo: &dyn ProjectRepository,
+    redirects: &InMemoryProjectRedirectRepo,
+    mut project: Project,
+    name: &str,
+) -> anyhow::Result<Project> {
+    project.name = name.to_string();
+    match project.owner_id {
+        Some(owner_id) => {
+            let owner_kind = project.owner_kind;
+            relocate(repo, redirects, project, owner_id, owner_kind)
+        },
+        None => {
+            project.slug = slugify(name);
+            repo.update(project.clone())?;
+            Ok(project)
+        }
+    }
+}
+
+pub fn move_project(
+    repo: &dyn ProjectRepository,
+    redirects: &InMemoryProjectRedirectRepo,
+    project: Project,
+    new_owner_id: Uuid,
+) -> anyhow::Result<Project> {
+    relocate(repo, redirects, project, new_owner_id, OwnerKind::User)
+}
+
+/// Déplace le projet dans le namespace d'une organisation.
+pub fn move_project_to_org(
+    repo: &dyn ProjectRepository,
+    redirects: &InMemoryProjectRedirectRepo,
+    project: Project,
+    org_id: Uuid,
+) -> anyhow::Result<Project> {
+    relocate(repo, redirects, project, org_id, OwnerKind::Organization)
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ForkOptions {
+    /// Nom du nouveau projet, celui de la source par défaut.
+    pub name: Option<String>,
+    pub include_open_issues: bool,
+}
+
+/// Crée dans le namespace de `owner_id` une copie de `source` : description, sujets, labels,
+/// workflow, modèles d'issues, jalons et réglages, plus les issues ouvertes si demandé. La copie
+/// démarre en brouillon, avec la visibilité par défaut de la source, et garde un lien vers elle.
+pub fn fork_project(
+    repo: &dyn ProjectRepository,
+    redirects: &InMemoryProjectRedirectRepo,
+    users: &InMemoryUserRepo,
+    issues: &dyn IssueRepository,
+    source: &Project,
+    owner_id: Uuid,
+    options: &ForkOptions,
+) -> anyhow::Result<Project> {
+    let name = options.name.as_deref().unwrap_or(&source.name);
+    let mut fork = create_owned_project(repo, redirects, users, owner_id, name, &source.description)?;
+    fork.topics = source.topics.clone();
+    fork.language = source.language.clone();
+    fork.forked_from = Some(source.id);
+    fork.labels = source.labels.clone();
+    fork.workflow = source.workflow.clone();
+    fork.issue_templates = source.issue_templates.clone();
+    // L'assigné par défaut n'est pas membre du fork
+    fork.settings = ProjectSettings { default_assignee_id: None, ..source.settings.clone() };
+    fork.visibility = fork.settings.default_visibility.clone();
+    fork.milestones = source
+        .milestones
+        .iter()
+        .map(|m| Milestone { id: Uuid::new_v4(), ..m.clone() })
+        .collect();
+    repo.update(fork.clone())?;
+
+    if options.include_open_issues {
+        let now = Utc::now();
+        for issue in issues.list_by_project(source.id).map_err(anyhow::Error::msg)? {
+            if issue.status == IssueStatus::Closed {
+                continue;
+            }
+            // L'assigné n'est pas membre du nouveau projet : la copie revient au créateur du fork
+            issues
+                .save(Issue {
+                    id: Uuid::new_v4(),
+                    project_id: fork.id,
+                    author_id: Some(owner_id),
+                    assignee_id: None,
+                    created_at: now,
+                    updated_at: now,
+                    ..issue
+                })
+                .map_err(anyhow::Error::msg)?;
+        }
+    }
+    Ok(fork)
+}
+
+pub enum SlugLookup {
+    Found(Project),
+    /// L'adresse demandée est une ancienne adresse du projet.
+    Moved(Project),
+}
+
+pub fn find_project_by_slug(
+    repo: &dyn ProjectRepository,
+    redirects: &InMemoryProjectRedirectRepo,
+    owner_id: Uuid,
+    slug: &str,
+) -> anyhow::Result<Option<SlugLookup>> {
+    if let Some(project) = repo.find_by_slug(owner_id, slug)? {
+        return Ok(Some(SlugLookup::Found(project)));
+    }
+    match redirects.find(owner_id, slug) {
+        Some(redirect) => Ok(repo.get_by_id(redirect.project_id)?.map(SlugLookup::Moved)),
+        None => Ok(None),
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum LifecycleError {
+    NotFound,
+    /// Seul un propriétaire du projet peut changer son statut (`Action::ChangeStatus`).
+    Forbidden,
+    /// Le projet n'est pas dans le statut attendu par la transition.
+    InvalidTransition { from: ProjectStatus, to: ProjectStatus },
+    Storage(String),
+}
+
+fn transition(
+    repo: &dyn ProjectRepository,
+    access: &ProjectAccess,
+    project_id: Uuid,
+    actor_id: Uuid,
+    from: ProjectStatus,
+    to: ProjectStatus,
+) -> Result<Project, LifecycleError> {
+    let mut project = repo
+        .get_by_id(project_id)
+        .map_err(|e| LifecycleError::Storage(e.to_string()))?
+        .filter(|p| access.can_view(p, Some(actor_id)))
+        .ok_or(LifecycleError::NotFound)?;
+    if !access.can(&project, Some(actor_id), Action::ChangeStatus) {
+        return Err(LifecycleError::Forbidden);
+    }
+    if project.status != from {
+        return Err(LifecycleError::InvalidTransition { from: project.status, to });
+    }
+    project.status = to;
+    repo.update(project.clone()).map_err(|e| LifecycleError::Storage(e.to_string()))?;
+    Ok(project)
+}
+
+/// `Draft` -> `Active`.
+pub fn publish_project(
+    repo: &dyn ProjectRepository,
+    access: &ProjectAccess,
+    project_id: Uuid,
+    actor_id: Uuid,
+) -> Result<Project, LifecycleError> {
+    transition(repo, access, project_id, actor_id, ProjectStatus::Draft, ProjectStatus::Active)
+}
+
+/// `Active` -> `Archived`. Le projet devient lecture seule.
+pub fn archive_project(
+    repo: &dyn ProjectRepository,
+    access: &ProjectAccess,
+    project_id: Uuid,
+    actor_id: Uuid,
+) -> Result<Project, LifecycleError> {
+    transition(repo, access, project_id, actor_id, ProjectStatus::Active, ProjectStatus::Archived)
+}
+
+/// `Archived` -> `Active`.
+pub fn unarchive_project(
+    repo: &dyn ProjectRepository,
+    access: &ProjectAccess,
+    project_id: Uuid,
+    actor_id: Uuid,
+) -> Result<Project, LifecycleError> {
+    transition(repo, access, project_id, actor_id, ProjectStatus::Archived, ProjectStatus::Active)
+}
@@ -0,0 +1,88 @@
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use crate::models::session::RefreshToken;
+use crate::repositories::in_memory_refresh_token::InMemoryRefreshTokenRepo;
+
+pub const REFRESH_TOKEN_DAYS: i64 = 7;
+
+#[derive(Debug, PartialEq)]
+pub enum SessionError {
+    NotFound,
+    Expired,
+    /// La session a été fermée (déconnexion ou réutilisation détectée).
+    Revoked,
+    /// Le jeton avait déjà été échangé : la famille entière vient d'être révoquée.
+    Reused,
+}
+
+pub struct SessionContext<'a> {
+    pub tokens: &'a InMemoryRefreshTokenRepo,
+}
+
+impl SessionContext<'_> {
+    /// Ouvre une session : premier jeton d'une nouvelle famille.
+    pub fn start(&self, user_id: Uuid) -> RefreshToken {
+        self.issue(user_id, Uuid::new_v4())
+    }
+
+    /// Échange un refresh token contre son successeur. Un jeton ne sert qu'une fois : le
+    /// présenter à nouveau signale un vol probable et ferme toute la session.
+    pub fn rotate(&self, token_id: Uuid, user_id: Uuid) -> Result<RefreshToken, SessionError> {
+        let now = Utc::now();
+        let token = self.tokens.get(token_id).filter(|t| t.user_id == user_id).ok_or(SessionError::NotFound)?;
+        if token.revoked_at.is_some() {
+            return Err(SessionError::Revoked);
+        }
+        if token.rotated_at.is_some() {
+            return Err(self.reused(token.family_id));
+        }
+        if token.is_expired(now) {
+            return Err(SessionError::Expired);
+        }
+        // Marquage atomique : de deux échanges simultanés du même jeton, le second est une réutilisation
+        match self.tokens.mark_rotated(token.id, now) {
+            Ok(token) => Ok(self.issue(user_id, token.family_id)),
+            Err(_) => Err(self.reused(token.family_id)),
+        }
+    }
+
+    fn reused(&self, family_id: Uuid) -> SessionError {
+        self.tokens.revoke_family(family_id, Utc::now());
+        SessionError::Reused
+    }
+
+    pub fn is_active(&self, family_id: Uuid) -> bool {
+        !self.tokens.is_family_revoked(family_id)
+    }
+
+    /// Ferme une session de l'utilisateur.
+    pub fn logout(&self, family_id: Uuid, user_id: Uuid) -> Result<(), SessionError> {
+        let family = self.tokens.list_by_family(family_id);
+        if family.is_empty() || family.iter().any(|t| t.user_id != user_id) {
+            return Err(SessionError::NotFound);
+        }
+        self.tokens.revoke_family(family_id, Utc::now());
+        Ok(())
+    }
+
+    /// Ferme toutes les sessions de l'utilisateur et renvoie leur nombre.
+    pub fn logout_everywhere(&self, user_id: Uuid) -> usize {
+        self.tokens.revoke_for_user(user_id, Utc::now())
+    }
+
+    fn issue(&self, user_id: Uuid, family_id: Uuid) -> RefreshToken {
+        let now = Utc::now();
+        let token = RefreshToken {
+            id: Uuid::new_v4(),
+            family_id,
+            user_id,
+            created_at: now,
+            expires_at: now + Duration::days(REFRESH_TOKEN_DAYS),
+            rotated_at: None,
+            revoked_at: None,
+        };
+        self.tokens.save(token.clone());
+        token
+    }
+}
@@ -0,0 +1,165 @@
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::models::issue::IssueStatus;
+use crate::models::user::ProjectRole;
+use crate::repositories::in_memory_comment::InMemoryCommentRepo;
+use crate::repositories::in_memory_issue_history::InMemoryIssueHistoryRepo;
+use crate::repositories::in_memory_stats::InMemoryStatsCache;
+use crate::repositories::in_memory_user::InMemoryUserRepo;
+use crate::repositories::issue_repository::IssueRepository;
+use crate::usecases::time_tracking::iso_week;
+
+const TOP_CONTRIBUTORS: usize = 5;
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct IssueCounts {
+    pub open: usize,
+    pub in_progress: usize,
+    pub closed: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MemberCounts {
+    pub owner: usize,
+    pub maintainer: usize,
+    pub contributor: usize,
+    pub viewer: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WeeklyThroughput {
+    /// Semaine ISO, ex. `2026-W42`.
+    pub week: String,
+    pub opened: usize,
+    pub closed: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Contributor {
+    pub user_id: Uuid,
+    pub username: Option<String>,
+    pub issues_opened: usize,
+    pub comments: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectStats {
+    pub project_id: Uuid,
+    pub issues: IssueCounts,
+    pub members: MemberCounts,
+    pub weekly: Vec<WeeklyThroughput>,
+    /// Médiane, en heures, entre la création d'une issue fermée et sa dernière fermeture.
+    pub median_hours_to_close: Option<f64>,
+    pub top_contributors: Vec<Contributor>,
+    pub generated_at: DateTime<Utc>,
+}
+
+pub struct StatsContext<'a> {
+    pub issues: &'a dyn IssueRepository,
+    pub history: &'a InMemoryIssueHistoryRepo,
+    pub comments: &'a InMemoryCommentRepo,
+    pub users: &'a InMemoryUserRepo,
+}
+
+impl StatsContext<'_> {
+    /// Renvoie les statistiques en cache, et ne les recalcule qu'après une invalidation.
+    pub fn cached(&self, cache: &InMemoryStatsCache, project_id: Uuid) -> Result<ProjectStats, String> {
+        if let Some(stats) = cache.get(project_id) {
+            return Ok(stats);
+        }
+        let generation = cache.generation(project_id);
+        let stats = self.compute(project_id)?;
+        cache.put(stats.clone(), generation);
+        Ok(stats)
+    }
+
+    pub fn compute(&self, project_id: Uuid) -> Result<ProjectStats, String> {
+        let closed_status = IssueStatus::Closed.to_string();
+        let mut counts = IssueCounts::default();
+        let mut weekly: BTreeMap<String, (usize, usize)> = BTreeMap::new();
+        let mut hours_to_close = Vec::new();
+        let mut contributors: HashMap<Uuid, (usize, usize)> = HashMap::new();
+
+        for issue in self.issues.list_by_project(project_id)? {
+            match issue.status {
+                IssueStatus::Open => counts.open += 1,
+                IssueStatus::InProgress => counts.in_progress += 1,
+                IssueStatus::Closed => counts.closed += 1,
+            }
+            weekly.entry(iso_week(issue.created_at.date_naive())).or_default().0 += 1;
+            if let Some(author_id) = issue.author_id {
+                contributors.entry(author_id).or_default().0 += 1;
+            }
+            let closings: Vec<DateTime<Utc>> = self
+                .history
+                .list(issue.id)
+                .into_iter()
+                .filter(|r| r.changes.iter().any(|c| c.field == "status" && c.new_value == closed_status))
+                .map(|r| r.created_at)
+                .collect();
+            for closed_at in &closings {
+                weekly.entry(iso_week(closed_at.date_naive())).or_default().1 += 1;
+            }
+            if issue.status == IssueStatus::Closed
+                && let Some(last) = closings.last()
+            {
+                hours_to_close.push((*last - issue.created_at).num_seconds() as f64 / 3600.0);
+            }
+            for comment in self.comments.list_by_issue(issue.id) {
+                contributors.entry(comment.author_id).or_default().1 += 1;
+            }
+        }
+
+        let mut members = MemberCounts::default();
+        for member in self.users.list_members(project_id) {
+            match member.role {
+                ProjectRole::Owner => members.owner += 1,
+                ProjectRole::Maintainer => members.maintainer += 1,
+                ProjectRole::Contributor => members.contributor += 1,
+                ProjectRole::Viewer => members.viewer += 1,
+            }
+        }
+
+        let mut top_contributors: Vec<Contributor> = contributors
+            .into_iter()
+            .map(|(user_id, (issues_opened, comments))| Contributor {
+                user_id,
+                username: self.users.get_user(user_id).map(|u| u.username),
+                issues_opened,
+                comments,
+            })
+            .collect();
+        top_contributors.sort_by(|a, b| {
+            (b.issues_opened + b.comments)
+                .cmp(&(a.issues_opened + a.comments))
+                .then_with(|| a.user_id.cmp(&b.user_id))
+        });
+        top_contributors.truncate(TOP_CONTRIBUTORS);
+
+        Ok(ProjectStats {
+            project_id,
+            issues: counts,
+            members,
+            weekly: weekly
+                .into_iter()
+                .map(|(week, (opened, closed))| WeeklyThroughput { week, opened, closed })
+                .collect(),
+            median_hours_to_close: median(hours_to_close),
+            top_contributors,
+            generated_at: Utc::now(),
+        })
+    }
+}
+
+fn median(mut values: Vec<f64>) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(f64::total_cmp);
+    let mid = values.len() / 2;
+    Some(if values.len().is_multiple_of(2) { (values[mid - 1] + values[mid]) / 2.0 } else { values[mid] })
+}
@@ -0,0 +1,91 @@
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::models::issue::Issue;
+use crate::models::notification::{Notification, NotificationKind};
+use crate::models::project::Project;
+use crate::models::project_settings::NotificationPolicy;
+use crate::models::subscription::{SubscriptionReason, SubscriptionTarget};
+use crate::repositories::in_memory_notification::InMemoryNotificationRepo;
+use crate::repositories::in_memory_subscription::InMemorySubscriptionRepo;
+use crate::usecases::access::ProjectAccess;
+
+pub struct WatcherContext<'a> {
+    pub subscriptions: &'a InMemorySubscriptionRepo,
+    pub access: &'a ProjectAccess,
+    pub notifications: &'a InMemoryNotificationRepo,
+}
+
+impl WatcherContext<'_> {
+    /// Utilisateurs à prévenir d'un changement sur `issue` : ceux qui suivent l'issue ou son
+    /// projet, sauf l'auteur du changement et ceux qui n'ont plus accès au projet.
+    pub fn recipients(&self, project: Option<&Project>, issue: &Issue, actor_id: Option<Uuid>) -> Vec<Uuid> {
+        let mut recipients: Vec<Uuid> = Vec::new();
+        let watchers = self
+            .subscriptions
+            .watchers(SubscriptionTarget::Issue(issue.id))
+            .into_iter()
+            .chain(self.subscriptions.watchers(SubscriptionTarget::Project(issue.project_id)));
+        for subscription in watchers {
+            let user_id = subscription.user_id;
+            if Some(user_id) == actor_id || recipients.contains(&user_id) {
+                continue;
+            }
+            if project.is_none_or(|p| self.access.can_view(p, Some(user_id))) {
+                recipients.push(user_id);
+            }
+        }
+        recipients
+    }
+
+    pub fn notify(
+        &self,
+        project: Option<&Project>,
+        issue: &Issue,
+        actor_id: Option<Uuid>,
+        kind: NotificationKind,
+        message: &str,
+    ) {
+        for user_id in self.recipients(project, issue, actor_id) {
+            self.notifications.push(Notification {
+                id: Uuid::new_v4(),
+                user_id,
+                kind: kind.clone(),
+                project_id: issue.project_id,
+                issue_id: Some(issue.id),
+                message: message.to_string(),
+                read: false,
+                created_at: Utc::now(),
+            });
+        }
+    }
+}
+
+/// Abonnement automatique d'un participant à une issue, sauf si le projet n'en veut pas.
+/// Une issue sans projet suit la politique par défaut.
+pub fn auto_subscribe(
+    subscriptions: &InMemorySubscriptionRepo,
+    project: Option<&Project>,
+    user_id: Uuid,
+    issue_id: Uuid,
+    reason: SubscriptionReason,
+) -> bool {
+    let policy = project.map(|p| p.settings.notification_policy).unwrap_or_default();
+    policy != NotificationPolicy::MentionsOnly && subscriptions.subscribe(user_id, SubscriptionTarget::Issue(issue_id), reason)
+}
+
+/// Abonne un membre au projet entier quand la politique du projet est `Watching`.
+pub fn watch_as_member(subscriptions: &InMemorySubscriptionRepo, project: &Project, user_id: Uuid) -> bool {
+    project.settings.notification_policy == NotificationPolicy::Watching
+        && subscriptions.subscribe(user_id, SubscriptionTarget::Project(project.id), SubscriptionReason::Member)
+}
+
+/// Retire l'abonnement posé par `watch_as_member`, pas celui demandé par l'utilisateur.
+pub fn unwatch_as_member(subscriptions: &InMemorySubscriptionRepo, project_id: Uuid, user_id: Uuid) -> bool {
+    let target = SubscriptionTarget::Project(project_id);
+    let automatic = subscriptions
+        .watchers(target)
+        .iter()
+        .any(|s| s.user_id == user_id && s.reason == SubscriptionReason::Member);
+    automatic && subscriptions.unsubscribe(user_id, target)
+}
@@ -0,0 +1,24 @@
+use chrono::{DateTime, Duration, Utc};
+
+/// Délai minimal entre deux emails envoyés à la demande d'un même compte.
+pub const RESEND_INTERVAL_SECONDS: i64 = 60;
+/// Nombre d'emails autorisés par compte sur 24 heures glissantes.
+pub const MAX_SENDS_PER_DAY: usize = 5;
+
+/// Vérifie qu'un nouvel envoi est permis au vu des envois précédents `sent`. Sinon, renvoie
+/// le nombre de secondes à attendre.
+pub fn check_send_rate(sent: &[DateTime<Utc>], now: DateTime<Utc>) -> Result<(), i64> {
+    let mut recent: Vec<DateTime<Utc>> = sent.iter().copied().filter(|at| *at > now - Duration::hours(24)).collect();
+    recent.sort();
+    if let Some(last) = recent.last() {
+        let next = *last + Duration::seconds(RESEND_INTERVAL_SECONDS);
+        if next > now {
+            return Err((next - now).num_seconds().max(1));
+        }
+    }
+    if recent.len() >= MAX_SENDS_PER_DAY {
+        let next = recent[recent.len() - MAX_SENDS_PER_DAY] + Duration::hours(24);
+        return Err((next - now).num_seconds().max(1));
+    }
+    Ok(())
+}
@@ -0,0 +1,141 @@
+use std::collections::BTreeMap;
+
+use chrono::{Datelike, NaiveDate, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::models::issue::Issue;
+use crate::models::time_entry::{RunningTimer, TimeEntry};
+use crate::repositories::in_memory_user::InMemoryUserRepo;
+
+/// Durée maximale d'une saisie : une journée.
+pub const MAX_MINUTES_PER_ENTRY: u32 = 24 * 60;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct IssueTimeSummary {
+    pub issue_id: Uuid,
+    pub estimate_minutes: Option<u32>,
+    pub logged_minutes: u64,
+    /// Négatif quand l'estimation est dépassée.
+    pub remaining_minutes: Option<i64>,
+    pub over_estimate: bool,
+}
+
+pub fn summarize_issue(issue: &Issue, entries: &[TimeEntry]) -> IssueTimeSummary {
+    let logged_minutes: u64 = entries.iter().filter(|e| e.issue_id == issue.id).map(|e| u64::from(e.minutes)).sum();
+    let remaining_minutes = issue.estimate_minutes.map(|estimate| estimate as i64 - logged_minutes as i64);
+    IssueTimeSummary {
+        issue_id: issue.id,
+        estimate_minutes: issue.estimate_minutes,
+        logged_minutes,
+        remaining_minutes,
+        over_estimate: remaining_minutes.is_some_and(|r| r < 0),
+    }
+}
+
+/// Transforme un chronomètre arrêté en saisie de temps. Toute minute entamée compte,
+/// avec un minimum d'une minute et un maximum de [`MAX_MINUTES_PER_ENTRY`].
+pub fn entry_from_timer(timer: &RunningTimer, project_id: Uuid, note: String) -> TimeEntry {
+    let seconds = (Utc::now() - timer.started_at).num_seconds().max(1);
+    TimeEntry {
+        id: Uuid::new_v4(),
+        issue_id: timer.issue_id,
+        project_id,
+        user_id: timer.user_id,
+        minutes: ((seconds + 59) / 60).min(MAX_MINUTES_PER_ENTRY as i64) as u32,
+        date: timer.started_at.date_naive(),
+        note,
+        created_at: Utc::now(),
+    }
+}
+
+/// Semaine ISO 8601, par exemple `2026-W42`.
+pub fn iso_week(date: NaiveDate) -> String {
+    let week = date.iso_week();
+    format!("{}-W{:02}", week.year(), week.week())
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UserTotal {
+    pub user_id: Uuid,
+    pub username: Option<String>,
+    pub minutes: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WeekTotal {
+    pub week: String,
+    pub minutes: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TimeReportRow {
+    pub user_id: Uuid,
+    pub username: Option<String>,
+    pub week: String,
+    pub minutes: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TimeReport {
+    pub project_id: Uuid,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    pub total_minutes: u64,
+    pub by_user: Vec<UserTotal>,
+    pub by_week: Vec<WeekTotal>,
+    /// Détail utilisateur × semaine, c'est ce qui est exporté en CSV.
+    pub rows: Vec<TimeReportRow>,
+}
+
+/// Agrège les saisies d'un projet par utilisateur et par semaine ISO, bornes incluses.
+pub fn time_report(
+    project_id: Uuid,
+    entries: &[TimeEntry],
+    users: &InMemoryUserRepo,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+) -> TimeReport {
+    let in_range = |e: &&TimeEntry| {
+        e.project_id == project_id && from.is_none_or(|f| e.date >= f) && to.is_none_or(|t| e.date <= t)
+    };
+    let mut by_user: BTreeMap<Uuid, u64> = BTreeMap::new();
+    let mut by_week: BTreeMap<String, u64> = BTreeMap::new();
+    let mut by_user_week: BTreeMap<(String, Uuid), u64> = BTreeMap::new();
+    for entry in entries.iter().filter(in_range) {
+        let week = iso_week(entry.date);
+        let minutes = u64::from(entry.minutes);
+        *by_user.entry(entry.user_id).or_default() += minutes;
+        *by_week.entry(week.clone()).or_default() += minutes;
+        *by_user_week.entry((week, entry.user_id)).or_default() += minutes;
+    }
+    let username = |id: Uuid| users.get_user(id).map(|u| u.username);
+    let mut by_user: Vec<UserTotal> = by_user
+        .into_iter()
+        .map(|(user_id, minutes)| UserTotal { user_id, username: username(user_id), minutes })
+        .collect();
+    by_user.sort_by_key(|u| std::cmp::Reverse(u.minutes));
+    TimeReport {
+        project_id,
+        from,
+        to,
+        total_minutes: by_week.values().sum(),
+        by_user,
+        by_week: by_week.into_iter().map(|(week, minutes)| WeekTotal { week, minutes }).collect(),
+        rows: by_user_week
+            .into_iter()
+            .map(|((week, user_id), minutes)| TimeReportRow { user_id, username: username(user_id), week, minutes })
+            .collect(),
+    }
+}
+
+pub fn report_csv(report: &TimeReport) -> Result<String, String> {
+    // En-tête écrit à la main pour qu'un rapport vide reste un CSV valide
+    let mut writer = csv::WriterBuilder::new().has_headers(false).from_writer(Vec::new());
+    writer.write_record(["user_id", "username", "week", "minutes"]).map_err(|e| e.to_string())?;
+    for row in &report.rows {
+        writer.serialize(row).map_err(|e| e.to_string())?;
+    }
+    let bytes = writer.into_inner().map_err(|e| e.to_string())?;
+    String::from_utf8(bytes).map_err(|e| e.to_string())
+}
@@ -0,0 +1,120 @@
+use std::cmp::Reverse;
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::repositories::in_memory_comment::InMemoryCommentRepo;
+use crate::repositories::in_memory_social::InMemorySocialRepo;
+use crate::repositories::issue_repository::IssueRepository;
+use crate::repositories::project_repository::ProjectRepository;
+use crate::usecases::access::ProjectAccess;
+
+pub const DEFAULT_TIMELINE_LIMIT: usize = 50;
+pub const MAX_TIMELINE_LIMIT: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityKind {
+    ProjectCreated,
+    IssueOpened,
+    IssueCommented,
+    ProjectStarred,
+    UserFollowed,
+}
+
+/// Une entrée du fil d'activité. `target_id` désigne le projet, l'issue ou
+/// l'utilisateur suivi selon `kind`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ActivityEvent {
+    pub actor_id: Uuid,
+    pub kind: ActivityKind,
+    pub project_id: Option<Uuid>,
+    pub target_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+pub struct TimelineContext<'a> {
+    pub projects: &'a dyn ProjectRepository,
+    pub issues: &'a dyn IssueRepository,
+    pub comments: &'a InMemoryCommentRepo,
+    pub social: &'a InMemorySocialRepo,
+    pub access: &'a ProjectAccess,
+}
+
+impl TimelineContext<'_> {
+    /// Activité des utilisateurs suivis par `viewer`, de la plus récente à la plus ancienne.
+    /// Les événements liés à un projet que `viewer` ne voit pas dans les listes sont écartés :
+    /// un projet non listé ne s'y montre qu'à ses membres.
+    pub fn timeline(&self, viewer: Uuid, limit: usize) -> Result<Vec<ActivityEvent>, String> {
+        let followed: HashSet<Uuid> = self.social.following(viewer).iter().map(|f| f.followee_id).collect();
+        let mut events = Vec::new();
+        if followed.is_empty() {
+            return Ok(events);
+        }
+        let mut visible = HashSet::new();
+        for project in self.projects.list().map_err(|e| e.to_string())? {
+            if !self.access.is_listed(&project, Some(viewer)) {
+                continue;
+            }
+            visible.insert(project.id);
+            if let Some(owner_id) = project.owner_id.filter(|id| followed.contains(id)) {
+                events.push(ActivityEvent {
+                    actor_id: owner_id,
+                    kind: ActivityKind::ProjectCreated,
+                    project_id: Some(project.id),
+                    target_id: project.id,
+                    created_at: project.created_at,
+                });
+            }
+            for issue in self.issues.list_by_project(project.id)? {
+                if let Some(author_id) = issue.author_id.filter(|id| followed.contains(id)) {
+                    events.push(ActivityEvent {
+                        actor_id: author_id,
+                        kind: ActivityKind::IssueOpened,
+                        project_id: Some(project.id),
+                        target_id: issue.id,
+                        created_at: issue.created_at,
+                    });
+                }
+                for comment in self.comments.list_by_issue(issue.id) {
+                    if followed.contains(&comment.author_id) {
+                        events.push(ActivityEvent {
+                            actor_id: comment.author_id,
+                            kind: ActivityKind::IssueCommented,
+                            project_id: Some(project.id),
+                            target_id: issue.id,
+                            created_at: comment.created_at,
+                        });
+                    }
+                }
+            }
+        }
+        for user_id in &followed {
+            for star in self.social.starred_by(*user_id) {
+                if visible.contains(&star.project_id) {
+                    events.push(ActivityEvent {
+                        actor_id: star.user_id,
+                        kind: ActivityKind::ProjectStarred,
+                        project_id: Some(star.project_id),
+                        target_id: star.project_id,
+                        created_at: star.created_at,
+                    });
+                }
+            }
+            for follow in self.social.following(*user_id) {
+                events.push(ActivityEvent {
+                    actor_id: follow.follower_id,
+                    kind: ActivityKind::UserFollowed,
+                    project_id: None,
+                    target_id: follow.followee_id,
+                    created_at: follow.created_at,
+                });
+            }
+        }
+        events.sort_by_key(|e| Reverse(e.created_at));
+        events.truncate(limit.clamp(1, MAX_TIMELINE_LIMIT));
+        Ok(events)
+    }
+}
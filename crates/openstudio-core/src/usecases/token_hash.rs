@@ -0,0 +1,7 @@
+use sha2::{Digest, Sha256};
+
+/// Empreinte SHA-256 (hexadécimale) d'un jeton secret : lien d'invitation, de réinitialisation,
+/// jeton d'accès. Seule l'empreinte est stockée.
+pub fn hash_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
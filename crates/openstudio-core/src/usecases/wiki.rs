@@ -0,0 +1,217 @@
+use chrono::Utc;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::models::wiki::{WikiPage, WikiRevision};
+use crate::repositories::in_memory_search::{InMemorySearchIndex, SearchDocument, SearchKind};
+use crate::repositories::in_memory_wiki::InMemoryWikiRepo;
+use crate::usecases::project::slugify;
+
+/// Taille maximale du contenu d'une page, en octets.
+pub const MAX_PAGE_BYTES: usize = 256 * 1024;
+/// Nombre maximal de lignes d'une page : le diff entre deux révisions est quadratique en
+/// nombre de lignes.
+pub const MAX_PAGE_LINES: usize = 2_000;
+
+#[derive(Debug, PartialEq)]
+pub enum WikiError {
+    PageNotFound,
+    RevisionNotFound,
+    EmptyTitle,
+    /// Parent inexistant, dans un autre projet, ou qui créerait un cycle.
+    InvalidParent,
+    /// Une page qui a des sous-pages ne peut pas être supprimée.
+    HasChildren,
+    /// Contenu au-delà de `MAX_PAGE_BYTES` ou de `MAX_PAGE_LINES`.
+    PageTooLarge,
+}
+
+#[derive(Debug, Default)]
+pub struct PageEdit {
+    pub title: Option<String>,
+    pub content: Option<String>,
+    /// `Some(None)` remonte la page à la racine.
+    pub parent_id: Option<Option<Uuid>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffOp {
+    Equal,
+    Insert,
+    Delete,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DiffLine {
+    pub op: DiffOp,
+    pub text: String,
+}
+
+/// Diff ligne à ligne (plus longue sous-séquence commune). Le début et la fin communs sont
+/// écartés avant le calcul, qui ne porte que sur la partie modifiée.
+pub fn line_diff(old: &str, new: &str) -> Vec<DiffLine> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..].iter().rev().zip(new[prefix..].iter().rev()).take_while(|(a, b)| a == b).count();
+    let line = |op, text: &str| DiffLine { op, text: text.to_string() };
+    let mut diff: Vec<DiffLine> = old[..prefix].iter().map(|l| line(DiffOp::Equal, l)).collect();
+    let (old_mid, new_mid) = (&old[prefix..old.len() - suffix], &new[prefix..new.len() - suffix]);
+    let mut lcs = vec![vec![0u32; new_mid.len() + 1]; old_mid.len() + 1];
+    for i in (0..old_mid.len()).rev() {
+        for j in (0..new_mid.len()).rev() {
+            lcs[i][j] = if old_mid[i] == new_mid[j] { lcs[i + 1][j + 1] + 1 } else { lcs[i + 1][j].max(lcs[i][j + 1]) };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    while i < old_mid.len() && j < new_mid.len() {
+        if old_mid[i] == new_mid[j] {
+            diff.push(line(DiffOp::Equal, old_mid[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            diff.push(line(DiffOp::Delete, old_mid[i]));
+            i += 1;
+        } else {
+            diff.push(line(DiffOp::Insert, new_mid[j]));
+            j += 1;
+        }
+    }
+    diff.extend(old_mid[i..].iter().map(|l| line(DiffOp::Delete, l)));
+    diff.extend(new_mid[j..].iter().map(|l| line(DiffOp::Insert, l)));
+    diff.extend(old[old.len() - suffix..].iter().map(|l| line(DiffOp::Equal, l)));
+    diff
+}
+
+fn check_size(content: &str) -> Result<(), WikiError> {
+    if content.len() > MAX_PAGE_BYTES || content.lines().count() > MAX_PAGE_LINES {
+        return Err(WikiError::PageTooLarge);
+    }
+    Ok(())
+}
+
+pub struct WikiContext<'a> {
+    pub wiki: &'a InMemoryWikiRepo,
+    pub index: &'a InMemorySearchIndex,
+}
+
+impl WikiContext<'_> {
+    pub fn create_page(
+        &self,
+        project_id: Uuid,
+        parent_id: Option<Uuid>,
+        title: &str,
+        content: &str,
+        author_id: Option<Uuid>,
+    ) -> Result<WikiPage, WikiError> {
+        let title = title.trim();
+        if title.is_empty() {
+            return Err(WikiError::EmptyTitle);
+        }
+        check_size(content)?;
+        if let Some(parent_id) = parent_id {
+            self.check_parent(project_id, None, parent_id)?;
+        }
+        let base = slugify(title);
+        let mut slug = base.clone();
+        let mut n = 2;
+        while self.wiki.find_by_slug(project_id, &slug).is_some() {
+            slug = format!("{}-{}", base, n);
+            n += 1;
+        }
+        let now = Utc::now();
+        let page = WikiPage {
+            id: Uuid::new_v4(),
+            project_id,
+            parent_id,
+            slug,
+            title: title.to_string(),
+            content: content.to_string(),
+            revision: 0,
+            created_at: now,
+            updated_at: now,
+        };
+        Ok(self.commit(page, author_id))
+    }
+
+    /// Applique `edit` et enregistre une révision si le titre ou le contenu change.
+    /// Le slug ne change pas, pour ne pas casser les liens existants.
+    pub fn update_page(&self, mut page: WikiPage, edit: PageEdit, author_id: Option<Uuid>) -> Result<WikiPage, WikiError> {
+        if let Some(parent_id) = edit.parent_id {
+            if let Some(parent_id) = parent_id {
+                self.check_parent(page.project_id, Some(page.id), parent_id)?;
+            }
+            page.parent_id = parent_id;
+        }
+        let title = edit.title.map(|t| t.trim().to_string()).unwrap_or_else(|| page.title.clone());
+        if title.is_empty() {
+            return Err(WikiError::EmptyTitle);
+        }
+        let content = edit.content.unwrap_or_else(|| page.content.clone());
+        check_size(&content)?;
+        if title == page.title && content == page.content {
+            self.wiki.save_page(page.clone());
+            return Ok(page);
+        }
+        page.title = title;
+        page.content = content;
+        page.updated_at = Utc::now();
+        Ok(self.commit(page, author_id))
+    }
+
+    /// Restaure le titre et le contenu d'une révision, sous la forme d'une nouvelle révision.
+    pub fn restore(&self, page: WikiPage, revision: u32, author_id: Option<Uuid>) -> Result<WikiPage, WikiError> {
+        let target = self.wiki.get_revision(page.id, revision).ok_or(WikiError::RevisionNotFound)?;
+        let edit = PageEdit { title: Some(target.title), content: Some(target.content), parent_id: None };
+        self.update_page(page, edit, author_id)
+    }
+
+    pub fn delete_page(&self, page: &WikiPage) -> Result<(), WikiError> {
+        if !self.wiki.children(page.id).is_empty() {
+            return Err(WikiError::HasChildren);
+        }
+        self.wiki.delete_page(page.id);
+        self.index.remove(SearchKind::WikiPage, page.id);
+        Ok(())
+    }
+
+    /// Chemin de la page dans l'arborescence, ex. `guide/installation`.
+    pub fn path(&self, page: &WikiPage) -> String {
+        let mut slugs = vec![page.slug.clone()];
+        let mut parent_id = page.parent_id;
+        while let Some(parent) = parent_id.and_then(|id| self.wiki.get_page(id)) {
+            slugs.push(parent.slug);
+            parent_id = parent.parent_id;
+        }
+        slugs.reverse();
+        slugs.join("/")
+    }
+
+    fn check_parent(&self, project_id: Uuid, page_id: Option<Uuid>, parent_id: Uuid) -> Result<(), WikiError> {
+        let mut current = Some(parent_id);
+        while let Some(id) = current {
+            let parent = self.wiki.get_page(id).filter(|p| p.project_id == project_id);
+            match parent {
+                Some(parent) if Some(parent.id) != page_id => current = parent.parent_id,
+                _ => return Err(WikiError::InvalidParent),
+            }
+        }
+        Ok(())
+    }
+
+    fn commit(&self, mut page: WikiPage, author_id: Option<Uuid>) -> WikiPage {
+        page.revision += 1;
+        self.wiki.add_revision(WikiRevision {
+            page_id: page.id,
+            revision: page.revision,
+            title: page.title.clone(),
+            content: page.content.clone(),
+            author_id,
+            created_at: page.updated_at,
+        });
+        self.wiki.save_page(page.clone());
+        self.index.upsert(SearchDocument::from(&page));
+        page
+    }
+}
@@ -0,0 +1,41 @@
+use std::sync::Arc;
+use chrono::Utc;
+use uuid::Uuid;
+use openstudio_core::models::project_status::Visibility;
+use openstudio_core::models::user::{ProjectMember, ProjectRole};
+use openstudio_core::repositories::in_memory_user::InMemoryUserRepo;
+use openstudio_core::usecases::access::ProjectAccess;
+use openstudio_core::usecases::project::create_project;
+
+#[test]
+fn test_visibility_rules() {
+    let users = Arc::new(InMemoryUserRepo::new());
+    let access = ProjectAccess::new(users.clone());
+    let (owner, member, outsider) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+    let mut project = create_project("Secret", "desc");
+    project.owner_id = Some(owner);
+    users.add_member(ProjectMember {
+        user_id: member,
+        project_id: project.id,
+        role: ProjectRole::Viewer,
+        joined_at: Utc::now(),
+    });
+
+    project.visibility = Visibility::Private;
+    assert!(access.can_view(&project, Some(owner)));
+    assert!(access.can_view(&project, Some(member)));
+    assert!(!access.can_view(&project, Some(outsider)));
+    assert!(!access.can_view(&project, None));
+
+    // Non listé : accessible par lien, absent des listes pour les non-membres
+    project.visibility = Visibility::Unlisted;
+    assert!(access.can_view(&project, None));
+    assert!(!access.is_listed(&project, None));
+    assert!(access.is_listed(&project, Some(member)));
+
+    project.visibility = Visibility::Public;
+    assert!(access.is_listed(&project, None));
+    assert_eq!(access.role(&project, owner), Some(ProjectRole::Owner));
+    assert_eq!(access.role(&project, member), Some(ProjectRole::Viewer));
+    assert_eq!(access.role(&project, outsider), None);
+}
@@ -0,0 +1,51 @@
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+use openstudio_core::models::access_token::TokenScope;
+use openstudio_core::repositories::in_memory_access_token::InMemoryAccessTokenRepo;
+use openstudio_core::usecases::access_token::{AccessTokenContext, AccessTokenError, TOKEN_PREFIX};
+
+#[test]
+fn test_access_token_is_hashed_and_authenticates_until_revoked() {
+    let tokens = InMemoryAccessTokenRepo::new();
+    let ctx = AccessTokenContext { tokens: &tokens };
+    let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+
+    let (token, raw) = ctx.create(alice, " ci ", &[TokenScope::IssuesWrite, TokenScope::IssuesWrite], Some(30), None).unwrap();
+    assert!(raw.starts_with(TOKEN_PREFIX));
+    assert!(raw.starts_with(&token.prefix));
+    assert_ne!(token.token_hash, raw);
+    assert_eq!(token.name, "ci");
+    assert_eq!(token.scopes, vec![TokenScope::IssuesWrite]);
+    assert!(token.grants(TokenScope::IssuesRead));
+    assert!(!token.grants(TokenScope::ProjectsRead));
+    assert_eq!(ctx.create(alice, "ci", &[TokenScope::IssuesRead], None, None).unwrap_err(), AccessTokenError::DuplicateName);
+
+    let used = ctx.authenticate(&raw).unwrap();
+    assert_eq!(used.user_id, alice);
+    assert!(used.last_used_at.is_some());
+    assert!(ctx.authenticate("osp_unknown").is_none());
+
+    assert_eq!(ctx.revoke(bob, token.id).unwrap_err(), AccessTokenError::NotFound);
+    ctx.revoke(alice, token.id).unwrap();
+    assert!(ctx.authenticate(&raw).is_none());
+    // Le nom redevient disponible une fois le jeton révoqué
+    ctx.create(alice, "ci", &[TokenScope::IssuesRead], None, None).unwrap();
+}
+
+#[test]
+fn test_access_token_validation_and_expiry() {
+    let tokens = InMemoryAccessTokenRepo::new();
+    let ctx = AccessTokenContext { tokens: &tokens };
+    let alice = Uuid::new_v4();
+
+    assert_eq!(ctx.create(alice, "  ", &[TokenScope::ProjectsRead], None, None).unwrap_err(), AccessTokenError::InvalidName);
+    assert_eq!(ctx.create(alice, "deploy", &[], None, None).unwrap_err(), AccessTokenError::NoScopes);
+    assert_eq!(ctx.create(alice, "deploy", &[TokenScope::ProjectsRead], Some(0), None).unwrap_err(), AccessTokenError::InvalidExpiry);
+    assert_eq!(ctx.create(alice, "deploy", &[TokenScope::ProjectsRead], Some(400), None).unwrap_err(), AccessTokenError::InvalidExpiry);
+
+    let (mut token, raw) = ctx.create(alice, "deploy", &[TokenScope::ProjectsRead], Some(1), None).unwrap();
+    token.expires_at = Some(Utc::now() - Duration::minutes(1));
+    tokens.save(token);
+    assert!(ctx.authenticate(&raw).is_none());
+    assert_eq!(ctx.list(alice).len(), 1);
+}
@@ -0,0 +1,43 @@
+#![allow(dead_code)]
+
+use chrono::Utc;
+use uuid::Uuid;
+use openstudio_core::models::issue::{Issue, IssueStatus};
+use openstudio_core::models::user::User;
+use openstudio_core::repositories::in_memory_user::InMemoryUserRepo;
+
+/// Compte `username` avec l'adresse `<username>@example.com`, non vérifiée et sans mot de
+/// passe. Les variantes passent par `User { .., ..user("alice") }`.
+pub fn user(username: &str) -> User {
+    User {
+        id: Uuid::new_v4(),
+        username: username.to_string(),
+        email: format!("{}@example.com", username),
+        email_verified: false,
+        password: String::new(),
+        first_name: None,
+        last_name: None,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+pub fn create_user(users: &InMemoryUserRepo, username: &str) -> User {
+    users.create_user(user(username))
+}
+
+/// Issue ouverte, sans description, auteur ni assignation.
+pub fn issue(project_id: Uuid, title: &str) -> Issue {
+    Issue {
+        id: Uuid::new_v4(),
+        project_id,
+        title: title.to_string(),
+        description: String::new(),
+        status: IssueStatus::Open,
+        author_id: None,
+        assignee_id: None,
+        estimate_minutes: None,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
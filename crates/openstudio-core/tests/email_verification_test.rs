@@ -0,0 +1,67 @@
+mod common;
+
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+use openstudio_core::models::email_verification::VerificationEmail;
+use openstudio_core::repositories::in_memory_email_verification::InMemoryEmailVerificationRepo;
+use openstudio_core::repositories::in_memory_user::InMemoryUserRepo;
+use openstudio_core::usecases::email_verification::{is_valid_email, EmailVerificationContext, EmailVerificationError};
+use openstudio_core::usecases::throttle::MAX_SENDS_PER_DAY;
+
+#[test]
+fn test_email_format_and_confirmation() {
+    for email in ["alice@example.com", "a.b+tag@sub.example.org"] {
+        assert!(is_valid_email(email), "{}", email);
+    }
+    for email in ["", "alice", "@example.com", "alice@", "alice@localhost", "alice@example.com.", "a b@example.com", "a@b@example.com"] {
+        assert!(!is_valid_email(email), "{}", email);
+    }
+
+    let sends = InMemoryEmailVerificationRepo::new();
+    let users = InMemoryUserRepo::new();
+    let ctx = EmailVerificationContext { sends: &sends, users: &users };
+    let mut alice = common::create_user(&users, "alice");
+    assert_eq!(ctx.confirm(Uuid::new_v4(), "alice@example.com").unwrap_err(), EmailVerificationError::NotFound);
+    assert!(ctx.confirm(alice.id, "Alice@Example.com").unwrap().email_verified);
+    // Second clic sur le même lien : sans effet
+    assert!(ctx.confirm(alice.id, "alice@example.com").is_ok());
+    assert_eq!(ctx.record_send(alice.id).unwrap_err(), EmailVerificationError::AlreadyVerified);
+
+    // Un lien envoyé à l'ancienne adresse ne vérifie pas la nouvelle
+    alice.email = "alice@new.example.com".to_string();
+    alice.email_verified = false;
+    users.save_user(alice.clone());
+    assert_eq!(ctx.confirm(alice.id, "alice@example.com").unwrap_err(), EmailVerificationError::EmailChanged);
+    assert!(!users.get_user(alice.id).unwrap().email_verified);
+}
+
+#[test]
+fn test_resend_is_throttled() {
+    let sends = InMemoryEmailVerificationRepo::new();
+    let users = InMemoryUserRepo::new();
+    let ctx = EmailVerificationContext { sends: &sends, users: &users };
+    let alice = common::create_user(&users, "alice");
+
+    assert_eq!(ctx.record_send(alice.id).unwrap().id, alice.id);
+    match ctx.record_send(alice.id).unwrap_err() {
+        EmailVerificationError::TooSoon { retry_after } => assert!((1..=60).contains(&retry_after)),
+        other => panic!("unexpected {:?}", other),
+    }
+
+    // Envois espacés mais trop nombreux sur la journée
+    for hours in 1..MAX_SENDS_PER_DAY as i64 {
+        sends.save(VerificationEmail {
+            id: Uuid::new_v4(),
+            user_id: alice.id,
+            email: alice.email.clone(),
+            sent_at: Utc::now() - Duration::hours(hours),
+        });
+    }
+    let mut last = sends.sent_since(alice.id, Utc::now() - Duration::hours(1)).pop().unwrap();
+    last.sent_at = Utc::now() - Duration::minutes(5);
+    sends.save(last);
+    match ctx.record_send(alice.id).unwrap_err() {
+        EmailVerificationError::TooSoon { retry_after } => assert!(retry_after > 3600),
+        other => panic!("unexpected {:?}", other),
+    }
+}
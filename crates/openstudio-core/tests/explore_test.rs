@@ -0,0 +1,68 @@
+mod common;
+
+use chrono::Utc;
+use uuid::Uuid;
+use openstudio_core::models::issue::IssueStatus;
+use openstudio_core::models::project::Project;
+use openstudio_core::models::project_status::{ProjectStatus, Visibility};
+use openstudio_core::models::user::{ProjectMember, ProjectRole};
+use openstudio_core::repositories::in_memory_comment::InMemoryCommentRepo;
+use openstudio_core::repositories::in_memory_issue::InMemoryIssueRepo;
+use openstudio_core::repositories::in_memory_issue_history::InMemoryIssueHistoryRepo;
+use openstudio_core::repositories::in_memory_user::InMemoryUserRepo;
+use openstudio_core::repositories::issue_repository::IssueRepository;
+use openstudio_core::usecases::explore::ExploreContext;
+use openstudio_core::usecases::issue::update_issue;
+use openstudio_core::usecases::project::{create_project, normalize_topics};
+
+fn public_project(name: &str, topics: &[&str]) -> Project {
+    let mut project = create_project(name, "desc");
+    project.visibility = Visibility::Public;
+    project.status = ProjectStatus::Active;
+    project.topics = topics.iter().map(|t| t.to_string()).collect();
+    project
+}
+
+#[test]
+fn test_normalize_topics() {
+    let topics = vec!["Rust".to_string(), " game dev ".to_string(), "rust".to_string()];
+    assert_eq!(normalize_topics(&topics).unwrap(), vec!["rust", "game-dev"]);
+    assert!(normalize_topics(&["c++".to_string()]).is_err());
+}
+
+#[test]
+fn test_explore_ranks_active_public_projects() {
+    let issues = InMemoryIssueRepo::new();
+    let history = InMemoryIssueHistoryRepo::new();
+    let comments = InMemoryCommentRepo::new();
+    let users = InMemoryUserRepo::new();
+    let quiet = public_project("Calme", &["rust"]);
+    let busy = public_project("Actif", &["rust", "cli"]);
+    let mut draft = public_project("Brouillon", &["rust"]);
+    draft.status = ProjectStatus::Draft;
+    let mut private = public_project("Privé", &["rust"]);
+    private.visibility = Visibility::Private;
+
+    let mut bug = common::issue(busy.id, "Bug");
+    issues.save(bug.clone()).unwrap();
+    bug.status = IssueStatus::Closed;
+    update_issue(&issues, &history, bug, None).unwrap();
+    users.add_member(ProjectMember {
+        user_id: Uuid::new_v4(),
+        project_id: busy.id,
+        role: ProjectRole::Contributor,
+        joined_at: Utc::now(),
+    });
+
+    let context = ExploreContext { issues: &issues, history: &history, comments: &comments, users: &users };
+    let projects = vec![quiet.clone(), busy.clone(), draft, private];
+    let trending = context.explore(projects.clone(), Some("rust"), 30).unwrap();
+    println!("Tendances: {:?}", trending.iter().map(|t| (&t.project.name, t.score)).collect::<Vec<_>>());
+    let names: Vec<&str> = trending.iter().map(|t| t.project.name.as_str()).collect();
+    assert_eq!(names, vec!["Actif", "Calme"]);
+    assert_eq!(trending[0].activity.closed_issues, 1);
+    assert_eq!(trending[0].activity.new_members, 1);
+
+    let cli = context.explore(projects, Some("CLI"), 30).unwrap();
+    assert_eq!(cli.len(), 1);
+}
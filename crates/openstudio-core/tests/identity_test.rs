@@ -0,0 +1,71 @@
+mod common;
+
+use openstudio_core::models::user::User;
+use openstudio_core::repositories::in_memory_identity::InMemoryIdentityRepo;
+use openstudio_core::repositories::in_memory_org::InMemoryOrgRepo;
+use openstudio_core::repositories::in_memory_user::InMemoryUserRepo;
+use openstudio_core::usecases::organization::OrgContext;
+use openstudio_core::usecases::identity::{ExternalProfile, IdentityContext, IdentityError};
+
+fn profile(subject: &str, email: &str, verified: bool, username: &str) -> ExternalProfile {
+    ExternalProfile {
+        subject: subject.to_string(),
+        email: Some(email.to_string()),
+        email_verified: verified,
+        username: Some(username.to_string()),
+        first_name: None,
+        last_name: None,
+    }
+}
+
+#[test]
+fn test_first_login_creates_account_then_reuses_identity() {
+    let identities = InMemoryIdentityRepo::new();
+    let users = InMemoryUserRepo::new();
+    let orgs = InMemoryOrgRepo::new();
+    let ctx = IdentityContext { identities: &identities, users: &users, orgs: &orgs };
+    let octo = users.create_user(User { email: "someone@example.com".to_string(), ..common::user("octo") });
+
+    let first = ctx.sign_in("github", &profile("42", "Octo@Example.com", false, "octo")).unwrap();
+    assert!(first.created);
+    assert_eq!(first.user.email, "octo@example.com");
+    // Le login GitHub est déjà pris : suffixe
+    assert_eq!(first.user.username, "octo2");
+
+    // Même identité, même compte, même si l'adresse a changé chez le fournisseur
+    let again = ctx.sign_in("github", &profile("42", "new@example.com", false, "octo")).unwrap();
+    assert!(!again.created);
+    assert_eq!(again.user.id, first.user.id);
+    // Un autre fournisseur avec le même sujet est une autre identité
+    assert!(ctx.sign_in("gitlab", &profile("42", "other@example.com", true, "octo")).unwrap().created);
+
+    // Le slug d'une organisation n'est pas non plus disponible comme nom d'utilisateur
+    OrgContext { orgs: &orgs, users: &users }.create_organization(octo.id, "acme", "Acme", "").unwrap();
+    let acme = ctx.sign_in("github", &profile("43", "acme@example.com", true, "acme")).unwrap();
+    assert_eq!(acme.user.username, "acme2");
+}
+
+#[test]
+fn test_existing_account_is_linked_only_by_verified_email() {
+    let identities = InMemoryIdentityRepo::new();
+    let users = InMemoryUserRepo::new();
+    let orgs = InMemoryOrgRepo::new();
+    let ctx = IdentityContext { identities: &identities, users: &users, orgs: &orgs };
+    let alice = users.create_user(User { email_verified: true, ..common::user("alice") });
+
+    let unverified = profile("sub-1", "alice@example.com", false, "alice");
+    assert_eq!(ctx.sign_in("oidc", &unverified).unwrap_err(), IdentityError::UnverifiedEmail);
+    let mut no_email = unverified.clone();
+    no_email.email = None;
+    assert_eq!(ctx.sign_in("oidc", &no_email).unwrap_err(), IdentityError::MissingEmail);
+
+    let linked = ctx.sign_in("oidc", &profile("sub-1", "ALICE@example.com", true, "alice")).unwrap();
+    assert!(!linked.created);
+    assert_eq!(linked.user.id, alice.id);
+    assert_eq!(identities.list_by_user(alice.id).len(), 1);
+
+    // Un compte dont l'adresse n'a jamais été confirmée a pu être créé par un tiers : pas de rattachement
+    users.create_user(User { email: "bob@example.com".to_string(), ..common::user("squatter") });
+    let bob = profile("sub-2", "bob@example.com", true, "bob");
+    assert_eq!(ctx.sign_in("oidc", &bob).unwrap_err(), IdentityError::UnverifiedAccount);
+}
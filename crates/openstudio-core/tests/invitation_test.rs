@@ -0,0 +1,75 @@
+mod common;
+
+use std::sync::Arc;
+use chrono::{Duration, Utc};
+use openstudio_core::models::invitation::InvitationStatus;
+use openstudio_core::models::user::ProjectRole;
+use openstudio_core::repositories::in_memory::InMemoryProjectRepo;
+use openstudio_core::repositories::in_memory_invitation::InMemoryInvitationRepo;
+use openstudio_core::repositories::in_memory_redirect::InMemoryProjectRedirectRepo;
+use openstudio_core::repositories::in_memory_user::InMemoryUserRepo;
+use openstudio_core::usecases::access::ProjectAccess;
+use openstudio_core::usecases::invitation::{InvitationContext, InvitationError, Invitee};
+use openstudio_core::usecases::project::create_owned_project;
+
+#[test]
+fn test_invitation_is_accepted_once_by_its_invitee() {
+    let users = Arc::new(InMemoryUserRepo::new());
+    let access = ProjectAccess::new(users.clone());
+    let invitations = InMemoryInvitationRepo::new();
+    let repo = InMemoryProjectRepo::new();
+    let redirects = InMemoryProjectRedirectRepo::new();
+    let ctx = InvitationContext { invitations: &invitations, users: &users, access: &access };
+    let (alice, bob, carol) = (common::create_user(&users, "alice"), common::create_user(&users, "bob"), common::create_user(&users, "carol"));
+    let project = create_owned_project(&repo, &redirects, &users, alice.id, "Demo", "").unwrap();
+
+    // Seul un membre habilité invite, et une seule invitation ouverte par personne
+    let bob_invitee = Invitee::Username("bob".into());
+    assert_eq!(ctx.invite(&project, carol.id, &bob_invitee, ProjectRole::Viewer, 7).unwrap_err(), InvitationError::Forbidden);
+    let (invitation, token) = ctx.invite(&project, alice.id, &bob_invitee, ProjectRole::Contributor, 7).unwrap();
+    assert_ne!(invitation.token_hash, token);
+    assert_eq!(ctx.invite(&project, alice.id, &bob_invitee, ProjectRole::Viewer, 7).unwrap_err(), InvitationError::AlreadyInvited);
+    assert_eq!(ctx.pending_for(&bob).len(), 1);
+
+    let found = ctx.find_by_token(&token).unwrap();
+    assert_eq!(ctx.accept(found.clone(), &project, &carol).unwrap_err(), InvitationError::NotInvitee);
+    let member = ctx.accept(found, &project, &bob).unwrap();
+    assert_eq!(member.role, ProjectRole::Contributor);
+    assert_eq!(access.role(&project, bob.id), Some(ProjectRole::Contributor));
+
+    // Le jeton ne sert qu'une fois
+    let used = ctx.find_by_token(&token).unwrap();
+    assert_eq!(used.status, InvitationStatus::Accepted);
+    assert_eq!(ctx.decline(used, &bob).unwrap_err(), InvitationError::AlreadyAnswered);
+    assert!(ctx.pending_for(&bob).is_empty());
+}
+
+#[test]
+fn test_email_invitation_expiry_and_revocation() {
+    let users = Arc::new(InMemoryUserRepo::new());
+    let access = ProjectAccess::new(users.clone());
+    let invitations = InMemoryInvitationRepo::new();
+    let repo = InMemoryProjectRepo::new();
+    let redirects = InMemoryProjectRedirectRepo::new();
+    let ctx = InvitationContext { invitations: &invitations, users: &users, access: &access };
+    let alice = common::create_user(&users, "alice");
+    let project = create_owned_project(&repo, &redirects, &users, alice.id, "Demo", "").unwrap();
+
+    // Une invitation par email est retrouvée une fois le compte créé
+    let (invitation, _) = ctx.invite(&project, alice.id, &Invitee::Email("Dave@Example.com".into()), ProjectRole::Viewer, 7).unwrap();
+    let mut dave = common::create_user(&users, "dave");
+    // Tant que l'adresse n'est pas confirmée, n'importe qui a pu créer le compte avec
+    assert!(ctx.pending_for(&dave).is_empty());
+    dave.email_verified = true;
+    users.save_user(dave.clone());
+    assert_eq!(ctx.pending_for(&dave).len(), 1);
+    ctx.revoke(&project, alice.id, invitation.id).unwrap();
+    assert!(ctx.pending_for(&dave).is_empty());
+
+    let (mut invitation, token) = ctx.invite(&project, alice.id, &Invitee::Username("dave".into()), ProjectRole::Viewer, 7).unwrap();
+    invitation.expires_at = Utc::now() - Duration::minutes(1);
+    invitations.save(invitation);
+    let expired = ctx.find_by_token(&token).unwrap();
+    assert_eq!(ctx.accept(expired, &project, &dave).unwrap_err(), InvitationError::Expired);
+    assert_eq!(access.role(&project, dave.id), None);
+}
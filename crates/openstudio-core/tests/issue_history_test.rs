@@ -0,0 +1,69 @@
+mod common;
+
+use uuid::Uuid;
+use openstudio_core::models::issue::{Issue, IssueStatus};
+use openstudio_core::repositories::in_memory_issue::InMemoryIssueRepo;
+use openstudio_core::repositories::in_memory_issue_history::InMemoryIssueHistoryRepo;
+use openstudio_core::repositories::issue_repository::IssueRepository;
+use openstudio_core::usecases::issue::{revert_issue, update_issue, RevertError};
+
+fn new_issue(title: &str) -> Issue {
+    Issue { description: "Description initiale".to_string(), ..common::issue(Uuid::new_v4(), title) }
+}
+
+#[test]
+fn test_update_records_changed_fields() {
+    let repo = InMemoryIssueRepo::new();
+    let history = InMemoryIssueHistoryRepo::new();
+    let issue = new_issue("Crash au démarrage");
+    repo.save(issue.clone()).unwrap();
+    let actor = Uuid::new_v4();
+
+    let mut updated = issue.clone();
+    updated.title = "Crash au démarrage sous Windows".to_string();
+    updated.status = IssueStatus::InProgress;
+    update_issue(&repo, &history, updated, Some(actor)).unwrap().unwrap();
+
+    let revisions = history.list(issue.id);
+    println!("Historique: {:?}", revisions);
+    assert_eq!(revisions.len(), 1);
+    assert_eq!(revisions[0].revision, 1);
+    assert_eq!(revisions[0].actor_id, Some(actor));
+    let fields: Vec<&str> = revisions[0].changes.iter().map(|c| c.field.as_str()).collect();
+    assert_eq!(fields, vec!["title", "status"]);
+    assert_eq!(revisions[0].changes[1].old_value, "Open");
+    assert_eq!(revisions[0].changes[1].new_value, "InProgress");
+
+    // Une mise à jour sans changement n'ajoute pas de révision
+    let same = repo.get_by_id(issue.id).unwrap().unwrap();
+    update_issue(&repo, &history, same, Some(actor)).unwrap();
+    assert_eq!(history.list(issue.id).len(), 1);
+}
+
+#[test]
+fn test_revert_to_earlier_revision() {
+    let repo = InMemoryIssueRepo::new();
+    let history = InMemoryIssueHistoryRepo::new();
+    let issue = new_issue("Titre v0");
+    repo.save(issue.clone()).unwrap();
+
+    let mut v1 = issue.clone();
+    v1.title = "Titre v1".to_string();
+    update_issue(&repo, &history, v1.clone(), None).unwrap();
+    let mut v2 = v1.clone();
+    v2.description = "Nouvelle description".to_string();
+    v2.status = IssueStatus::Closed;
+    update_issue(&repo, &history, v2, None).unwrap();
+
+    let reverted = revert_issue(&repo, &history, issue.id, 1, None).unwrap();
+    assert_eq!(reverted.title, "Titre v1");
+    assert_eq!(reverted.description, "Description initiale");
+    assert_eq!(reverted.status, IssueStatus::Open);
+    // Le retour en arrière est tracé comme une nouvelle révision
+    assert_eq!(history.list(issue.id).len(), 3);
+
+    let original = revert_issue(&repo, &history, issue.id, 0, None).unwrap();
+    assert_eq!(original.title, "Titre v0");
+    assert_eq!(revert_issue(&repo, &history, issue.id, 42, None).unwrap_err(), RevertError::RevisionNotFound);
+    assert_eq!(revert_issue(&repo, &history, Uuid::new_v4(), 0, None).unwrap_err(), RevertError::IssueNotFound);
+}
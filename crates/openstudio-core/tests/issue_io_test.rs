@@ -0,0 +1,117 @@
+use openstudio_core::models::issue::IssueStatus;
+use openstudio_core::models::planning::WorkflowTransition;
+use openstudio_core::repositories::in_memory_issue::InMemoryIssueRepo;
+use openstudio_core::repositories::in_memory_issue_history::InMemoryIssueHistoryRepo;
+use openstudio_core::repositories::issue_repository::IssueRepository;
+use openstudio_core::usecases::issue_io::{export_issues, import_issues, ExchangeFormat, ImportedAs};
+use openstudio_core::usecases::project::create_project;
+
+const CSV_BACKLOG: &str = "title,description,status\n\
+Login cassé,\"Le bouton ne répond pas, sur mobile\",Open\n\
+Ajouter le mode sombre,,in progress\n\
+Migrer la CI,Passer à GitHub Actions,Closed\n";
+
+#[test]
+fn test_import_csv_creates_issues() {
+    let repo = InMemoryIssueRepo::new();
+    let project = create_project("Backlog", "desc");
+    let project_id = project.id;
+    let report = import_issues(&repo, &InMemoryIssueHistoryRepo::new(), None, &project, CSV_BACKLOG, ExchangeFormat::Csv, false).unwrap();
+    println!("Rapport d'import: {:?}", report);
+    assert!(report.is_ok());
+    assert_eq!(report.created, 3);
+    // Les issues écrites sont rendues, pour abonner l'auteur de l'import et les assignés
+    assert_eq!(report.written.len(), 3);
+    let issues = repo.list_by_project(project_id).unwrap();
+    assert_eq!(issues.len(), 3);
+    let dark = issues.iter().find(|i| i.title == "Ajouter le mode sombre").unwrap();
+    assert_eq!(dark.status, IssueStatus::InProgress);
+    assert!(issues.iter().any(|i| i.description == "Le bouton ne répond pas, sur mobile"));
+}
+
+#[test]
+fn test_import_dry_run_and_row_errors() {
+    let repo = InMemoryIssueRepo::new();
+    let project = create_project("Backlog", "desc");
+    let project_id = project.id;
+    let report = import_issues(&repo, &InMemoryIssueHistoryRepo::new(), None, &project, CSV_BACKLOG, ExchangeFormat::Csv, true).unwrap();
+    assert!(report.dry_run);
+    assert_eq!(report.created, 3);
+    assert!(report.written.is_empty());
+    assert!(repo.list_by_project(project_id).unwrap().is_empty());
+
+    let bad = "title,status\nOk,Open\n,Open\nStatut inconnu,Blocked\n";
+    let report = import_issues(&repo, &InMemoryIssueHistoryRepo::new(), None, &project, bad, ExchangeFormat::Csv, false).unwrap();
+    println!("Erreurs: {:?}", report.errors);
+    let rows: Vec<usize> = report.errors.iter().map(|e| e.row).collect();
+    assert_eq!(rows, vec![2, 3]);
+    // Tout ou rien : la ligne valide n'a pas été importée non plus
+    assert!(repo.list_by_project(project_id).unwrap().is_empty());
+}
+
+#[test]
+fn test_export_round_trip() {
+    for format in [ExchangeFormat::Csv, ExchangeFormat::Json] {
+        let repo = InMemoryIssueRepo::new();
+        let history = InMemoryIssueHistoryRepo::new();
+        let source = create_project("Source", "desc");
+        let source_project = source.id;
+        import_issues(&repo, &history, None, &source, CSV_BACKLOG, ExchangeFormat::Csv, false).unwrap();
+        let original = repo.list_by_project(source_project).unwrap();
+        let exported = export_issues(&original, format).unwrap();
+
+        // Ré-import dans le même projet : mise à jour, aucun doublon
+        let report = import_issues(&repo, &history, None, &source, &exported, format, false).unwrap();
+        assert_eq!((report.created, report.updated), (0, 3));
+        assert!(report.written.iter().all(|(_, imported_as)| *imported_as == ImportedAs::Updated));
+        assert_eq!(repo.list_by_project(source_project).unwrap().len(), 3);
+        assert!(original.iter().all(|i| history.list(i.id).is_empty()));
+
+        // Import dans un nouveau projet : les données sont conservées
+        let target = InMemoryIssueRepo::new();
+        let target_project = create_project("Cible", "desc");
+        let report = import_issues(&target, &InMemoryIssueHistoryRepo::new(), None, &target_project, &exported, format, false).unwrap();
+        assert_eq!(report.created, 3);
+        for issue in &original {
+            let copy = target.get_by_id(issue.id).unwrap().unwrap();
+            assert_eq!(copy.project_id, target_project.id);
+            assert_eq!(copy.title, issue.title);
+            assert_eq!(copy.description, issue.description);
+            assert_eq!(copy.status, issue.status);
+            assert_eq!(copy.created_at, issue.created_at);
+            assert_eq!(copy.updated_at, issue.updated_at);
+        }
+    }
+}
+
+#[test]
+fn test_import_rejects_malformed_file() {
+    let repo = InMemoryIssueRepo::new();
+    assert!(import_issues(&repo, &InMemoryIssueHistoryRepo::new(), None, &create_project("Backlog", "desc"), "{not json", ExchangeFormat::Json, false).is_err());
+    assert!(import_issues(&repo, &InMemoryIssueHistoryRepo::new(), None, &create_project("Backlog", "desc"), "name,status\nfoo,Open\n", ExchangeFormat::Csv, false).is_err());
+}
+
+#[test]
+fn test_import_follows_project_rules() {
+    let repo = InMemoryIssueRepo::new();
+    let history = InMemoryIssueHistoryRepo::new();
+    let mut project = create_project("Backlog", "desc");
+    project.settings.allowed_issue_states = vec![IssueStatus::Open, IssueStatus::InProgress];
+    project.workflow = vec![WorkflowTransition { from: IssueStatus::Open, to: IssueStatus::InProgress }];
+
+    let report = import_issues(&repo, &history, None, &project, "title,status\nA,In Progress\nB,Closed\n", ExchangeFormat::Csv, false).unwrap();
+    let rows: Vec<usize> = report.errors.iter().map(|e| e.row).collect();
+    assert_eq!(rows, vec![2]);
+    assert!(repo.list_by_project(project.id).unwrap().is_empty());
+
+    import_issues(&repo, &history, None, &project, "title,status\nA,In Progress\n", ExchangeFormat::Csv, false).unwrap();
+    let issue = repo.list_by_project(project.id).unwrap().pop().unwrap();
+    // Une mise à jour suit le workflow, comme une édition
+    let back = format!("id,title,status\n{},A,Open\n", issue.id);
+    let report = import_issues(&repo, &history, None, &project, &back, ExchangeFormat::Csv, false).unwrap();
+    assert_eq!(report.errors.len(), 1);
+    assert!(report.errors[0].message.contains("workflow"));
+    let renamed = format!("id,title,status\n{},A renommée,In Progress\n", issue.id);
+    let report = import_issues(&repo, &history, None, &project, &renamed, ExchangeFormat::Csv, false).unwrap();
+    assert_eq!((report.updated, report.errors.len()), (1, 0));
+}
@@ -0,0 +1,74 @@
+mod common;
+
+use std::sync::Arc;
+use openstudio_core::models::join_request::JoinRequestStatus;
+use openstudio_core::models::notification::NotificationKind;
+use openstudio_core::models::project_status::Visibility;
+use openstudio_core::models::user::ProjectRole;
+use openstudio_core::repositories::in_memory::InMemoryProjectRepo;
+use openstudio_core::repositories::in_memory_join_request::InMemoryJoinRequestRepo;
+use openstudio_core::repositories::in_memory_notification::InMemoryNotificationRepo;
+use openstudio_core::repositories::in_memory_redirect::InMemoryProjectRedirectRepo;
+use openstudio_core::repositories::in_memory_user::InMemoryUserRepo;
+use openstudio_core::usecases::access::ProjectAccess;
+use openstudio_core::usecases::join_request::{JoinRequestContext, JoinRequestError};
+use openstudio_core::usecases::project::create_owned_project;
+
+#[test]
+fn test_join_request_approval_creates_member_and_notifies() {
+    let users = Arc::new(InMemoryUserRepo::new());
+    let access = ProjectAccess::new(users.clone());
+    let requests = InMemoryJoinRequestRepo::new();
+    let notifications = InMemoryNotificationRepo::new();
+    let repo = InMemoryProjectRepo::new();
+    let redirects = InMemoryProjectRedirectRepo::new();
+    let ctx = JoinRequestContext { requests: &requests, users: &users, notifications: &notifications, access: &access };
+    let (alice, bob, carol) = (common::create_user(&users, "alice").id, common::create_user(&users, "bob").id, common::create_user(&users, "carol").id);
+    let mut project = create_owned_project(&repo, &redirects, &users, alice, "Demo", "").unwrap();
+
+    assert_eq!(ctx.submit(&project, bob, "Hi").unwrap_err(), JoinRequestError::NotPublic);
+    project.visibility = Visibility::Public;
+    assert_eq!(ctx.submit(&project, alice, "").unwrap_err(), JoinRequestError::AlreadyMember);
+    let request = ctx.submit(&project, bob, "  I'd like to help  ").unwrap();
+    assert_eq!(request.message, "I'd like to help");
+    assert_eq!(ctx.submit(&project, bob, "again").unwrap_err(), JoinRequestError::AlreadyRequested);
+
+    // Seuls owners et maintainers voient et traitent les demandes
+    assert_eq!(ctx.list(&project, carol, None).unwrap_err(), JoinRequestError::Forbidden);
+    assert_eq!(ctx.approve(&project, carol, request.id, ProjectRole::Viewer).unwrap_err(), JoinRequestError::Forbidden);
+    let member = ctx.approve(&project, alice, request.id, ProjectRole::Contributor).unwrap();
+    assert_eq!(member.role, ProjectRole::Contributor);
+    assert_eq!(access.role(&project, bob), Some(ProjectRole::Contributor));
+    assert_eq!(ctx.reject(&project, alice, request.id).unwrap_err(), JoinRequestError::AlreadyDecided);
+
+    let approved = ctx.list(&project, alice, Some(JoinRequestStatus::Approved)).unwrap();
+    assert_eq!(approved[0].role, Some(ProjectRole::Contributor));
+    assert_eq!(approved[0].decided_by, Some(alice));
+    let notified = notifications.list_for_user(bob);
+    assert_eq!(notified.len(), 1);
+    assert_eq!(notified[0].kind, NotificationKind::JoinRequestApproved);
+}
+
+#[test]
+fn test_rejected_request_can_be_resubmitted() {
+    let users = Arc::new(InMemoryUserRepo::new());
+    let access = ProjectAccess::new(users.clone());
+    let requests = InMemoryJoinRequestRepo::new();
+    let notifications = InMemoryNotificationRepo::new();
+    let repo = InMemoryProjectRepo::new();
+    let redirects = InMemoryProjectRedirectRepo::new();
+    let ctx = JoinRequestContext { requests: &requests, users: &users, notifications: &notifications, access: &access };
+    let (alice, bob, maya) = (common::create_user(&users, "alice").id, common::create_user(&users, "bob").id, common::create_user(&users, "maya").id);
+    let mut project = create_owned_project(&repo, &redirects, &users, alice, "Demo", "").unwrap();
+    project.visibility = Visibility::Public;
+    openstudio_core::usecases::member::add_member(&users, &project, maya, ProjectRole::Maintainer).unwrap();
+
+    let request = ctx.submit(&project, bob, "").unwrap();
+    assert_eq!(ctx.approve(&project, maya, request.id, ProjectRole::Owner).unwrap_err(), JoinRequestError::Forbidden);
+    ctx.reject(&project, maya, request.id).unwrap();
+    assert_eq!(access.role(&project, bob), None);
+    assert_eq!(notifications.list_for_user(bob)[0].kind, NotificationKind::JoinRequestRejected);
+
+    ctx.submit(&project, bob, "Second try").unwrap();
+    assert_eq!(ctx.list(&project, maya, Some(JoinRequestStatus::Pending)).unwrap().len(), 1);
+}
@@ -0,0 +1,71 @@
+use uuid::Uuid;
+use openstudio_core::models::user::ProjectRole;
+use openstudio_core::repositories::in_memory::InMemoryProjectRepo;
+use openstudio_core::repositories::in_memory_org::InMemoryOrgRepo;
+use openstudio_core::repositories::in_memory_redirect::InMemoryProjectRedirectRepo;
+use openstudio_core::repositories::in_memory_user::InMemoryUserRepo;
+use openstudio_core::repositories::project_repository::ProjectRepository;
+use openstudio_core::usecases::member::{add_member, owners, remove_member, transfer_ownership, MemberError};
+use openstudio_core::usecases::organization::OrgContext;
+use openstudio_core::usecases::project::create_owned_project;
+
+#[test]
+fn test_creator_is_owner_and_last_owner_is_kept() {
+    let repo = InMemoryProjectRepo::new();
+    let users = InMemoryUserRepo::new();
+    let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+    let project = create_owned_project(&repo, &InMemoryProjectRedirectRepo::new(), &users, alice, "Atelier", "desc").unwrap();
+    assert_eq!(owners(&users, project.id), vec![alice]);
+
+    assert_eq!(remove_member(&repo, &users, &project, alice), Err(MemberError::NamespaceOwner));
+    add_member(&users, &project, bob, ProjectRole::Owner).unwrap();
+    // Bob peut être rétrogradé tant qu'Alice reste propriétaire
+    add_member(&users, &project, bob, ProjectRole::Contributor).unwrap();
+    assert_eq!(owners(&users, project.id), vec![alice]);
+    assert_eq!(users.list_members(project.id).len(), 2);
+    remove_member(&repo, &users, &project, bob).unwrap();
+}
+
+#[test]
+fn test_removed_member_is_no_longer_default_assignee() {
+    let repo = InMemoryProjectRepo::new();
+    let users = InMemoryUserRepo::new();
+    let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+    let mut project = create_owned_project(&repo, &InMemoryProjectRedirectRepo::new(), &users, alice, "Atelier", "desc").unwrap();
+    add_member(&users, &project, bob, ProjectRole::Contributor).unwrap();
+    project.settings.default_assignee_id = Some(bob);
+    repo.update(project.clone()).unwrap();
+
+    remove_member(&repo, &users, &project, bob).unwrap();
+    assert_eq!(repo.get_by_id(project.id).unwrap().unwrap().settings.default_assignee_id, None);
+}
+
+#[test]
+fn test_transfer_demotes_previous_owner() {
+    let repo = InMemoryProjectRepo::new();
+    let redirects = InMemoryProjectRedirectRepo::new();
+    let users = InMemoryUserRepo::new();
+    let orgs = InMemoryOrgRepo::new();
+    let org_ctx = OrgContext { orgs: &orgs, users: &users };
+    let (alice, bob, carol) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+    let project = create_owned_project(&repo, &redirects, &users, alice, "Atelier", "desc").unwrap();
+
+    assert_eq!(
+        transfer_ownership(&repo, &redirects, &org_ctx, project.clone(), bob, bob).unwrap_err(),
+        MemberError::Forbidden
+    );
+    // Un co-propriétaire ne peut pas sortir le projet du namespace d'Alice
+    add_member(&users, &project, carol, ProjectRole::Owner).unwrap();
+    assert_eq!(
+        transfer_ownership(&repo, &redirects, &org_ctx, project.clone(), carol, carol).unwrap_err(),
+        MemberError::Forbidden
+    );
+    let moved = transfer_ownership(&repo, &redirects, &org_ctx, project, alice, bob).unwrap();
+    println!("Projet transféré: {:?}", moved);
+    assert_eq!(moved.owner_id, Some(bob));
+    assert_eq!(owners(&users, moved.id), vec![carol, bob]);
+    let alice_role = users.list_members(moved.id).into_iter().find(|m| m.user_id == alice).map(|m| m.role);
+    assert_eq!(alice_role, Some(ProjectRole::Maintainer));
+    // Le nouveau propriétaire est désormais le dernier : il ne peut pas partir
+    assert_eq!(remove_member(&repo, &users, &moved, bob), Err(MemberError::NamespaceOwner));
+}
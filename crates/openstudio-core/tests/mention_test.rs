@@ -0,0 +1,76 @@
+mod common;
+
+use std::sync::Arc;
+use chrono::Utc;
+use uuid::Uuid;
+use openstudio_core::models::issue::Issue;
+use openstudio_core::models::project_status::Visibility;
+use openstudio_core::models::user::{ProjectMember, ProjectRole};
+use openstudio_core::repositories::in_memory_notification::{InMemoryMentionRepo, InMemoryNotificationRepo};
+use openstudio_core::repositories::in_memory_user::InMemoryUserRepo;
+use openstudio_core::usecases::access::ProjectAccess;
+use openstudio_core::usecases::mention::{parse_mentions, MentionContext};
+use openstudio_core::usecases::project::create_project;
+
+fn issue(project_id: Uuid, description: &str) -> Issue {
+    Issue { description: description.to_string(), ..common::issue(project_id, "Revoir le parser") }
+}
+
+#[test]
+fn test_parse_mentions() {
+    let text = "Merci @alice et @bob_2. Voir aussi @alice, mais pas bob@example.com ni @";
+    assert_eq!(parse_mentions(text), vec!["alice", "bob_2"]);
+    assert_eq!(parse_mentions("(@carol) @dave-x!"), vec!["carol", "dave-x"]);
+}
+
+#[test]
+fn test_mentions_notify_new_users_once() {
+    let users = Arc::new(InMemoryUserRepo::new());
+    let access = ProjectAccess::new(users.clone());
+    let mentions = InMemoryMentionRepo::new();
+    let notifications = InMemoryNotificationRepo::new();
+    let alice = common::create_user(&users, "alice");
+    let bob = common::create_user(&users, "bob");
+    let context = MentionContext { users: &users, access: &access, mentions: &mentions, notifications: &notifications };
+    let mut project = create_project("Public", "desc");
+    project.visibility = Visibility::Public;
+    let issue = issue(project.id, "@alice peux-tu regarder ? cc @inconnu");
+
+    let recorded = context.process(Some(&project), &issue, None, &issue.description, Some(bob.id));
+    assert_eq!(recorded.len(), 1);
+    assert_eq!(notifications.list_for_user(alice.id).len(), 1);
+
+    // Nouvelle mention dans un commentaire : lien stocké, mais pas de seconde notification
+    let comment_id = Uuid::new_v4();
+    let recorded = context.process(Some(&project), &issue, Some(comment_id), "@alice @bob", Some(bob.id));
+    assert_eq!(recorded.len(), 2);
+    assert_eq!(notifications.list_for_user(alice.id).len(), 1);
+    // L'auteur ne se notifie pas lui-même
+    assert!(notifications.list_for_user(bob.id).is_empty());
+    assert_eq!(mentions.list_by_issue(issue.id).len(), 3);
+}
+
+#[test]
+fn test_mentions_do_not_leak_private_projects() {
+    let users = Arc::new(InMemoryUserRepo::new());
+    let access = ProjectAccess::new(users.clone());
+    let mentions = InMemoryMentionRepo::new();
+    let notifications = InMemoryNotificationRepo::new();
+    let member = common::create_user(&users, "member");
+    let outsider = common::create_user(&users, "outsider");
+    let project = create_project("Secret", "desc");
+    assert_eq!(project.visibility, Visibility::Private);
+    users.add_member(ProjectMember {
+        user_id: member.id,
+        project_id: project.id,
+        role: ProjectRole::Contributor,
+        joined_at: Utc::now(),
+    });
+    let context = MentionContext { users: &users, access: &access, mentions: &mentions, notifications: &notifications };
+    let issue = issue(project.id, "@member @outsider");
+    context.process(Some(&project), &issue, None, &issue.description, None);
+
+    assert_eq!(notifications.list_for_user(member.id).len(), 1);
+    assert!(notifications.list_for_user(outsider.id).is_empty());
+    assert!(mentions.list_by_issue(issue.id).iter().all(|m| m.user_id != outsider.id));
+}
@@ -0,0 +1,89 @@
+mod common;
+
+use std::sync::Arc;
+use openstudio_core::models::organization::OrgRole;
+use openstudio_core::models::project::OwnerKind;
+use openstudio_core::models::user::ProjectRole;
+use openstudio_core::repositories::in_memory::InMemoryProjectRepo;
+use openstudio_core::repositories::in_memory_org::InMemoryOrgRepo;
+use openstudio_core::repositories::in_memory_redirect::InMemoryProjectRedirectRepo;
+use openstudio_core::repositories::in_memory_user::InMemoryUserRepo;
+use openstudio_core::usecases::access::ProjectAccess;
+use openstudio_core::usecases::member::{transfer_ownership, MemberError};
+use openstudio_core::usecases::organization::{OrgContext, OrgError};
+use openstudio_core::usecases::project::{create_owned_project, find_project_by_slug, SlugLookup};
+
+#[test]
+fn test_organization_membership_rules() {
+    let users = InMemoryUserRepo::new();
+    let orgs = InMemoryOrgRepo::new();
+    let ctx = OrgContext { orgs: &orgs, users: &users };
+    let (alice, bob) = (common::create_user(&users, "alice").id, common::create_user(&users, "bob").id);
+
+    assert_eq!(ctx.create_organization(alice, "Acme Corp", "Acme", "").unwrap_err(), OrgError::InvalidSlug);
+    assert_eq!(ctx.create_organization(alice, "bob", "Bob", "").unwrap_err(), OrgError::SlugTaken);
+    let org = ctx.create_organization(alice, "acme", "Acme", "").unwrap();
+    assert_eq!(ctx.create_organization(bob, "acme", "Autre", "").unwrap_err(), OrgError::SlugTaken);
+
+    // Un admin ne peut pas nommer de propriétaire, ni le dernier propriétaire partir
+    ctx.set_member(&org, alice, bob, OrgRole::Admin).unwrap();
+    assert_eq!(ctx.set_member(&org, bob, bob, OrgRole::Owner), Err(OrgError::Forbidden));
+    assert_eq!(ctx.set_member(&org, alice, alice, OrgRole::Member), Err(OrgError::LastOwner));
+    assert_eq!(ctx.remove_member(&org, alice, alice), Err(OrgError::LastOwner));
+    ctx.set_member(&org, alice, bob, OrgRole::Owner).unwrap();
+    ctx.remove_member(&org, alice, alice).unwrap();
+    assert_eq!(orgs.list_members(org.id).len(), 1);
+}
+
+#[test]
+fn test_team_grants_and_org_roles_feed_project_access() {
+    let users = Arc::new(InMemoryUserRepo::new());
+    let orgs = Arc::new(InMemoryOrgRepo::new());
+    let access = ProjectAccess::with_orgs(users.clone(), orgs.clone());
+    let repo = InMemoryProjectRepo::new();
+    let redirects = InMemoryProjectRedirectRepo::new();
+    let ctx = OrgContext { orgs: &orgs, users: &users };
+    let (alice, bob, carol, dave) = (common::create_user(&users, "alice").id, common::create_user(&users, "bob").id, common::create_user(&users, "carol").id, common::create_user(&users, "dave").id);
+
+    let org = ctx.create_organization(alice, "acme", "Acme", "").unwrap();
+    ctx.set_member(&org, alice, bob, OrgRole::Admin).unwrap();
+    ctx.set_member(&org, alice, carol, OrgRole::Member).unwrap();
+    let api = ctx.create_project(&repo, &redirects, &org, bob, "API", "").unwrap();
+    let web = ctx.create_project(&repo, &redirects, &org, bob, "Web", "").unwrap();
+    assert_eq!(api.owner_kind, OwnerKind::Organization);
+    assert!(matches!(find_project_by_slug(&repo, &redirects, org.id, "api").unwrap(), Some(SlugLookup::Found(_))));
+    assert_eq!(ctx.create_project(&repo, &redirects, &org, carol, "Perso", "").unwrap_err(), OrgError::Forbidden);
+
+    assert_eq!(access.role(&api, alice), Some(ProjectRole::Owner));
+    // Pas de rôle direct pour le créateur : celui d'admin de l'organisation s'applique
+    assert_eq!(access.role(&web, bob), Some(ProjectRole::Maintainer));
+    assert!(users.list_members(web.id).is_empty());
+    assert_eq!(access.role(&api, carol), None);
+    assert!(!access.can_view(&api, Some(carol)));
+
+    let team = ctx.create_team(&org, alice, "backend", "Backend").unwrap();
+    assert_eq!(ctx.add_team_member(&org, alice, team.clone(), dave).unwrap_err(), OrgError::NotOrgMember);
+    let team = ctx.add_team_member(&org, alice, team, carol).unwrap();
+    let outside = create_owned_project(&repo, &redirects, &users, dave, "Perso", "").unwrap();
+    assert_eq!(
+        ctx.grant_team(&repo, &org, alice, &team, &[api.id, outside.id], ProjectRole::Contributor).unwrap_err(),
+        OrgError::ProjectNotInOrg
+    );
+    // Un admin ne peut pas faire d'une équipe le propriétaire des projets
+    assert_eq!(ctx.grant_team(&repo, &org, bob, &team, &[api.id], ProjectRole::Owner).unwrap_err(), OrgError::Forbidden);
+    ctx.grant_team(&repo, &org, alice, &team, &[api.id, web.id], ProjectRole::Contributor).unwrap();
+    assert_eq!(access.role(&api, carol), Some(ProjectRole::Contributor));
+    assert_eq!(access.role(&web, carol), Some(ProjectRole::Contributor));
+
+    // Propriétaire du projet par son équipe, mais simple membre de l'organisation : il ne peut pas l'en sortir
+    ctx.grant_team(&repo, &org, alice, &team, &[web.id], ProjectRole::Owner).unwrap();
+    assert_eq!(access.role(&web, carol), Some(ProjectRole::Owner));
+    assert!(!ctx.can_release(&web, carol));
+    assert_eq!(transfer_ownership(&repo, &redirects, &ctx, web.clone(), carol, carol).unwrap_err(), MemberError::Forbidden);
+    let moved = transfer_ownership(&repo, &redirects, &ctx, web, alice, dave).unwrap();
+    assert_eq!((moved.owner_kind, moved.owner_id), (OwnerKind::User, Some(dave)));
+
+    // Quitter l'organisation retire aussi des équipes
+    ctx.remove_member(&org, alice, carol).unwrap();
+    assert_eq!(access.role(&api, carol), None);
+}
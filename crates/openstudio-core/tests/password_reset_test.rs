@@ -0,0 +1,101 @@
+mod common;
+
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+use openstudio_core::models::user::User;
+use openstudio_core::repositories::in_memory_password_reset::InMemoryPasswordResetRepo;
+use openstudio_core::repositories::in_memory_user::InMemoryUserRepo;
+use openstudio_core::usecases::token_hash::hash_token;
+use openstudio_core::usecases::password_reset::{PasswordResetContext, PasswordResetError};
+use openstudio_core::usecases::throttle::MAX_SENDS_PER_DAY;
+
+fn alice(users: &InMemoryUserRepo) -> User {
+    users.create_user(User { password: "old-hash".to_string(), ..common::user("alice") })
+}
+
+#[test]
+fn test_reset_token_is_single_use() {
+    let resets = InMemoryPasswordResetRepo::new();
+    let users = InMemoryUserRepo::new();
+    let ctx = PasswordResetContext { resets: &resets, users: &users };
+    let alice = alice(&users);
+
+    assert!(ctx.request("nobody@example.com").is_none());
+    let (user, raw) = ctx.request(" Alice@Example.com ").unwrap();
+    assert_eq!(user.id, alice.id);
+    assert!(resets.find_by_token_hash(&raw).is_none());
+
+    assert_eq!(ctx.reset(&raw, "short", |p| Some(p.to_string())).unwrap_err(), PasswordResetError::PasswordTooShort);
+    // Un hachage raté ne consomme pas le jeton et ne touche pas au mot de passe
+    assert_eq!(ctx.reset(&raw, "correct horse", |_| None).unwrap_err(), PasswordResetError::HashFailed);
+    assert_eq!(users.get_user(alice.id).unwrap().password, "old-hash");
+    let updated = ctx.reset(&raw, "correct horse", |p| Some(format!("hashed:{}", p))).unwrap();
+    assert_eq!(updated.password, "hashed:correct horse");
+    // Le lien a été reçu à cette adresse : elle est du même coup vérifiée
+    assert!(updated.email_verified);
+    assert_eq!(users.get_user(alice.id).unwrap().password, "hashed:correct horse");
+    assert_eq!(ctx.reset(&raw, "another password", |p| Some(p.to_string())).unwrap_err(), PasswordResetError::AlreadyUsed);
+    assert_eq!(ctx.reset("forged", "another password", |p| Some(p.to_string())).unwrap_err(), PasswordResetError::InvalidToken);
+}
+
+#[test]
+fn test_mark_used_succeeds_once() {
+    let resets = InMemoryPasswordResetRepo::new();
+    let users = InMemoryUserRepo::new();
+    let ctx = PasswordResetContext { resets: &resets, users: &users };
+    alice(&users);
+
+    let (_, raw) = ctx.request("alice@example.com").unwrap();
+    let token = resets.find_by_token_hash(&hash_token(&raw)).unwrap();
+    let used = resets.mark_used(token.id, Utc::now()).unwrap();
+    assert!(used.used_at.is_some());
+    // Une seconde consommation, concurrente ou non, échoue et renvoie le jeton stocké
+    assert_eq!(resets.mark_used(token.id, Utc::now()).unwrap_err().unwrap().used_at, used.used_at);
+    assert!(resets.mark_used(Uuid::new_v4(), Utc::now()).unwrap_err().is_none());
+    assert_eq!(ctx.reset(&raw, "correct horse", |p| Some(p.to_string())).unwrap_err(), PasswordResetError::AlreadyUsed);
+}
+
+#[test]
+fn test_new_request_replaces_previous_and_tokens_expire() {
+    let resets = InMemoryPasswordResetRepo::new();
+    let users = InMemoryUserRepo::new();
+    let ctx = PasswordResetContext { resets: &resets, users: &users };
+    alice(&users);
+
+    let (_, first) = ctx.request("alice@example.com").unwrap();
+    let mut token = resets.find_by_token_hash(&hash_token(&first)).unwrap();
+    token.created_at = Utc::now() - Duration::minutes(2);
+    resets.save(token);
+    let (_, second) = ctx.request("alice@example.com").unwrap();
+    assert_eq!(ctx.reset(&first, "new password", |p| Some(p.to_string())).unwrap_err(), PasswordResetError::InvalidToken);
+
+    let mut token = resets.find_by_token_hash(&hash_token(&second)).unwrap();
+    token.expires_at = Utc::now() - Duration::minutes(1);
+    resets.save(token);
+    assert_eq!(ctx.reset(&second, "new password", |p| Some(p.to_string())).unwrap_err(), PasswordResetError::Expired);
+}
+
+#[test]
+fn test_requests_are_throttled_per_account() {
+    let resets = InMemoryPasswordResetRepo::new();
+    let users = InMemoryUserRepo::new();
+    let ctx = PasswordResetContext { resets: &resets, users: &users };
+    let alice = alice(&users);
+
+    // Une demande répétée aussitôt n'envoie rien et n'annule pas le lien déjà reçu
+    let (_, first) = ctx.request("alice@example.com").unwrap();
+    assert!(ctx.request("alice@example.com").is_none());
+    assert!(ctx.reset(&first, "new password", |p| Some(p.to_string())).is_ok());
+
+    // Demandes espacées mais trop nombreuses sur la journée
+    for hours in 1..MAX_SENDS_PER_DAY as i64 {
+        let mut token = resets.created_since(alice.id, Utc::now() - Duration::hours(1)).pop().unwrap();
+        token.created_at = Utc::now() - Duration::hours(hours);
+        resets.save(token);
+        ctx.request("alice@example.com").unwrap();
+    }
+    let mut token = resets.created_since(alice.id, Utc::now() - Duration::minutes(1)).pop().unwrap();
+    token.created_at = Utc::now() - Duration::minutes(5);
+    resets.save(token);
+    assert!(ctx.request("alice@example.com").is_none());
+}
@@ -0,0 +1,55 @@
+use std::sync::Arc;
+use chrono::Utc;
+use uuid::Uuid;
+use openstudio_core::models::project_status::Visibility;
+use openstudio_core::models::user::{ProjectMember, ProjectRole};
+use openstudio_core::repositories::in_memory_user::InMemoryUserRepo;
+use openstudio_core::usecases::access::ProjectAccess;
+use openstudio_core::usecases::policy::{allows, Action};
+use openstudio_core::usecases::project::create_project;
+
+#[test]
+fn test_role_matrix() {
+    assert!(allows(ProjectRole::Viewer, Action::Comment));
+    assert!(!allows(ProjectRole::Viewer, Action::EditIssue));
+    assert!(allows(ProjectRole::Contributor, Action::EditWiki));
+    assert!(!allows(ProjectRole::Contributor, Action::DeleteIssue));
+    assert!(allows(ProjectRole::Maintainer, Action::ManageMembers));
+    assert!(!allows(ProjectRole::Maintainer, Action::DeleteProject));
+    assert!(!allows(ProjectRole::Maintainer, Action::GrantOwner));
+    assert!(allows(ProjectRole::Owner, Action::TransferProject));
+}
+
+#[test]
+fn test_non_members_follow_project_settings() {
+    let users = Arc::new(InMemoryUserRepo::new());
+    let access = ProjectAccess::new(users.clone());
+    let (owner, maintainer, outsider) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+    let mut project = create_project("Demo", "desc");
+    project.owner_id = Some(owner);
+    project.visibility = Visibility::Public;
+    users.add_member(ProjectMember {
+        user_id: maintainer,
+        project_id: project.id,
+        role: ProjectRole::Maintainer,
+        joined_at: Utc::now(),
+    });
+
+    assert!(access.can(&project, Some(outsider), Action::OpenIssue));
+    assert!(access.can(&project, Some(outsider), Action::Comment));
+    assert!(!access.can(&project, Some(outsider), Action::EditIssue));
+    assert!(!access.can(&project, None, Action::Comment));
+    project.settings.non_members_can_open_issues = false;
+    assert!(!access.can(&project, Some(outsider), Action::OpenIssue));
+
+    // Un maintainer gère les membres mais ne nomme pas d'owner
+    assert!(access.can_grant(&project, maintainer, ProjectRole::Maintainer));
+    assert!(!access.can_grant(&project, maintainer, ProjectRole::Owner));
+    assert!(access.can_grant(&project, owner, ProjectRole::Owner));
+
+    // Un projet privé reste fermé aux non-membres
+    project.visibility = Visibility::Private;
+    project.settings.non_members_can_open_issues = true;
+    assert!(!access.can(&project, Some(outsider), Action::Comment));
+    assert!(!access.can(&project, Some(outsider), Action::OpenIssue));
+}
@@ -0,0 +1,56 @@
+mod common;
+
+use uuid::Uuid;
+use openstudio_core::models::issue::{Issue, IssueStatus};
+use openstudio_core::models::planning::{IssueTemplate, Label, Milestone, WorkflowTransition};
+use openstudio_core::repositories::in_memory::InMemoryProjectRepo;
+use openstudio_core::repositories::in_memory_issue::InMemoryIssueRepo;
+use openstudio_core::repositories::in_memory_redirect::InMemoryProjectRedirectRepo;
+use openstudio_core::repositories::in_memory_user::InMemoryUserRepo;
+use openstudio_core::repositories::issue_repository::IssueRepository;
+use openstudio_core::repositories::project_repository::ProjectRepository;
+use openstudio_core::usecases::member::owners;
+use openstudio_core::usecases::project::{create_owned_project, fork_project, ForkOptions};
+
+fn issue(project_id: Uuid, title: &str, status: IssueStatus) -> Issue {
+    Issue { status, assignee_id: Some(Uuid::new_v4()), estimate_minutes: Some(30), ..common::issue(project_id, title) }
+}
+
+#[test]
+fn test_fork_copies_template() {
+    let repo = InMemoryProjectRepo::new();
+    let redirects = InMemoryProjectRedirectRepo::new();
+    let users = InMemoryUserRepo::new();
+    let issues = InMemoryIssueRepo::new();
+    let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+    let mut template = create_owned_project(&repo, &redirects, &users, alice, "Modèle jeu", "Base d'un jeu").unwrap();
+    template.is_template = true;
+    template.labels = vec![Label { name: "bug".to_string(), color: "#d73a4a".to_string() }];
+    template.workflow = vec![WorkflowTransition { from: IssueStatus::Open, to: IssueStatus::Closed }];
+    template.issue_templates = vec![IssueTemplate { name: "Bug".to_string(), title: "[bug] ".to_string(), body: String::new() }];
+    template.milestones = vec![Milestone { id: Uuid::new_v4(), title: "v1".to_string(), description: String::new(), due_on: None }];
+    repo.update(template.clone()).unwrap();
+    issues.save(issue(template.id, "Ouvert", IssueStatus::Open)).unwrap();
+    issues.save(issue(template.id, "Fermé", IssueStatus::Closed)).unwrap();
+
+    let bare = fork_project(&repo, &redirects, &users, &issues, &template, bob, &ForkOptions::default()).unwrap();
+    assert!(issues.list_by_project(bare.id).unwrap().is_empty());
+
+    let options = ForkOptions { name: Some("Mon jeu".to_string()), include_open_issues: true };
+    let fork = fork_project(&repo, &redirects, &users, &issues, &template, bob, &options).unwrap();
+    println!("Fork: {:?}", fork);
+    assert_eq!(fork.forked_from, Some(template.id));
+    assert_eq!((fork.slug.as_str(), fork.owner_id), ("mon-jeu", Some(bob)));
+    assert!(!fork.is_template);
+    assert_eq!(fork.description, template.description);
+    assert_eq!(fork.labels, template.labels);
+    assert_eq!(fork.workflow, template.workflow);
+    assert_eq!(fork.issue_templates.len(), 1);
+    assert_ne!(fork.milestones[0].id, template.milestones[0].id);
+    assert_eq!(owners(&users, fork.id), vec![bob]);
+
+    let copied = issues.list_by_project(fork.id).unwrap();
+    assert_eq!(copied.len(), 1);
+    assert_eq!(copied[0].title, "Ouvert");
+    assert_eq!((copied[0].author_id, copied[0].assignee_id), (Some(bob), None));
+}
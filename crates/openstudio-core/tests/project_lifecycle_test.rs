@@ -0,0 +1,53 @@
+use std::sync::Arc;
+use chrono::Utc;
+use uuid::Uuid;
+use openstudio_core::models::project_status::ProjectStatus;
+use openstudio_core::models::user::{ProjectMember, ProjectRole};
+use openstudio_core::repositories::in_memory::InMemoryProjectRepo;
+use openstudio_core::repositories::in_memory_redirect::InMemoryProjectRedirectRepo;
+use openstudio_core::repositories::in_memory_user::InMemoryUserRepo;
+use openstudio_core::usecases::access::ProjectAccess;
+use openstudio_core::usecases::project::{
+    archive_project, create_owned_project, publish_project, unarchive_project, LifecycleError,
+};
+
+#[test]
+fn test_lifecycle_transitions() {
+    let repo = InMemoryProjectRepo::new();
+    let users = Arc::new(InMemoryUserRepo::new());
+    let access = ProjectAccess::new(users.clone());
+    let owner = Uuid::new_v4();
+    let project = create_owned_project(&repo, &InMemoryProjectRedirectRepo::new(), &users, owner, "Cycle", "desc").unwrap();
+    assert_eq!(project.status, ProjectStatus::Draft);
+
+    // Un brouillon ne peut pas être archivé directement
+    assert_eq!(
+        archive_project(&repo, &access, project.id, owner).unwrap_err(),
+        LifecycleError::InvalidTransition { from: ProjectStatus::Draft, to: ProjectStatus::Archived }
+    );
+    assert_eq!(publish_project(&repo, &access, project.id, owner).unwrap().status, ProjectStatus::Active);
+    let archived = archive_project(&repo, &access, project.id, owner).unwrap();
+    assert!(archived.is_read_only());
+    assert_eq!(unarchive_project(&repo, &access, project.id, owner).unwrap().status, ProjectStatus::Active);
+    assert_eq!(publish_project(&repo, &access, project.id, owner).unwrap_err(),
+        LifecycleError::InvalidTransition { from: ProjectStatus::Active, to: ProjectStatus::Active });
+}
+
+#[test]
+fn test_lifecycle_is_owner_only() {
+    let repo = InMemoryProjectRepo::new();
+    let users = Arc::new(InMemoryUserRepo::new());
+    let access = ProjectAccess::new(users.clone());
+    let (owner, maintainer, outsider) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+    let project = create_owned_project(&repo, &InMemoryProjectRedirectRepo::new(), &users, owner, "Cycle", "desc").unwrap();
+    users.add_member(ProjectMember {
+        user_id: maintainer,
+        project_id: project.id,
+        role: ProjectRole::Maintainer,
+        joined_at: Utc::now(),
+    });
+    assert_eq!(publish_project(&repo, &access, project.id, maintainer).unwrap_err(), LifecycleError::Forbidden);
+    // Le projet est privé : un inconnu ne doit même pas apprendre qu'il existe
+    assert_eq!(publish_project(&repo, &access, project.id, outsider).unwrap_err(), LifecycleError::NotFound);
+    assert_eq!(publish_project(&repo, &access, Uuid::new_v4(), owner).unwrap_err(), LifecycleError::NotFound);
+}
@@ -0,0 +1,62 @@
+use uuid::Uuid;
+use openstudio_core::models::issue::IssueStatus;
+use openstudio_core::models::project_settings::{NotificationPolicy, ProjectSettings};
+use openstudio_core::models::project_status::Visibility;
+use openstudio_core::models::subscription::{SubscriptionReason, SubscriptionTarget};
+use openstudio_core::repositories::in_memory::InMemoryProjectRepo;
+use openstudio_core::repositories::in_memory_issue::InMemoryIssueRepo;
+use openstudio_core::repositories::in_memory_redirect::InMemoryProjectRedirectRepo;
+use openstudio_core::repositories::in_memory_subscription::InMemorySubscriptionRepo;
+use openstudio_core::repositories::in_memory_user::InMemoryUserRepo;
+use openstudio_core::usecases::project::{create_owned_project, create_project, fork_project, ForkOptions};
+use openstudio_core::usecases::subscription::{auto_subscribe, unwatch_as_member, watch_as_member};
+
+#[test]
+fn test_settings_validation_and_fork() {
+    let mut settings = ProjectSettings::default();
+    assert!(settings.validate().is_ok());
+    assert!(settings.allows_state(&IssueStatus::InProgress));
+    settings.allowed_issue_states = vec![IssueStatus::Closed];
+    assert!(settings.validate().is_err());
+
+    let repo = InMemoryProjectRepo::new();
+    let redirects = InMemoryProjectRedirectRepo::new();
+    let users = InMemoryUserRepo::new();
+    let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+    let mut source = create_owned_project(&repo, &redirects, &users, alice, "Moteur", "desc").unwrap();
+    source.settings = ProjectSettings {
+        default_visibility: Visibility::Public,
+        non_members_can_open_issues: false,
+        default_assignee_id: Some(alice),
+        allowed_issue_states: vec![IssueStatus::Open, IssueStatus::Closed],
+        notification_policy: NotificationPolicy::Watching,
+    };
+    let fork = fork_project(&repo, &redirects, &users, &InMemoryIssueRepo::new(), &source, bob, &ForkOptions::default()).unwrap();
+    // Le fork reprend les réglages, sauf l'assigné par défaut qui n'en est pas membre
+    assert_eq!(fork.visibility, Visibility::Public);
+    assert_eq!(fork.settings.allowed_issue_states, source.settings.allowed_issue_states);
+    assert!(!fork.settings.non_members_can_open_issues);
+    assert_eq!(fork.settings.default_assignee_id, None);
+}
+
+#[test]
+fn test_notification_policy_drives_auto_subscriptions() {
+    let subscriptions = InMemorySubscriptionRepo::new();
+    let (user, issue_id) = (Uuid::new_v4(), Uuid::new_v4());
+    let mut project = create_project("Calme", "desc");
+
+    project.settings.notification_policy = NotificationPolicy::MentionsOnly;
+    assert!(!auto_subscribe(&subscriptions, Some(&project), user, issue_id, SubscriptionReason::Author));
+    assert!(!watch_as_member(&subscriptions, &project, user));
+    // Sans projet, la politique par défaut s'applique
+    assert!(auto_subscribe(&subscriptions, None, user, issue_id, SubscriptionReason::Author));
+
+    project.settings.notification_policy = NotificationPolicy::Watching;
+    assert!(watch_as_member(&subscriptions, &project, user));
+    assert_eq!(subscriptions.watchers(SubscriptionTarget::Project(project.id))[0].reason, SubscriptionReason::Member);
+    assert!(unwatch_as_member(&subscriptions, project.id, user));
+
+    // Un abonnement demandé par l'utilisateur survit à son départ
+    subscriptions.subscribe(user, SubscriptionTarget::Project(project.id), SubscriptionReason::Manual);
+    assert!(!unwatch_as_member(&subscriptions, project.id, user));
+}
@@ -0,0 +1,68 @@
+use uuid::Uuid;
+use openstudio_core::repositories::in_memory::InMemoryProjectRepo;
+use openstudio_core::repositories::in_memory_redirect::InMemoryProjectRedirectRepo;
+use openstudio_core::repositories::in_memory_user::InMemoryUserRepo;
+use openstudio_core::repositories::project_repository::ProjectRepository;
+use openstudio_core::usecases::project::{
+    create_owned_project, find_project_by_slug, move_project, rename_project, slugify, SlugLookup,
+};
+
+#[test]
+fn test_slugify() {
+    assert_eq!(slugify("OpenStudio"), "openstudio");
+    assert_eq!(slugify("  Éditeur de Cartes -- v2! "), "editeur-de-cartes-v2");
+    assert_eq!(slugify("🚀🚀"), "project");
+}
+
+#[test]
+fn test_slugs_are_unique_per_owner() {
+    let repo = InMemoryProjectRepo::new();
+    let redirects = InMemoryProjectRedirectRepo::new();
+    let users = InMemoryUserRepo::new();
+    let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+    let first = create_owned_project(&repo, &redirects, &users, alice, "Mon Projet", "desc").unwrap();
+    let second = create_owned_project(&repo, &redirects, &users, alice, "mon projet", "desc").unwrap();
+    let other = create_owned_project(&repo, &redirects, &users, bob, "Mon Projet", "desc").unwrap();
+    assert_eq!(first.slug, "mon-projet");
+    assert_eq!(second.slug, "mon-projet-2");
+    assert_eq!(other.slug, "mon-projet");
+    // Un slug réservé par une route n'est jamais attribué
+    let reserved = create_owned_project(&repo, &redirects, &users, alice, "Issues", "desc").unwrap();
+    assert_eq!(reserved.slug, "issues-2");
+}
+
+#[test]
+fn test_rename_and_move_leave_redirects() {
+    let repo = InMemoryProjectRepo::new();
+    let redirects = InMemoryProjectRedirectRepo::new();
+    let users = InMemoryUserRepo::new();
+    let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+    let project = create_owned_project(&repo, &redirects, &users, alice, "Ancien nom", "desc").unwrap();
+
+    let renamed = rename_project(&repo, &redirects, project.clone(), "Nouveau nom").unwrap();
+    assert_eq!(renamed.slug, "nouveau-nom");
+    assert!(matches!(
+        find_project_by_slug(&repo, &redirects, alice, "nouveau-nom").unwrap(),
+        Some(SlugLookup::Found(_))
+    ));
+    match find_project_by_slug(&repo, &redirects, alice, "ancien-nom").unwrap() {
+        Some(SlugLookup::Moved(p)) => assert_eq!(p.slug, "nouveau-nom"),
+        _ => panic!("expected a redirect"),
+    }
+
+    let moved = move_project(&repo, &redirects, renamed, bob).unwrap();
+    assert_eq!(moved.owner_id, Some(bob));
+    assert_eq!(repo.get_by_id(project.id).unwrap().unwrap().owner_id, Some(bob));
+    match find_project_by_slug(&repo, &redirects, alice, "nouveau-nom").unwrap() {
+        Some(SlugLookup::Moved(p)) => assert_eq!(p.owner_id, Some(bob)),
+        _ => panic!("expected a redirect"),
+    }
+
+    // Un nouveau projet peut reprendre une ancienne adresse
+    let reuse = create_owned_project(&repo, &redirects, &users, alice, "Ancien nom", "desc").unwrap();
+    assert_eq!(reuse.slug, "ancien-nom");
+    match find_project_by_slug(&repo, &redirects, alice, "ancien-nom").unwrap() {
+        Some(SlugLookup::Found(p)) => assert_eq!(p.id, reuse.id),
+        _ => panic!("expected the new project"),
+    }
+}
@@ -0,0 +1,60 @@
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+use openstudio_core::repositories::in_memory_refresh_token::InMemoryRefreshTokenRepo;
+use openstudio_core::usecases::session::{SessionContext, SessionError};
+
+#[test]
+fn test_refresh_token_reuse_revokes_the_family() {
+    let tokens = InMemoryRefreshTokenRepo::new();
+    let ctx = SessionContext { tokens: &tokens };
+    let alice = Uuid::new_v4();
+    let first = ctx.start(alice);
+    let other_session = ctx.start(alice);
+
+    assert_eq!(ctx.rotate(first.id, Uuid::new_v4()).unwrap_err(), SessionError::NotFound);
+    let second = ctx.rotate(first.id, alice).unwrap();
+    assert_eq!(second.family_id, first.family_id);
+    assert_ne!(second.id, first.id);
+
+    // Rejouer le premier jeton ferme la session, y compris pour son successeur
+    assert_eq!(ctx.rotate(first.id, alice).unwrap_err(), SessionError::Reused);
+    assert!(!ctx.is_active(first.family_id));
+    assert_eq!(ctx.rotate(second.id, alice).unwrap_err(), SessionError::Revoked);
+
+    // Les autres sessions ne sont pas touchées
+    assert!(ctx.is_active(other_session.family_id));
+    ctx.rotate(other_session.id, alice).unwrap();
+
+    // Échanges simultanés du même jeton : un seul réussit, et la session est fermée
+    let raced = ctx.start(alice);
+    let results: Vec<_> = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..8).map(|_| scope.spawn(|| ctx.rotate(raced.id, alice))).collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+    assert_eq!(results.iter().filter(|r| r.is_ok()).count(), 1);
+    assert!(!ctx.is_active(raced.family_id));
+}
+
+#[test]
+fn test_logout_and_expiry() {
+    let tokens = InMemoryRefreshTokenRepo::new();
+    let ctx = SessionContext { tokens: &tokens };
+    let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+    let laptop = ctx.start(alice);
+    let phone = ctx.start(alice);
+    let bob_session = ctx.start(bob);
+
+    assert_eq!(ctx.logout(laptop.family_id, bob).unwrap_err(), SessionError::NotFound);
+    ctx.logout(laptop.family_id, alice).unwrap();
+    assert_eq!(ctx.rotate(laptop.id, alice).unwrap_err(), SessionError::Revoked);
+    assert!(ctx.is_active(phone.family_id));
+
+    assert_eq!(ctx.logout_everywhere(alice), 1);
+    assert!(!ctx.is_active(phone.family_id));
+    assert!(ctx.is_active(bob_session.family_id));
+
+    let mut expired = ctx.start(bob);
+    expired.expires_at = Utc::now() - Duration::minutes(1);
+    tokens.save(expired.clone());
+    assert_eq!(ctx.rotate(expired.id, bob).unwrap_err(), SessionError::Expired);
+}
@@ -0,0 +1,78 @@
+mod common;
+
+use std::sync::Arc;
+use uuid::Uuid;
+use openstudio_core::models::issue::Issue;
+use openstudio_core::models::project_status::Visibility;
+use openstudio_core::repositories::in_memory::InMemoryProjectRepo;
+use openstudio_core::repositories::in_memory_comment::InMemoryCommentRepo;
+use openstudio_core::repositories::in_memory_issue::InMemoryIssueRepo;
+use openstudio_core::repositories::in_memory_social::InMemorySocialRepo;
+use openstudio_core::repositories::in_memory_user::InMemoryUserRepo;
+use openstudio_core::repositories::issue_repository::IssueRepository;
+use openstudio_core::repositories::project_repository::ProjectRepository;
+use openstudio_core::usecases::access::ProjectAccess;
+use openstudio_core::usecases::project::create_project;
+use openstudio_core::usecases::timeline::{ActivityKind, TimelineContext};
+
+#[test]
+fn test_star_and_follow_are_idempotent() {
+    let social = InMemorySocialRepo::new();
+    let (alice, bob, project) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+    assert!(social.star(alice, project));
+    assert!(!social.star(alice, project));
+    assert!(social.star(bob, project));
+    assert_eq!(social.star_count(project), 2);
+    assert!(social.unstar(bob, project));
+    assert!(!social.unstar(bob, project));
+    assert_eq!(social.starred_by(alice).len(), 1);
+
+    assert!(social.follow(alice, bob));
+    assert!(!social.follow(alice, bob));
+    assert_eq!(social.followers(bob).len(), 1);
+    assert_eq!(social.following(alice)[0].followee_id, bob);
+    assert!(social.unfollow(alice, bob));
+    assert!(social.followers(bob).is_empty());
+}
+
+#[test]
+fn test_timeline_shows_followed_activity_on_visible_projects() {
+    let projects = InMemoryProjectRepo::default();
+    let issues = InMemoryIssueRepo::new();
+    let comments = InMemoryCommentRepo::new();
+    let social = InMemorySocialRepo::new();
+    let access = ProjectAccess::new(Arc::new(InMemoryUserRepo::new()));
+    let (viewer, followed, stranger) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+
+    let mut public = create_project("Public", "desc");
+    public.visibility = Visibility::Public;
+    public.owner_id = Some(followed);
+    let mut private = create_project("Privé", "desc");
+    private.owner_id = Some(followed);
+    let mut unlisted = create_project("Non listé", "desc");
+    unlisted.visibility = Visibility::Unlisted;
+    unlisted.owner_id = Some(followed);
+    for author in [followed, stranger] {
+        issues.save(Issue { author_id: Some(author), ..common::issue(public.id, "Bug") }).unwrap();
+    }
+    projects.save(public.clone()).unwrap();
+    projects.save(private.clone()).unwrap();
+    projects.save(unlisted.clone()).unwrap();
+    social.star(followed, public.id);
+    social.star(followed, private.id);
+    social.star(followed, unlisted.id);
+    social.follow(viewer, followed);
+
+    let ctx = TimelineContext { projects: &projects, issues: &issues, comments: &comments, social: &social, access: &access };
+    let events = ctx.timeline(viewer, 50).unwrap();
+    println!("Fil: {:?}", events);
+    // Les projets privé et non listé, leurs étoiles et l'issue de l'inconnu n'apparaissent pas
+    assert!(events.iter().all(|e| e.actor_id == followed && e.project_id == Some(public.id)));
+    let kinds: Vec<ActivityKind> = events.iter().map(|e| e.kind).collect();
+    assert_eq!(kinds.len(), 3);
+    for kind in [ActivityKind::ProjectCreated, ActivityKind::IssueOpened, ActivityKind::ProjectStarred] {
+        assert!(kinds.contains(&kind));
+    }
+    assert!(events.windows(2).all(|w| w[0].created_at >= w[1].created_at));
+    assert!(ctx.timeline(stranger, 50).unwrap().is_empty());
+}
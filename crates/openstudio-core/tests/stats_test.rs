@@ -0,0 +1,82 @@
+mod common;
+
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+use openstudio_core::models::comment::Comment;
+use openstudio_core::models::issue::{Issue, IssueStatus};
+use openstudio_core::models::user::{ProjectMember, ProjectRole};
+use openstudio_core::repositories::in_memory_comment::InMemoryCommentRepo;
+use openstudio_core::repositories::in_memory_issue::InMemoryIssueRepo;
+use openstudio_core::repositories::in_memory_issue_history::InMemoryIssueHistoryRepo;
+use openstudio_core::repositories::in_memory_stats::InMemoryStatsCache;
+use openstudio_core::repositories::in_memory_user::InMemoryUserRepo;
+use openstudio_core::repositories::issue_repository::IssueRepository;
+use openstudio_core::usecases::issue::update_issue;
+use openstudio_core::usecases::stats::StatsContext;
+
+fn issue(project_id: Uuid, author_id: Uuid, hours_ago: i64) -> Issue {
+    let created_at = Utc::now() - Duration::hours(hours_ago);
+    Issue { author_id: Some(author_id), created_at, updated_at: created_at, ..common::issue(project_id, "Bug") }
+}
+
+#[test]
+fn test_project_stats() {
+    let issues = InMemoryIssueRepo::new();
+    let history = InMemoryIssueHistoryRepo::new();
+    let comments = InMemoryCommentRepo::new();
+    let users = InMemoryUserRepo::new();
+    let project_id = Uuid::new_v4();
+    let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+    for (user_id, role) in [(alice, ProjectRole::Owner), (bob, ProjectRole::Contributor)] {
+        users.add_member(ProjectMember { user_id, project_id, role, joined_at: Utc::now() });
+    }
+
+    // Deux issues fermées après 2h et 4h, une en cours, une ouverte
+    for (hours, status) in [(2, IssueStatus::Closed), (4, IssueStatus::Closed), (1, IssueStatus::InProgress)] {
+        let mut created = issue(project_id, alice, hours);
+        issues.save(created.clone()).unwrap();
+        created.status = status;
+        update_issue(&issues, &history, created, Some(alice)).unwrap();
+    }
+    let open = issue(project_id, bob, 0);
+    issues.save(open.clone()).unwrap();
+    comments.save(Comment { id: Uuid::new_v4(), issue_id: open.id, author_id: bob, body: "+1".to_string(), created_at: Utc::now() });
+
+    let context = StatsContext { issues: &issues, history: &history, comments: &comments, users: &users };
+    let stats = context.compute(project_id).unwrap();
+    println!("Statistiques: {:?}", stats);
+    assert_eq!((stats.issues.open, stats.issues.in_progress, stats.issues.closed), (1, 1, 2));
+    assert_eq!((stats.members.owner, stats.members.contributor), (1, 1));
+    let median = stats.median_hours_to_close.unwrap();
+    assert!((median - 3.0).abs() < 0.01);
+    assert_eq!(stats.weekly.iter().map(|w| w.closed).sum::<usize>(), 2);
+    assert_eq!(stats.top_contributors[0].user_id, alice);
+    assert_eq!(stats.top_contributors[1].comments, 1);
+}
+
+#[test]
+fn test_stats_cache_is_invalidated() {
+    let issues = InMemoryIssueRepo::new();
+    let history = InMemoryIssueHistoryRepo::new();
+    let comments = InMemoryCommentRepo::new();
+    let users = InMemoryUserRepo::new();
+    let cache = InMemoryStatsCache::new();
+    let project_id = Uuid::new_v4();
+    let context = StatsContext { issues: &issues, history: &history, comments: &comments, users: &users };
+
+    let first = context.cached(&cache, project_id).unwrap();
+    issues.save(issue(project_id, Uuid::new_v4(), 0)).unwrap();
+    // Sans invalidation, la valeur en cache est servie telle quelle
+    assert_eq!(context.cached(&cache, project_id).unwrap().generated_at, first.generated_at);
+    cache.invalidate(project_id);
+    assert_eq!(context.cached(&cache, project_id).unwrap().issues.open, 1);
+
+    // Un calcul commencé avant une invalidation n'est pas mis en cache
+    cache.invalidate(project_id);
+    let generation = cache.generation(project_id);
+    let stale = context.compute(project_id).unwrap();
+    issues.save(issue(project_id, Uuid::new_v4(), 0)).unwrap();
+    cache.invalidate(project_id);
+    assert!(!cache.put(stale, generation));
+    assert_eq!(context.cached(&cache, project_id).unwrap().issues.open, 2);
+}
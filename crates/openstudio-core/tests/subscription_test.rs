@@ -0,0 +1,55 @@
+mod common;
+
+use std::sync::Arc;
+use uuid::Uuid;
+use openstudio_core::models::notification::NotificationKind;
+use openstudio_core::models::project_status::Visibility;
+use openstudio_core::models::subscription::{SubscriptionReason, SubscriptionTarget};
+use openstudio_core::repositories::in_memory_notification::InMemoryNotificationRepo;
+use openstudio_core::repositories::in_memory_subscription::InMemorySubscriptionRepo;
+use openstudio_core::repositories::in_memory_user::InMemoryUserRepo;
+use openstudio_core::usecases::project::create_project;
+use openstudio_core::usecases::access::ProjectAccess;
+use openstudio_core::usecases::subscription::WatcherContext;
+
+#[test]
+fn test_watch_and_unwatch() {
+    let repo = InMemorySubscriptionRepo::new();
+    let user = Uuid::new_v4();
+    let target = SubscriptionTarget::Issue(Uuid::new_v4());
+    assert!(repo.subscribe(user, target, SubscriptionReason::Author));
+    // Déjà abonné : la raison d'origine est conservée
+    assert!(!repo.subscribe(user, target, SubscriptionReason::Manual));
+    assert_eq!(repo.watchers(target)[0].reason, SubscriptionReason::Author);
+    assert_eq!(repo.list_for_user(user).len(), 1);
+    assert!(repo.unsubscribe(user, target));
+    assert!(!repo.unsubscribe(user, target));
+    assert!(repo.watchers(target).is_empty());
+}
+
+#[test]
+fn test_recipients_merge_issue_and_project_watchers() {
+    let subscriptions = InMemorySubscriptionRepo::new();
+    let access = ProjectAccess::new(Arc::new(InMemoryUserRepo::new()));
+    let notifications = InMemoryNotificationRepo::new();
+    let mut project = create_project("Public", "desc");
+    project.visibility = Visibility::Public;
+    let issue = common::issue(project.id, "Fuite mémoire");
+    let (author, project_watcher, actor) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+    subscriptions.subscribe(author, SubscriptionTarget::Issue(issue.id), SubscriptionReason::Author);
+    subscriptions.subscribe(author, SubscriptionTarget::Project(project.id), SubscriptionReason::Manual);
+    subscriptions.subscribe(project_watcher, SubscriptionTarget::Project(project.id), SubscriptionReason::Manual);
+    subscriptions.subscribe(actor, SubscriptionTarget::Issue(issue.id), SubscriptionReason::Commenter);
+
+    let context = WatcherContext { subscriptions: &subscriptions, access: &access, notifications: &notifications };
+    let recipients = context.recipients(Some(&project), &issue, Some(actor));
+    assert_eq!(recipients, vec![author, project_watcher]);
+
+    context.notify(Some(&project), &issue, Some(actor), NotificationKind::IssueUpdated, "maj");
+    assert_eq!(notifications.list_for_user(author).len(), 1);
+    assert!(notifications.list_for_user(actor).is_empty());
+
+    // Un projet privé ne notifie que ses membres
+    project.visibility = Visibility::Private;
+    assert!(context.recipients(Some(&project), &issue, Some(actor)).is_empty());
+}
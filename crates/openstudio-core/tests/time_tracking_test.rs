@@ -0,0 +1,95 @@
+mod common;
+
+use chrono::{Duration, NaiveDate, Utc};
+use uuid::Uuid;
+use openstudio_core::models::issue::{Issue, IssueStatus};
+use openstudio_core::models::time_entry::{RunningTimer, TimeEntry};
+use openstudio_core::repositories::in_memory_time::InMemoryTimeRepo;
+use openstudio_core::repositories::in_memory_user::InMemoryUserRepo;
+use openstudio_core::usecases::time_tracking::{entry_from_timer, iso_week, report_csv, summarize_issue, time_report, MAX_MINUTES_PER_ENTRY};
+
+fn entry(project_id: Uuid, issue_id: Uuid, user_id: Uuid, minutes: u32, date: NaiveDate) -> TimeEntry {
+    TimeEntry {
+        id: Uuid::new_v4(),
+        issue_id,
+        project_id,
+        user_id,
+        minutes,
+        date,
+        note: String::new(),
+        created_at: Utc::now(),
+    }
+}
+
+fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+#[test]
+fn test_issue_summary_against_estimate() {
+    let issue = Issue {
+        status: IssueStatus::InProgress,
+        estimate_minutes: Some(120),
+        ..common::issue(Uuid::new_v4(), "Refonte du menu")
+    };
+    let user = Uuid::new_v4();
+    let entries = vec![
+        entry(issue.project_id, issue.id, user, 90, date(2026, 10, 12)),
+        entry(issue.project_id, issue.id, user, 45, date(2026, 10, 13)),
+    ];
+    let summary = summarize_issue(&issue, &entries);
+    assert_eq!(summary.logged_minutes, 135);
+    assert_eq!(summary.remaining_minutes, Some(-15));
+    assert!(summary.over_estimate);
+}
+
+#[test]
+fn test_timer_is_exclusive_per_user() {
+    let repo = InMemoryTimeRepo::new();
+    let user = Uuid::new_v4();
+    let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+    assert!(repo.start_timer(user, first).is_ok());
+    let running = repo.start_timer(user, second).unwrap_err();
+    assert_eq!(running.issue_id, first);
+    // Le chronomètre d'une autre issue reste en place
+    assert!(repo.stop_timer(user, second).is_none());
+    assert!(repo.stop_timer(user, first).is_some());
+    assert!(repo.stop_timer(user, first).is_none());
+    assert!(repo.timer_for(user).is_none());
+
+    let timer = RunningTimer { user_id: user, issue_id: first, started_at: Utc::now() - Duration::seconds(61) };
+    let entry = entry_from_timer(&timer, Uuid::new_v4(), "pair programming".to_string());
+    assert_eq!(entry.minutes, 2);
+
+    // Un chronomètre oublié ne produit pas plus d'une journée
+    let forgotten = RunningTimer { user_id: user, issue_id: first, started_at: Utc::now() - Duration::days(3) };
+    assert_eq!(entry_from_timer(&forgotten, Uuid::new_v4(), String::new()).minutes, MAX_MINUTES_PER_ENTRY);
+}
+
+#[test]
+fn test_time_report_by_user_and_week() {
+    let users = InMemoryUserRepo::new();
+    let project = Uuid::new_v4();
+    let issue = Uuid::new_v4();
+    let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+    let entries = vec![
+        entry(project, issue, alice, 60, date(2026, 10, 12)),
+        entry(project, issue, alice, 30, date(2026, 10, 18)),
+        entry(project, issue, bob, 20, date(2026, 10, 19)),
+        entry(project, issue, bob, 500, date(2026, 9, 1)),
+        entry(Uuid::new_v4(), issue, bob, 999, date(2026, 10, 12)),
+    ];
+    assert_eq!(iso_week(date(2026, 10, 18)), "2026-W42");
+
+    let report = time_report(project, &entries, &users, Some(date(2026, 10, 1)), None);
+    assert_eq!(report.total_minutes, 110);
+    assert_eq!(report.by_user[0].user_id, alice);
+    assert_eq!(report.by_user[0].minutes, 90);
+    let weeks: Vec<(&str, u64)> = report.by_week.iter().map(|w| (w.week.as_str(), w.minutes)).collect();
+    assert_eq!(weeks, vec![("2026-W42", 90), ("2026-W43", 20)]);
+
+    let csv = report_csv(&report).unwrap();
+    println!("{}", csv);
+    assert!(csv.starts_with("user_id,username,week,minutes\n"));
+    assert_eq!(csv.lines().count(), 3);
+}
@@ -0,0 +1,69 @@
+use uuid::Uuid;
+use openstudio_core::repositories::in_memory_search::{InMemorySearchIndex, SearchKind};
+use openstudio_core::repositories::in_memory_wiki::InMemoryWikiRepo;
+use openstudio_core::usecases::wiki::{line_diff, DiffOp, PageEdit, WikiContext, WikiError, MAX_PAGE_LINES};
+
+#[test]
+fn test_line_diff() {
+    let diff = line_diff("a\nb\nc", "a\nc\nd");
+    let ops: Vec<(DiffOp, &str)> = diff.iter().map(|l| (l.op.clone(), l.text.as_str())).collect();
+    assert_eq!(
+        ops,
+        vec![(DiffOp::Equal, "a"), (DiffOp::Delete, "b"), (DiffOp::Equal, "c"), (DiffOp::Insert, "d")]
+    );
+    let diff = line_diff("a\nb\nc\nd", "a\nx\nc\nd");
+    let ops: Vec<(DiffOp, &str)> = diff.iter().map(|l| (l.op.clone(), l.text.as_str())).collect();
+    assert_eq!(
+        ops,
+        vec![(DiffOp::Equal, "a"), (DiffOp::Delete, "b"), (DiffOp::Insert, "x"), (DiffOp::Equal, "c"), (DiffOp::Equal, "d")]
+    );
+}
+
+#[test]
+fn test_page_size_is_capped_and_project_pages_are_purged() {
+    let wiki = InMemoryWikiRepo::new();
+    let index = InMemorySearchIndex::new();
+    let context = WikiContext { wiki: &wiki, index: &index };
+    let (project_id, other_id) = (Uuid::new_v4(), Uuid::new_v4());
+
+    let too_long = "line\n".repeat(MAX_PAGE_LINES + 1);
+    assert_eq!(context.create_page(project_id, None, "Big", &too_long, None).unwrap_err(), WikiError::PageTooLarge);
+    let page = context.create_page(project_id, None, "Guide", "hello", None).unwrap();
+    let edit = PageEdit { content: Some(too_long), ..PageEdit::default() };
+    assert_eq!(context.update_page(page.clone(), edit, None).unwrap_err(), WikiError::PageTooLarge);
+
+    let kept = context.create_page(other_id, None, "Guide", "hello", None).unwrap();
+    assert_eq!(wiki.delete_for_project(project_id), vec![page.id]);
+    index.remove_project(project_id);
+    assert!(wiki.get_page(page.id).is_none() && wiki.list_revisions(page.id).is_empty());
+    let hits = index.search("hello", None);
+    assert_eq!((hits.len(), hits[0].id), (1, kept.id));
+}
+
+#[test]
+fn test_wiki_revisions_and_restore() {
+    let wiki = InMemoryWikiRepo::new();
+    let index = InMemorySearchIndex::new();
+    let context = WikiContext { wiki: &wiki, index: &index };
+    let project_id = Uuid::new_v4();
+    let author = Some(Uuid::new_v4());
+
+    let guide = context.create_page(project_id, None, "Guide", "# Guide", author).unwrap();
+    let install = context.create_page(project_id, Some(guide.id), "Installation", "cargo build", author).unwrap();
+    assert_eq!(context.path(&install), "guide/installation");
+    // Une page ne peut pas devenir l'enfant de sa propre sous-page
+    let cycle = PageEdit { parent_id: Some(Some(install.id)), ..PageEdit::default() };
+    assert_eq!(context.update_page(guide.clone(), cycle, author).unwrap_err(), WikiError::InvalidParent);
+    assert_eq!(context.delete_page(&guide).unwrap_err(), WikiError::HasChildren);
+
+    let edit = PageEdit { content: Some("cargo build --release".to_string()), ..PageEdit::default() };
+    let install = context.update_page(install, edit, author).unwrap();
+    assert_eq!(install.revision, 2);
+    assert_eq!(index.search("release", Some(project_id))[0].kind, SearchKind::WikiPage);
+
+    let restored = context.restore(install, 1, author).unwrap();
+    println!("Révisions: {:?}", wiki.list_revisions(restored.id));
+    assert_eq!((restored.revision, restored.content.as_str()), (3, "cargo build"));
+    assert!(index.search("release", None).is_empty());
+    assert_eq!(context.restore(restored, 9, author).unwrap_err(), WikiError::RevisionNotFound);
+}
@@ -1,29 +1,183 @@
+use crate::routes::access_token::{AccessTokenState, access_token_routes};
 use crate::routes::auth::{AuthState, auth_routes};
+use crate::routes::explore::{ExploreState, explore_routes};
+use crate::routes::invitation::{InvitationState, invitation_routes};
+use crate::routes::join_request::{JoinRequestState, join_request_routes};
 use crate::routes::member::{MemberState, member_routes};
-use axum::{Router, serve};
+use crate::routes::notification::{NotificationState, notification_routes};
+use crate::routes::oauth::{OAuthState, oauth_routes};
+use crate::routes::organization::{OrgState, organization_routes};
+use crate::routes::search::{SearchState, search_routes};
+use crate::routes::social::{SocialState, social_routes};
+use crate::routes::stats::{StatsState, stats_routes};
+use crate::routes::subscription::{SubscriptionState, subscription_routes};
+use crate::routes::time::{TimeState, time_routes};
+use axum::{serve, Extension};
 use std::sync::Arc;
 use tokio::net::TcpListener;
 use tower_http::cors::{CorsLayer, Any};
-use tower::ServiceBuilder;
 use openstudio_core::repositories::in_memory::InMemoryProjectRepo;
+use openstudio_core::repositories::in_memory_redirect::InMemoryProjectRedirectRepo;
+use openstudio_core::repositories::in_memory_refresh_token::InMemoryRefreshTokenRepo;
 use openstudio_core::repositories::in_memory_issue::InMemoryIssueRepo;
+use openstudio_core::repositories::in_memory_issue_history::InMemoryIssueHistoryRepo;
+use openstudio_core::repositories::in_memory_comment::InMemoryCommentRepo;
+use openstudio_core::repositories::in_memory_access_token::InMemoryAccessTokenRepo;
+use openstudio_core::repositories::in_memory_identity::InMemoryIdentityRepo;
+use openstudio_core::repositories::in_memory_invitation::InMemoryInvitationRepo;
+use openstudio_core::repositories::in_memory_join_request::InMemoryJoinRequestRepo;
+use openstudio_core::repositories::in_memory_org::InMemoryOrgRepo;
+use openstudio_core::repositories::in_memory_password_reset::InMemoryPasswordResetRepo;
+use openstudio_core::repositories::in_memory_email_verification::InMemoryEmailVerificationRepo;
+use openstudio_core::repositories::in_memory_notification::{InMemoryMentionRepo, InMemoryNotificationRepo};
+use openstudio_core::repositories::in_memory_subscription::InMemorySubscriptionRepo;
+use openstudio_core::repositories::in_memory_search::InMemorySearchIndex;
+use openstudio_core::repositories::in_memory_social::InMemorySocialRepo;
+use openstudio_core::repositories::in_memory_stats::InMemoryStatsCache;
+use openstudio_core::repositories::in_memory_wiki::InMemoryWikiRepo;
+use openstudio_core::repositories::in_memory_time::InMemoryTimeRepo;
+use openstudio_core::usecases::access::ProjectAccess;
+mod mail;
+mod oauth;
 mod routes;
+mod token;
 
 use crate::routes::project::AppState;
 use crate::routes::issue::{IssueState, issue_routes};
 use crate::routes::user::{UserState, user_routes};
+use crate::routes::wiki::{WikiState, wiki_routes};
+use crate::mail::mailer_from_env;
+use crate::oauth::OAuthProviders;
+use crate::token::TokenService;
 use openstudio_core::repositories::in_memory_user::InMemoryUserRepo;
 
 #[tokio::main]
 async fn main() {
+    let tokens = match TokenService::from_env() {
+        Ok(tokens) => Arc::new(tokens),
+        Err(e) => {
+            eprintln!("Invalid JWT configuration: {}", e);
+            std::process::exit(1);
+        },
+    };
+    let addr = std::env::var("OPENSTUDIO_ADDR").unwrap_or_else(|_| "127.0.0.1:3001".to_string());
+    let public_url = std::env::var("OPENSTUDIO_PUBLIC_URL").unwrap_or_else(|_| format!("http://{}", addr));
+    let public_url = public_url.trim_end_matches('/').to_string();
+    let providers = match OAuthProviders::from_env(&public_url).await {
+        Ok(providers) => Arc::new(providers),
+        Err(e) => {
+            eprintln!("Invalid OAuth configuration: {}", e);
+            std::process::exit(1);
+        },
+    };
+    let mailer = match mailer_from_env() {
+        Ok(mailer) => mailer,
+        Err(e) => {
+            eprintln!("Invalid mail configuration: {}", e);
+            std::process::exit(1);
+        },
+    };
+    let orgs = Arc::new(InMemoryOrgRepo::new());
+    let users = Arc::new(InMemoryUserRepo::new());
+    let access = ProjectAccess::with_orgs(users.clone(), orgs.clone());
+    let projects = Arc::new(InMemoryProjectRepo::default());
+    let user_state = UserState {
+        repo: users,
+        social: Arc::new(InMemorySocialRepo::new()),
+        projects: projects.clone(),
+        access: access.clone(),
+    };
+    let issues = Arc::new(InMemoryIssueRepo::new());
+    let stats = Arc::new(InMemoryStatsCache::new());
+    let index = Arc::new(InMemorySearchIndex::new());
+    let subscriptions = Arc::new(InMemorySubscriptionRepo::new());
+    let wiki = Arc::new(InMemoryWikiRepo::new());
     let state = AppState {
-        repo: Arc::new(InMemoryProjectRepo::default()),
+        repo: projects,
+        users: user_state.repo.clone(),
+        redirects: Arc::new(InMemoryProjectRedirectRepo::new()),
+        orgs: orgs.clone(),
+        invitations: Arc::new(InMemoryInvitationRepo::new()),
+        join_requests: Arc::new(InMemoryJoinRequestRepo::new()),
+        issues: issues.clone(),
+        stats: stats.clone(),
+        index: index.clone(),
+        wiki: wiki.clone(),
+        social: user_state.social.clone(),
+        subscriptions: subscriptions.clone(),
+        access: access.clone(),
+    };
+    let notification_state = NotificationState {
+        repo: Arc::new(InMemoryNotificationRepo::new()),
     };
     let issue_state = IssueState {
-        repo: Arc::new(InMemoryIssueRepo::new()),
+        repo: issues.clone(),
+        projects: state.repo.clone(),
+        history: Arc::new(InMemoryIssueHistoryRepo::new()),
+        users: user_state.repo.clone(),
+        comments: Arc::new(InMemoryCommentRepo::new()),
+        mentions: Arc::new(InMemoryMentionRepo::new()),
+        notifications: notification_state.repo.clone(),
+        subscriptions: subscriptions.clone(),
+        stats: stats.clone(),
+        index: index.clone(),
+        access: access.clone(),
     };
-    let user_state = UserState {
-        repo: Arc::new(InMemoryUserRepo::new()),
+    let subscription_state = SubscriptionState {
+        repo: issue_state.subscriptions.clone(),
+        issues: issue_state.repo.clone(),
+        projects: state.repo.clone(),
+        access: access.clone(),
+    };
+    let explore_state = ExploreState {
+        projects: state.repo.clone(),
+        issues: issue_state.repo.clone(),
+        history: issue_state.history.clone(),
+        comments: issue_state.comments.clone(),
+        users: user_state.repo.clone(),
+        social: user_state.social.clone(),
+    };
+    let stats_state = StatsState {
+        projects: state.repo.clone(),
+        issues: issues.clone(),
+        history: issue_state.history.clone(),
+        comments: issue_state.comments.clone(),
+        users: user_state.repo.clone(),
+        cache: stats.clone(),
+        access: access.clone(),
+    };
+    let wiki_state = WikiState {
+        repo: wiki.clone(),
+        index: index.clone(),
+        projects: state.repo.clone(),
+        access: access.clone(),
+    };
+    let search_state = SearchState {
+        index: index.clone(),
+        projects: state.repo.clone(),
+        access: access.clone(),
+    };
+    let social_state = SocialState {
+        repo: user_state.social.clone(),
+        users: user_state.repo.clone(),
+        projects: state.repo.clone(),
+        issues: issues.clone(),
+        comments: issue_state.comments.clone(),
+        access: access.clone(),
+    };
+    let org_state = OrgState {
+        orgs: orgs.clone(),
+        users: user_state.repo.clone(),
+        projects: state.repo.clone(),
+        redirects: state.redirects.clone(),
+        access: access.clone(),
+    };
+    let time_state = TimeState {
+        repo: Arc::new(InMemoryTimeRepo::new()),
+        issues: issue_state.repo.clone(),
+        projects: state.repo.clone(),
+        users: user_state.repo.clone(),
+        access: access.clone(),
     };
 
     let api_routes = routes::project::project_routes().with_state(state.clone());
@@ -31,13 +185,59 @@ async fn main() {
     let user_api_routes = user_routes().with_state(user_state.clone());
     let member_state = MemberState {
         repo: user_state.repo.clone(),
+        projects: state.repo.clone(),
+        stats: stats.clone(),
+        subscriptions: subscriptions.clone(),
+        access: access.clone(),
     };
     let member_api_routes = member_routes().with_state(member_state.clone());
+    let invitation_state = InvitationState {
+        repo: state.invitations.clone(),
+        users: user_state.repo.clone(),
+        projects: state.repo.clone(),
+        subscriptions: subscriptions.clone(),
+        stats: stats.clone(),
+        access: access.clone(),
+    };
+    let invitation_api_routes = invitation_routes().with_state(invitation_state.clone());
+    let join_request_state = JoinRequestState {
+        repo: state.join_requests.clone(),
+        users: user_state.repo.clone(),
+        projects: state.repo.clone(),
+        notifications: notification_state.repo.clone(),
+        subscriptions: subscriptions.clone(),
+        stats: stats.clone(),
+        access: access.clone(),
+    };
+    let join_request_api_routes = join_request_routes().with_state(join_request_state.clone());
     let auth_state = AuthState {
         repo: user_state.repo.clone(),
-        jwt_secret: "supersecretkey".to_string(),
+        sessions: Arc::new(InMemoryRefreshTokenRepo::new()),
+        tokens: tokens.clone(),
+        resets: Arc::new(InMemoryPasswordResetRepo::new()),
+        verifications: Arc::new(InMemoryEmailVerificationRepo::new()),
+        orgs: orgs.clone(),
+        mailer,
+        public_url,
     };
     let auth_api_routes = auth_routes().with_state(auth_state.clone());
+    let oauth_state = OAuthState::new(providers, Arc::new(InMemoryIdentityRepo::new()), auth_state.clone());
+    let oauth_api_routes = oauth_routes().with_state(oauth_state.clone());
+    let access_token_state = AccessTokenState {
+        repo: Arc::new(InMemoryAccessTokenRepo::new()),
+        projects: state.repo.clone(),
+        access: access.clone(),
+    };
+    let access_token_api_routes = access_token_routes().with_state(access_token_state.clone());
+    let notification_api_routes = notification_routes().with_state(notification_state.clone());
+    let subscription_api_routes = subscription_routes().with_state(subscription_state.clone());
+    let time_api_routes = time_routes().with_state(time_state.clone());
+    let explore_api_routes = explore_routes().with_state(explore_state.clone());
+    let stats_api_routes = stats_routes().with_state(stats_state.clone());
+    let wiki_api_routes = wiki_routes().with_state(wiki_state.clone());
+    let search_api_routes = search_routes().with_state(search_state.clone());
+    let social_api_routes = social_routes().with_state(social_state.clone());
+    let org_api_routes = organization_routes().with_state(org_state.clone());
 
     // Configure CORS
     let cors = CorsLayer::new()
@@ -49,11 +249,28 @@ async fn main() {
         .merge(issue_api_routes)
         .merge(user_api_routes)
         .merge(member_api_routes)
+        .merge(invitation_api_routes)
+        .merge(join_request_api_routes)
         .merge(auth_api_routes)
+        .merge(oauth_api_routes)
+        .merge(access_token_api_routes)
+        .merge(notification_api_routes)
+        .merge(subscription_api_routes)
+        .merge(time_api_routes)
+        .merge(explore_api_routes)
+        .merge(stats_api_routes)
+        .merge(wiki_api_routes)
+        .merge(search_api_routes)
+        .merge(social_api_routes)
+        .merge(org_api_routes)
+        .layer(Extension(tokens))
+        .layer(Extension(auth_state.sessions.clone()))
+        .layer(Extension(access_token_state.repo.clone()))
+        .layer(Extension(issues.clone()))
         .layer(cors);
 
-    let listener = TcpListener::bind("127.0.0.1:3001").await.unwrap();
-    println!("🚀 API running at http://127.0.0.1:3001");
+    let listener = TcpListener::bind(&addr).await.unwrap();
+    println!("🚀 API running at http://{}", addr);
 
     serve(listener, app.into_make_service_with_connect_info::<std::net::SocketAddr>())
         .await
@@ -0,0 +1,289 @@
+use std::collections::HashMap;
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use reqwest::Url;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use openstudio_core::usecases::identity::ExternalProfile;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProviderKind {
+    Github,
+    Oidc,
+}
+
+/// Fournisseur décrit dans la configuration. Pour GitHub les URLs ont des valeurs par
+/// défaut ; pour un fournisseur OIDC, celles qui manquent sont lues depuis `issuer`
+/// (`/.well-known/openid-configuration`).
+#[derive(Debug, Deserialize)]
+pub struct ProviderConfig {
+    pub name: String,
+    pub kind: ProviderKind,
+    pub client_id: String,
+    pub client_secret: String,
+    pub issuer: Option<String>,
+    pub authorize_url: Option<String>,
+    pub token_url: Option<String>,
+    pub userinfo_url: Option<String>,
+    pub scopes: Option<Vec<String>>,
+}
+
+/// Fichier pointé par `OPENSTUDIO_OAUTH_CONFIG`.
+#[derive(Debug, Deserialize)]
+pub struct OAuthConfig {
+    pub providers: Vec<ProviderConfig>,
+}
+
+pub struct Provider {
+    pub name: String,
+    pub kind: ProviderKind,
+    client_id: String,
+    client_secret: String,
+    authorize_url: String,
+    token_url: String,
+    userinfo_url: String,
+    scopes: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct Discovery {
+    authorization_endpoint: String,
+    token_endpoint: String,
+    userinfo_endpoint: String,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+}
+
+#[derive(Deserialize)]
+struct OidcUserInfo {
+    sub: String,
+    email: Option<String>,
+    #[serde(default)]
+    email_verified: bool,
+    preferred_username: Option<String>,
+    given_name: Option<String>,
+    family_name: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GithubUser {
+    id: u64,
+    login: String,
+    name: Option<String>,
+    email: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GithubEmail {
+    email: String,
+    primary: bool,
+    verified: bool,
+}
+
+/// Fournisseurs d'identité configurés, et client HTTP pour les appels serveur à serveur
+/// (échange du code, profil).
+pub struct OAuthProviders {
+    providers: HashMap<String, Provider>,
+    public_url: String,
+    http: reqwest::Client,
+}
+
+impl OAuthProviders {
+    /// Sans `OPENSTUDIO_OAUTH_CONFIG`, aucun fournisseur n'est proposé. Les URLs de retour
+    /// sont construites sur `public_url`.
+    pub async fn from_env(public_url: &str) -> Result<Self, String> {
+        let config = match std::env::var("OPENSTUDIO_OAUTH_CONFIG") {
+            Ok(path) => {
+                let raw = std::fs::read_to_string(&path).map_err(|e| format!("{}: {}", path, e))?;
+                serde_json::from_str(&raw).map_err(|e| format!("{}: {}", path, e))?
+            },
+            Err(_) => OAuthConfig { providers: Vec::new() },
+        };
+        Self::from_config(config, public_url).await
+    }
+
+    pub async fn from_config(config: OAuthConfig, public_url: &str) -> Result<Self, String> {
+        let http = reqwest::Client::builder()
+            .user_agent("openstudio")
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .map_err(|e| e.to_string())?;
+        let mut providers = HashMap::new();
+        for config in config.providers {
+            let name = config.name.clone();
+            let provider = Self::resolve(&http, config).await.map_err(|e| format!("provider {}: {}", name, e))?;
+            if providers.insert(name.clone(), provider).is_some() {
+                return Err(format!("provider {}: duplicate name", name));
+            }
+        }
+        Ok(OAuthProviders { providers, public_url: public_url.to_string(), http })
+    }
+
+    async fn resolve(http: &reqwest::Client, config: ProviderConfig) -> Result<Provider, String> {
+        let (authorize_url, token_url, userinfo_url) = match (config.kind, config.authorize_url, config.token_url, config.userinfo_url) {
+            (_, Some(authorize), Some(token), Some(userinfo)) => (authorize, token, userinfo),
+            (ProviderKind::Github, authorize, token, userinfo) => (
+                authorize.unwrap_or_else(|| "https://github.com/login/oauth/authorize".to_string()),
+                token.unwrap_or_else(|| "https://github.com/login/oauth/access_token".to_string()),
+                userinfo.unwrap_or_else(|| "https://api.github.com/user".to_string()),
+            ),
+            (ProviderKind::Oidc, authorize, token, userinfo) => {
+                let issuer = config.issuer.as_deref().ok_or("OIDC providers need an issuer or explicit URLs")?;
+                let url = format!("{}/.well-known/openid-configuration", issuer.trim_end_matches('/'));
+                let discovery: Discovery = http
+                    .get(&url)
+                    .send()
+                    .await
+                    .and_then(|res| res.error_for_status())
+                    .map_err(|e| format!("discovery failed: {}", e))?
+                    .json()
+                    .await
+                    .map_err(|e| format!("invalid discovery document: {}", e))?;
+                (
+                    authorize.unwrap_or(discovery.authorization_endpoint),
+                    token.unwrap_or(discovery.token_endpoint),
+                    userinfo.unwrap_or(discovery.userinfo_endpoint),
+                )
+            },
+        };
+        let scopes = config.scopes.unwrap_or_else(|| match config.kind {
+            ProviderKind::Github => vec!["read:user".to_string(), "user:email".to_string()],
+            ProviderKind::Oidc => vec!["openid".to_string(), "email".to_string(), "profile".to_string()],
+        });
+        Ok(Provider {
+            name: config.name,
+            kind: config.kind,
+            client_id: config.client_id,
+            client_secret: config.client_secret,
+            authorize_url,
+            token_url,
+            userinfo_url,
+            scopes,
+        })
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Provider> {
+        self.providers.get(name)
+    }
+
+    pub fn list(&self) -> Vec<&Provider> {
+        let mut providers: Vec<&Provider> = self.providers.values().collect();
+        providers.sort_by(|a, b| a.name.cmp(&b.name));
+        providers
+    }
+
+    fn redirect_uri(&self, provider: &Provider) -> String {
+        format!("{}/auth/{}/callback", self.public_url, provider.name)
+    }
+
+    /// URL de la page de connexion du fournisseur (code d'autorisation + PKCE S256).
+    pub fn authorize_url(&self, provider: &Provider, state: &str, code_challenge: &str) -> Result<String, String> {
+        let scope = provider.scopes.join(" ");
+        let redirect_uri = self.redirect_uri(provider);
+        let params = [
+            ("response_type", "code"),
+            ("client_id", provider.client_id.as_str()),
+            ("redirect_uri", redirect_uri.as_str()),
+            ("scope", scope.as_str()),
+            ("state", state),
+            ("code_challenge", code_challenge),
+            ("code_challenge_method", "S256"),
+        ];
+        Url::parse_with_params(&provider.authorize_url, &params).map(String::from).map_err(|e| e.to_string())
+    }
+
+    /// Échange le code contre un jeton d'accès du fournisseur.
+    pub async fn exchange_code(&self, provider: &Provider, code: &str, code_verifier: &str) -> Result<String, String> {
+        let redirect_uri = self.redirect_uri(provider);
+        let form = [
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", redirect_uri.as_str()),
+            ("client_id", provider.client_id.as_str()),
+            ("client_secret", provider.client_secret.as_str()),
+            ("code_verifier", code_verifier),
+        ];
+        let response: TokenResponse = self
+            .http
+            .post(&provider.token_url)
+            .header("accept", "application/json")
+            .form(&form)
+            .send()
+            .await
+            .and_then(|res| res.error_for_status())
+            .map_err(|e| e.to_string())?
+            .json()
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(response.access_token)
+    }
+
+    /// Profil de l'utilisateur connecté. Il est lu directement auprès du fournisseur, avec
+    /// son jeton d'accès : inutile de vérifier la signature d'un id_token.
+    pub async fn profile(&self, provider: &Provider, access_token: &str) -> Result<ExternalProfile, String> {
+        match provider.kind {
+            ProviderKind::Oidc => {
+                let info: OidcUserInfo = self.get_json(&provider.userinfo_url, access_token).await?;
+                Ok(ExternalProfile {
+                    subject: info.sub,
+                    email: info.email,
+                    email_verified: info.email_verified,
+                    username: info.preferred_username,
+                    first_name: info.given_name,
+                    last_name: info.family_name,
+                })
+            },
+            ProviderKind::Github => {
+                let user: GithubUser = self.get_json(&provider.userinfo_url, access_token).await?;
+                // L'adresse publique du profil n'est pas forcément vérifiée : on préfère
+                // l'adresse principale vérifiée
+                let emails_url = format!("{}/emails", provider.userinfo_url.trim_end_matches('/'));
+                let emails: Vec<GithubEmail> = self.get_json(&emails_url, access_token).await.unwrap_or_default();
+                let verified = emails.into_iter().find(|e| e.primary && e.verified);
+                let (first_name, last_name) = match user.name.as_deref().map(str::trim) {
+                    Some(name) if !name.is_empty() => match name.split_once(' ') {
+                        Some((first, last)) => (Some(first.to_string()), Some(last.trim().to_string())),
+                        None => (Some(name.to_string()), None),
+                    },
+                    _ => (None, None),
+                };
+                Ok(ExternalProfile {
+                    subject: user.id.to_string(),
+                    email_verified: verified.is_some(),
+                    email: verified.map(|e| e.email).or(user.email),
+                    username: Some(user.login),
+                    first_name,
+                    last_name,
+                })
+            },
+        }
+    }
+
+    async fn get_json<T: serde::de::DeserializeOwned>(&self, url: &str, access_token: &str) -> Result<T, String> {
+        self.http
+            .get(url)
+            .bearer_auth(access_token)
+            .header("accept", "application/json")
+            .send()
+            .await
+            .and_then(|res| res.error_for_status())
+            .map_err(|e| e.to_string())?
+            .json()
+            .await
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// Paire PKCE : `code_verifier` aléatoire et son `code_challenge` S256.
+pub fn pkce_pair() -> (String, String) {
+    let bytes: [u8; 32] = rand::random();
+    let verifier = URL_SAFE_NO_PAD.encode(bytes);
+    let challenge = URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()));
+    (verifier, challenge)
+}
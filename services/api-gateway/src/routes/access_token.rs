@@ -0,0 +1,143 @@
+use axum::response::IntoResponse;
+use axum::{extract::State, http::{request::Parts, Method, StatusCode}, routing::{delete, post}, Json, Router};
+use serde::Deserialize;
+use std::sync::Arc;
+use openstudio_core::models::access_token::TokenScope;
+use openstudio_core::repositories::in_memory_access_token::InMemoryAccessTokenRepo;
+use openstudio_core::repositories::in_memory_issue::InMemoryIssueRepo;
+use openstudio_core::repositories::issue_repository::IssueRepository;
+use openstudio_core::repositories::project_repository::ProjectRepository;
+use openstudio_core::usecases::access::ProjectAccess;
+use openstudio_core::usecases::access_token::{AccessTokenContext, AccessTokenError};
+use uuid;
+use crate::routes::project::AuthBearer;
+use crate::routes::common::json_response;
+
+#[derive(Clone)]
+pub struct AccessTokenState {
+    pub repo: Arc<InMemoryAccessTokenRepo>,
+    pub projects: Arc<dyn ProjectRepository + Send + Sync + 'static>,
+    pub access: ProjectAccess,
+}
+
+#[derive(Deserialize)]
+pub struct CreateAccessTokenInput {
+    pub name: String,
+    pub scopes: Vec<TokenScope>,
+    pub expires_in_days: Option<i64>,
+    pub project_id: Option<uuid::Uuid>,
+}
+
+pub fn access_token_routes() -> Router<AccessTokenState> {
+    Router::new()
+        .route("/tokens", post(create_access_token).get(list_access_tokens))
+        .route("/tokens/{id}", delete(revoke_access_token))
+}
+
+fn access_token_error(error: AccessTokenError) -> axum::response::Response {
+    let (status, message) = match error {
+        AccessTokenError::NotFound => (StatusCode::NOT_FOUND, "Token not found"),
+        AccessTokenError::InvalidName => (StatusCode::BAD_REQUEST, "The token name must contain 1 to 100 characters"),
+        AccessTokenError::NoScopes => (StatusCode::BAD_REQUEST, "At least one scope is required"),
+        AccessTokenError::InvalidExpiry => (StatusCode::BAD_REQUEST, "The expiry must be between 1 and 365 days"),
+        AccessTokenError::DuplicateName => (StatusCode::CONFLICT, "A token with this name already exists"),
+    };
+    (status, message).into_response()
+}
+
+/// Portée qu'un jeton personnel doit porter pour cette requête. Les routes hors projets et
+/// tickets (compte, jetons, sessions…) lui restent fermées : `None`.
+fn required_scope(method: &Method, segments: &[&str]) -> Option<TokenScope> {
+    let read = method == Method::GET || method == Method::HEAD;
+    let issues = match segments {
+        ["issues", ..] => true,
+        ["projects", _, "issues" | "time-report", ..] => true,
+        ["projects", ..] => false,
+        _ => return None,
+    };
+    Some(match (issues, read) {
+        (true, true) => TokenScope::IssuesRead,
+        (true, false) => TokenScope::IssuesWrite,
+        (false, true) => TokenScope::ProjectsRead,
+        (false, false) => TokenScope::ProjectsWrite,
+    })
+}
+
+/// Projet visé par la requête, quand le chemin ou la query en désigne un.
+fn target_project(parts: &Parts, segments: &[&str]) -> Option<uuid::Uuid> {
+    match segments {
+        ["projects", id, ..] => id.parse().ok(),
+        ["issues"] => parts.uri.query()?.split('&').find_map(|pair| pair.strip_prefix("project_id="))?.parse().ok(),
+        ["issues", id, ..] => {
+            let issues = parts.extensions.get::<Arc<InMemoryIssueRepo>>()?;
+            issues.get_by_id(id.parse().ok()?).ok().flatten().map(|issue| issue.project_id)
+        },
+        _ => None,
+    }
+}
+
+/// Authentifie un jeton personnel (`osp_…`) et vérifie qu'il couvre la requête : 401 si le
+/// jeton est inconnu, expiré ou révoqué, 403 si sa portée ou son projet ne conviennent pas.
+pub fn authenticate(parts: &Parts, raw: &str) -> Result<AuthBearer, (StatusCode, &'static str)> {
+    let unauthorized = || (StatusCode::UNAUTHORIZED, "Unauthorized");
+    let repo = parts.extensions.get::<Arc<InMemoryAccessTokenRepo>>().ok_or_else(unauthorized)?;
+    let token = AccessTokenContext { tokens: repo }.authenticate(raw).ok_or_else(unauthorized)?;
+    let segments: Vec<&str> = parts.uri.path().split('/').filter(|s| !s.is_empty()).collect();
+    let Some(scope) = required_scope(&parts.method, &segments) else {
+        return Err((StatusCode::FORBIDDEN, "Personal access tokens cannot be used on this route"));
+    };
+    if !token.grants(scope) {
+        return Err((StatusCode::FORBIDDEN, "The token does not grant the required scope"));
+    }
+    // Le projet d'une création d'issue est dans le corps : `CurrentUser::authorize` le vérifie
+    let deferred = parts.method == Method::POST && segments == ["issues"];
+    if let Some(project_id) = token.project_id
+        && !deferred
+        && target_project(parts, &segments) != Some(project_id)
+    {
+        return Err((StatusCode::FORBIDDEN, "The token is restricted to another project"));
+    }
+    Ok(AuthBearer { user_id: token.user_id, session_id: None, restricted_to: token.project_id })
+}
+
+async fn create_access_token(
+    auth: AuthBearer,
+    State(state): State<AccessTokenState>,
+    Json(input): Json<CreateAccessTokenInput>,
+) -> axum::response::Response {
+    if let Some(project_id) = input.project_id {
+        match state.projects.get_by_id(project_id) {
+            Ok(Some(project)) if state.access.can_view(&project, Some(auth.user_id)) => {},
+            Ok(_) => return (StatusCode::NOT_FOUND, "Project not found").into_response(),
+            Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error").into_response(),
+        }
+    }
+    let ctx = AccessTokenContext { tokens: &state.repo };
+    match ctx.create(auth.user_id, &input.name, &input.scopes, input.expires_in_days, input.project_id) {
+        Ok((token, raw)) => {
+            // Le jeton n'est renvoyé qu'ici : seule son empreinte est conservée
+            let mut body = serde_json::to_value(&token).unwrap();
+            body["token"] = raw.into();
+            json_response(StatusCode::CREATED, &body)
+        },
+        Err(e) => access_token_error(e),
+    }
+}
+
+async fn list_access_tokens(
+    auth: AuthBearer,
+    State(state): State<AccessTokenState>,
+) -> axum::response::Response {
+    json_response(StatusCode::OK, &AccessTokenContext { tokens: &state.repo }.list(auth.user_id))
+}
+
+async fn revoke_access_token(
+    auth: AuthBearer,
+    State(state): State<AccessTokenState>,
+    axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
+) -> axum::response::Response {
+    match (AccessTokenContext { tokens: &state.repo }).revoke(auth.user_id, id) {
+        Ok(token) => json_response(StatusCode::OK, &token),
+        Err(e) => access_token_error(e),
+    }
+}
@@ -1,12 +1,23 @@
 use argon2::password_hash::{PasswordHash, PasswordVerifier};
 use argon2::Argon2;
-use axum::{extract::State, http::StatusCode, routing::post, Json, Router};
+use axum::{extract::State, http::StatusCode, routing::{get, post}, Json, Router};
 use serde::Deserialize;
 use std::sync::Arc;
 use openstudio_core::models::user::User;
 use openstudio_core::repositories::in_memory_user::InMemoryUserRepo;
-use jsonwebtoken::{encode, decode, EncodingKey, DecodingKey, Header, Validation};
-use serde::Serialize;
+use axum::response::IntoResponse;
+use openstudio_core::models::session::RefreshToken;
+use openstudio_core::repositories::in_memory_refresh_token::InMemoryRefreshTokenRepo;
+use openstudio_core::usecases::session::{SessionContext, SessionError};
+use openstudio_core::repositories::in_memory_password_reset::InMemoryPasswordResetRepo;
+use openstudio_core::usecases::password_reset::{PasswordResetContext, PasswordResetError, MIN_PASSWORD_LEN, RESET_TOKEN_MINUTES};
+use openstudio_core::repositories::in_memory_email_verification::InMemoryEmailVerificationRepo;
+use openstudio_core::repositories::in_memory_org::InMemoryOrgRepo;
+use openstudio_core::usecases::organization::namespace_taken;
+use openstudio_core::usecases::email_verification::{is_valid_email, EmailVerificationContext, EmailVerificationError, VERIFICATION_LINK_HOURS};
+use crate::mail::{Mail, Mailer};
+use crate::routes::project::AuthBearer;
+use crate::token::TokenService;
 
 #[derive(Deserialize)]
 pub struct LoginInput {
@@ -24,17 +35,70 @@ pub struct RegisterInput {
     pub last_name: Option<String>,
 }
 
-#[derive(Serialize, Deserialize)]
-struct Claims {
-    sub: String,
-    email: String,
-    exp: usize,
+#[derive(Deserialize)]
+pub struct ForgotPasswordInput {
+    pub email: String,
+}
+
+#[derive(Deserialize)]
+pub struct ResetPasswordInput {
+    pub token: String,
+    pub password: String,
+}
+
+#[derive(Deserialize)]
+pub struct VerifyEmailQuery {
+    pub token: String,
 }
 
 #[derive(Clone)]
 pub struct AuthState {
     pub repo: Arc<InMemoryUserRepo>,
-    pub jwt_secret: String,
+    pub sessions: Arc<InMemoryRefreshTokenRepo>,
+    pub tokens: Arc<TokenService>,
+    pub resets: Arc<InMemoryPasswordResetRepo>,
+    pub verifications: Arc<InMemoryEmailVerificationRepo>,
+    pub orgs: Arc<InMemoryOrgRepo>,
+    pub mailer: Arc<dyn Mailer>,
+    /// URL publique de l'API, pour les liens envoyés par email.
+    pub public_url: String,
+}
+
+impl AuthState {
+    pub(crate) fn sessions(&self) -> SessionContext<'_> {
+        SessionContext { tokens: &self.sessions }
+    }
+
+    /// Paire access/refresh pour le refresh token `session` qui vient d'être émis.
+    pub(crate) fn token_pair(&self, user: &User, session: &RefreshToken) -> serde_json::Value {
+        serde_json::json!({
+            "access_token": self.tokens.issue_access(user.id, &user.email, session.family_id),
+            "refresh_token": self.tokens.issue_refresh(session),
+        })
+    }
+
+    fn verifications(&self) -> EmailVerificationContext<'_> {
+        EmailVerificationContext { sends: &self.verifications, users: &self.repo }
+    }
+
+    /// Envoie en tâche de fond le lien signé qui confirme l'adresse de `user`.
+    fn send_verification(&self, user: &User) {
+        let token = self.tokens.issue_email_verification(user.id, &user.email);
+        let mail = Mail {
+            to: user.email.clone(),
+            subject: "Confirm your OpenStudio email address".to_string(),
+            body: format!(
+                "Hello {},\n\nConfirm your email address with this link:\n{}/email/verify?token={}\n\nThe link expires in {} hours.\n",
+                user.username, self.public_url, token, VERIFICATION_LINK_HOURS
+            ),
+        };
+        let mailer = self.mailer.clone();
+        tokio::spawn(async move {
+            if let Err(e) = mailer.send(mail).await {
+                eprintln!("Failed to send the verification email: {}", e);
+            }
+        });
+    }
 }
 
 pub fn auth_routes() -> Router<AuthState> {
@@ -42,6 +106,19 @@ pub fn auth_routes() -> Router<AuthState> {
         .route("/login", post(login))
         .route("/register", post(register))
         .route("/refresh", post(refresh_token))
+        .route("/logout", post(logout))
+        .route("/logout/all", post(logout_everywhere))
+        .route("/password/forgot", post(forgot_password))
+        .route("/password/reset", get(reset_password_page).post(reset_password))
+        .route("/password/reset/form", post(reset_password_form))
+        .route("/email/verify", get(verify_email))
+        .route("/email/verify/resend", post(resend_verification))
+        .route("/.well-known/jwks.json", get(jwks))
+}
+
+/// Clés publiques permettant à d'autres services de vérifier nos jetons.
+async fn jwks(State(state): State<AuthState>) -> Json<jsonwebtoken::jwk::JwkSet> {
+    Json(state.tokens.jwks())
 }
 
 #[derive(Deserialize)]
@@ -53,59 +130,186 @@ async fn refresh_token(
     State(state): State<AuthState>,
     Json(input): Json<RefreshInput>,
 ) -> axum::response::Response {
-    use axum::body::Body;
-    use axum::http::Response;
-    let token_data = decode::<Claims>(
-        &input.refresh_token,
-        &DecodingKey::from_secret(state.jwt_secret.as_bytes()),
-        &Validation::default(),
-    );
-    match token_data {
-        Ok(data) => {
-            // Vérifie l'expiration du refresh token
-            let now = chrono::Utc::now().timestamp() as usize;
-            if data.claims.exp < now {
-                return Response::builder()
-                    .status(StatusCode::UNAUTHORIZED)
-                    .body(Body::from("Refresh token expired"))
-                    .unwrap();
+    let Some(claims) = state.tokens.verify_refresh(&input.refresh_token) else {
+        return (StatusCode::UNAUTHORIZED, "Invalid refresh token").into_response();
+    };
+    let Some(user) = state.repo.get_user(claims.sub) else {
+        return (StatusCode::UNAUTHORIZED, "Invalid refresh token").into_response();
+    };
+    match state.sessions().rotate(claims.jti, user.id) {
+        Ok(session) => Json(state.token_pair(&user, &session)).into_response(),
+        Err(SessionError::Expired) => (StatusCode::UNAUTHORIZED, "Refresh token expired").into_response(),
+        Err(SessionError::Reused) => (StatusCode::UNAUTHORIZED, "Refresh token reused, the session has been revoked").into_response(),
+        Err(SessionError::NotFound | SessionError::Revoked) => (StatusCode::UNAUTHORIZED, "Invalid refresh token").into_response(),
+    }
+}
+
+/// Ferme la session du jeton d'accès présenté.
+async fn logout(
+    auth: AuthBearer,
+    State(state): State<AuthState>,
+) -> axum::response::Response {
+    let Some(session_id) = auth.session_id else {
+        return (StatusCode::UNAUTHORIZED, "Unauthorized").into_response();
+    };
+    match state.sessions().logout(session_id, auth.user_id) {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(_) => (StatusCode::UNAUTHORIZED, "Unauthorized").into_response(),
+    }
+}
+
+/// Ferme toutes les sessions de l'appelant, sur tous ses appareils.
+async fn logout_everywhere(
+    auth: AuthBearer,
+    State(state): State<AuthState>,
+) -> axum::response::Response {
+    let revoked = state.sessions().logout_everywhere(auth.user_id);
+    Json(serde_json::json!({ "revoked_sessions": revoked })).into_response()
+}
+
+/// Envoie un lien de réinitialisation. La réponse est la même que l'adresse corresponde à un
+/// compte ou non, ou que l'envoi soit retenu parce que le compte en a trop reçu, et l'envoi
+/// se fait en tâche de fond pour ne rien trahir par le délai.
+async fn forgot_password(
+    State(state): State<AuthState>,
+    Json(input): Json<ForgotPasswordInput>,
+) -> axum::response::Response {
+    let ctx = PasswordResetContext { resets: &state.resets, users: &state.repo };
+    if let Some((user, token)) = ctx.request(&input.email) {
+        let mail = Mail {
+            to: user.email.clone(),
+            subject: "Reset your OpenStudio password".to_string(),
+            body: format!(
+                "Hello {},\n\nUse this link to choose a new password:\n{}/password/reset?token={}\n\nThe link expires in {} minutes. If you did not ask for it, ignore this email.\n",
+                user.username, state.public_url, token, RESET_TOKEN_MINUTES
+            ),
+        };
+        let mailer = state.mailer.clone();
+        tokio::spawn(async move {
+            if let Err(e) = mailer.send(mail).await {
+                eprintln!("Failed to send the password reset email: {}", e);
             }
-            // Génère un nouvel access token (1h)
-            let claims = Claims {
-                sub: data.claims.sub.clone(),
-                email: data.claims.email.clone(),
-                exp: (chrono::Utc::now().timestamp() + 3600) as usize,
-            };
-            let access_token = encode(
-                &Header::default(),
-                &claims,
-                &EncodingKey::from_secret(state.jwt_secret.as_bytes()),
-            ).unwrap();
-            // Génère un nouveau refresh token (7j)
-            let refresh_claims = Claims {
-                sub: data.claims.sub,
-                email: data.claims.email,
-                exp: (chrono::Utc::now().timestamp() + 7 * 24 * 3600) as usize,
-            };
-            let refresh_token = encode(
-                &Header::default(),
-                &refresh_claims,
-                &EncodingKey::from_secret(state.jwt_secret.as_bytes()),
-            ).unwrap();
-            let body = serde_json::json!({"access_token": access_token, "refresh_token": refresh_token});
-            Response::builder()
-                .status(StatusCode::OK)
-                .header("content-type", "application/json")
-                .body(Body::from(body.to_string()))
-                .unwrap()
-        }
-        Err(_) => Response::builder()
-            .status(StatusCode::UNAUTHORIZED)
-            .body(Body::from("Invalid refresh token"))
-            .unwrap(),
+        });
     }
+    (StatusCode::ACCEPTED, "If an account uses this email, a reset link has been sent").into_response()
+}
+
+#[derive(Deserialize)]
+pub struct ResetPageQuery {
+    #[serde(default)]
+    pub token: String,
+}
+
+/// Cible du lien envoyé par email : formulaire de saisie du nouveau mot de passe.
+async fn reset_password_page(
+    State(state): State<AuthState>,
+    axum::extract::Query(query): axum::extract::Query<ResetPageQuery>,
+) -> axum::response::Response {
+    let page = format!(
+        r#"<!doctype html>
+<html>
+<head><meta charset="utf-8"><title>Reset your OpenStudio password</title></head>
+<body>
+<form method="post" action="{}/password/reset/form">
+<input type="hidden" name="token" value="{}">
+<label>New password <input type="password" name="password" minlength="{}" required></label>
+<button type="submit">Update password</button>
+</form>
+</body>
+</html>
+"#,
+        state.public_url,
+        html_escape(&query.token),
+        MIN_PASSWORD_LEN
+    );
+    axum::response::Html(page).into_response()
 }
 
+fn html_escape(value: &str) -> String {
+    value.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;").replace('\'', "&#39;")
+}
+
+/// Remplace le mot de passe et ferme toutes les sessions ouvertes du compte.
+async fn reset_password(
+    State(state): State<AuthState>,
+    Json(input): Json<ResetPasswordInput>,
+) -> axum::response::Response {
+    apply_reset(&state, input)
+}
+
+/// Envoi du formulaire servi par `reset_password_page`.
+async fn reset_password_form(
+    State(state): State<AuthState>,
+    axum::Form(input): axum::Form<ResetPasswordInput>,
+) -> axum::response::Response {
+    apply_reset(&state, input)
+}
+
+fn apply_reset(state: &AuthState, input: ResetPasswordInput) -> axum::response::Response {
+    use argon2::{PasswordHasher, password_hash::SaltString};
+    use rand_core::OsRng;
+    let ctx = PasswordResetContext { resets: &state.resets, users: &state.repo };
+    let hash = |password: &str| {
+        let salt = SaltString::generate(&mut OsRng);
+        Argon2::default().hash_password(password.as_bytes(), &salt).map(|h| h.to_string()).ok()
+    };
+    match ctx.reset(&input.token, &input.password, hash) {
+        Ok(user) => {
+            state.sessions().logout_everywhere(user.id);
+            (StatusCode::OK, "Password updated").into_response()
+        },
+        Err(PasswordResetError::PasswordTooShort) => {
+            let message = format!("The password must contain at least {} characters", MIN_PASSWORD_LEN);
+            (StatusCode::BAD_REQUEST, message).into_response()
+        },
+        Err(PasswordResetError::InvalidToken | PasswordResetError::Expired | PasswordResetError::AlreadyUsed) => {
+            (StatusCode::BAD_REQUEST, "Invalid or expired reset token").into_response()
+        },
+        Err(PasswordResetError::HashFailed) => {
+            (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error").into_response()
+        },
+    }
+}
+
+/// Cible du lien envoyé par email.
+async fn verify_email(
+    State(state): State<AuthState>,
+    axum::extract::Query(query): axum::extract::Query<VerifyEmailQuery>,
+) -> axum::response::Response {
+    let Some(claims) = state.tokens.verify_email_verification(&query.token) else {
+        return (StatusCode::BAD_REQUEST, "Invalid or expired verification link").into_response();
+    };
+    match state.verifications().confirm(claims.sub, &claims.email) {
+        Ok(_) => (StatusCode::OK, "Email verified").into_response(),
+        Err(EmailVerificationError::EmailChanged) => {
+            (StatusCode::BAD_REQUEST, "The email address has changed since this link was sent").into_response()
+        },
+        Err(_) => (StatusCode::BAD_REQUEST, "Invalid or expired verification link").into_response(),
+    }
+}
+
+/// Renvoie le lien de vérification, au plus une fois par minute et quelques fois par jour.
+async fn resend_verification(
+    auth: AuthBearer,
+    State(state): State<AuthState>,
+) -> axum::response::Response {
+    match state.verifications().record_send(auth.user_id) {
+        Ok(user) => {
+            state.send_verification(&user);
+            (StatusCode::ACCEPTED, "Verification email sent").into_response()
+        },
+        Err(EmailVerificationError::AlreadyVerified) => (StatusCode::CONFLICT, "The email address is already verified").into_response(),
+        Err(EmailVerificationError::TooSoon { retry_after }) => (
+            StatusCode::TOO_MANY_REQUESTS,
+            [(axum::http::header::RETRY_AFTER, retry_after.to_string())],
+            "Too many verification emails, try again later",
+        )
+            .into_response(),
+        Err(EmailVerificationError::NotFound | EmailVerificationError::EmailChanged) => {
+            (StatusCode::UNAUTHORIZED, "Unauthorized").into_response()
+        },
+    }
+}
 
 async fn login(
     State(state): State<AuthState>,
@@ -124,29 +328,9 @@ async fn login(
         if let Ok(parsed_hash) = parsed_hash {
             let argon2 = Argon2::default();
             if argon2.verify_password(input.password.as_bytes(), &parsed_hash).is_ok() {
-                // Access token (1h)
-                let claims = Claims {
-                    sub: user.id.to_string(),
-                    email: user.email.clone(),
-                    exp: (chrono::Utc::now().timestamp() + 3600) as usize,
-                };
-                let access_token = encode(
-                    &Header::default(),
-                    &claims,
-                    &EncodingKey::from_secret(state.jwt_secret.as_bytes()),
-                ).unwrap();
-                // Refresh token (7j)
-                let refresh_claims = Claims {
-                    sub: user.id.to_string(),
-                    email: user.email.clone(),
-                    exp: (chrono::Utc::now().timestamp() + 7 * 24 * 3600) as usize,
-                };
-                let refresh_token = encode(
-                    &Header::default(),
-                    &refresh_claims,
-                    &EncodingKey::from_secret(state.jwt_secret.as_bytes()),
-                ).unwrap();
-                let body = serde_json::json!({"access_token": access_token, "refresh_token": refresh_token});
+                // Chaque connexion ouvre une nouvelle session
+                let session = state.sessions().start(user.id);
+                let body = state.token_pair(&user, &session);
                 return Response::builder()
                     .status(StatusCode::OK)
                     .header("content-type", "application/json")
@@ -175,14 +359,23 @@ async fn register(
     use argon2::{Argon2, PasswordHasher, password_hash::SaltString};
     use rand_core::OsRng;
     
-    // Vérifier si l'utilisateur existe déjà
+    let email = input.email.trim().to_string();
+    if !is_valid_email(&email) {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .header("content-type", "application/json")
+            .body(Body::from(r#"{"error": "Invalid email address"}"#))
+            .unwrap();
+    }
+
+    // Vérifier si l'utilisateur existe déjà, ou si son nom est le slug d'une organisation
     let existing_user = state
         .repo
         .list_users()
         .into_iter()
-        .find(|u| u.username == input.username || u.email == input.email);
+        .find(|u| u.email.eq_ignore_ascii_case(&email));
     
-    if existing_user.is_some() {
+    if existing_user.is_some() || namespace_taken(&state.repo, &state.orgs, &input.username) {
         return Response::builder()
             .status(StatusCode::CONFLICT)
             .header("content-type", "application/json")
@@ -200,7 +393,9 @@ async fn register(
         let new_user = User {
             id: uuid::Uuid::new_v4(),
             username: input.username,
-            email: input.email,
+            email,
+            // Confirmée par le lien envoyé juste après
+            email_verified: false,
             password: password_hash.to_string(),
             first_name: input.first_name,
             last_name: input.last_name,
@@ -210,37 +405,22 @@ async fn register(
         
         // Ajouter l'utilisateur au repository
         let created_user = state.repo.create_user(new_user);
+        if state.verifications().record_send(created_user.id).is_ok() {
+            state.send_verification(&created_user);
+        }
         
         // Générer les tokens
-        let claims = Claims {
-            sub: created_user.id.to_string(),
-            email: created_user.email.clone(),
-            exp: (chrono::Utc::now().timestamp() + 3600) as usize,
-        };
-        let access_token = encode(
-            &Header::default(),
-            &claims,
-            &EncodingKey::from_secret(state.jwt_secret.as_bytes()),
-        ).unwrap();
-        
-        let refresh_claims = Claims {
-            sub: created_user.id.to_string(),
-            email: created_user.email.clone(),
-            exp: (chrono::Utc::now().timestamp() + 7 * 24 * 3600) as usize,
-        };
-        let refresh_token = encode(
-            &Header::default(),
-            &refresh_claims,
-            &EncodingKey::from_secret(state.jwt_secret.as_bytes()),
-        ).unwrap();
+        let session = state.sessions().start(created_user.id);
+        let tokens = state.token_pair(&created_user, &session);
         
         let response_data = serde_json::json!({
-            "access_token": access_token,
-            "refresh_token": refresh_token,
+            "access_token": tokens["access_token"],
+            "refresh_token": tokens["refresh_token"],
             "user": {
                 "id": created_user.id,
                 "username": created_user.username,
                 "email": created_user.email,
+                "email_verified": created_user.email_verified,
                 "first_name": created_user.first_name,
                 "last_name": created_user.last_name
             }
@@ -0,0 +1,38 @@
+use axum::{body::Body, extract::FromRef, http::{Response, StatusCode}};
+use openstudio_core::usecases::access::ProjectAccess;
+
+use crate::routes::{
+    invitation::InvitationState, issue::IssueState, member::MemberState, organization::OrgState,
+    project::AppState, time::TimeState, wiki::WikiState,
+};
+
+pub fn json_response<T: serde::Serialize>(status: StatusCode, value: &T) -> axum::response::Response {
+    Response::builder()
+        .status(status)
+        .header("content-type", "application/json")
+        .body(Body::from(serde_json::to_string(value).unwrap()))
+        .unwrap()
+}
+
+pub fn text_response(status: StatusCode, message: &'static str) -> axum::response::Response {
+    Response::builder()
+        .status(status)
+        .body(Body::from(message))
+        .unwrap()
+}
+
+/// Rend `ProjectAccess` extractible (et donc `CurrentUser`) depuis les états qui le portent
+/// dans un champ `access`.
+macro_rules! project_access_from {
+    ($($state:ty),* $(,)?) => {
+        $(
+            impl FromRef<$state> for ProjectAccess {
+                fn from_ref(state: &$state) -> Self {
+                    state.access.clone()
+                }
+            }
+        )*
+    };
+}
+
+project_access_from!(AppState, IssueState, InvitationState, MemberState, OrgState, TimeState, WikiState);
@@ -0,0 +1,88 @@
+use axum::{extract::State, http::StatusCode, routing::get, Router};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use openstudio_core::repositories::in_memory_comment::InMemoryCommentRepo;
+use openstudio_core::repositories::in_memory_issue_history::InMemoryIssueHistoryRepo;
+use openstudio_core::repositories::in_memory_social::InMemorySocialRepo;
+use openstudio_core::repositories::in_memory_user::InMemoryUserRepo;
+use openstudio_core::repositories::issue_repository::IssueRepository;
+use openstudio_core::repositories::project_repository::ProjectRepository;
+use openstudio_core::usecases::explore::{ExploreContext, ProjectActivity, TrendingProject, DEFAULT_WINDOW_DAYS};
+use crate::routes::project::ProjectView;
+
+#[derive(Deserialize)]
+pub struct ExploreQuery {
+    pub topic: Option<String>,
+    pub window_days: Option<i64>,
+}
+
+#[derive(Clone)]
+pub struct ExploreState {
+    pub projects: Arc<dyn ProjectRepository + Send + Sync + 'static>,
+    pub issues: Arc<dyn IssueRepository + Send + Sync + 'static>,
+    pub history: Arc<InMemoryIssueHistoryRepo>,
+    pub comments: Arc<InMemoryCommentRepo>,
+    pub users: Arc<InMemoryUserRepo>,
+    pub social: Arc<InMemorySocialRepo>,
+}
+
+/// Projet en tendance tel qu'exposé par l'API, avec ses compteurs.
+#[derive(Serialize)]
+struct TrendingView<'a> {
+    #[serde(flatten)]
+    project: ProjectView<'a>,
+    activity: &'a ProjectActivity,
+    score: f64,
+}
+
+impl<'a> TrendingView<'a> {
+    fn new(trending: &'a TrendingProject, social: &InMemorySocialRepo) -> Self {
+        Self {
+            project: ProjectView::new(&trending.project, social),
+            activity: &trending.activity,
+            score: trending.score,
+        }
+    }
+}
+
+pub fn explore_routes() -> Router<ExploreState> {
+    Router::new().route("/explore", get(explore_projects))
+}
+
+async fn explore_projects(
+    State(state): State<ExploreState>,
+    axum::extract::Query(query): axum::extract::Query<ExploreQuery>,
+) -> axum::response::Response {
+    use axum::body::Body;
+    use axum::http::Response;
+    let projects = match state.projects.list() {
+        Ok(projects) => projects,
+        Err(_) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from("Internal server error"))
+                .unwrap();
+        }
+    };
+    let context = ExploreContext {
+        issues: state.issues.as_ref(),
+        history: &state.history,
+        comments: &state.comments,
+        users: &state.users,
+    };
+    let window_days = query.window_days.unwrap_or(DEFAULT_WINDOW_DAYS);
+    match context.explore(projects, query.topic.as_deref(), window_days) {
+        Ok(trending) => {
+            let views: Vec<TrendingView> = trending.iter().map(|t| TrendingView::new(t, &state.social)).collect();
+            Response::builder()
+                .status(StatusCode::OK)
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_string(&views).unwrap()))
+                .unwrap()
+        },
+        Err(_) => Response::builder()
+            .status(StatusCode::INTERNAL_SERVER_ERROR)
+            .body(Body::from("Internal server error"))
+            .unwrap(),
+    }
+}
@@ -0,0 +1,230 @@
+use axum::response::IntoResponse;
+use axum::{extract::State, http::StatusCode, routing::{delete, get, post}, Json, Router};
+use serde::Deserialize;
+use std::sync::Arc;
+use openstudio_core::models::invitation::Invitation;
+use openstudio_core::models::project::Project;
+use openstudio_core::models::user::{ProjectRole, User};
+use openstudio_core::repositories::in_memory_invitation::InMemoryInvitationRepo;
+use openstudio_core::repositories::in_memory_stats::InMemoryStatsCache;
+use openstudio_core::repositories::in_memory_subscription::InMemorySubscriptionRepo;
+use openstudio_core::repositories::in_memory_user::InMemoryUserRepo;
+use openstudio_core::repositories::project_repository::ProjectRepository;
+use openstudio_core::usecases::access::ProjectAccess;
+use openstudio_core::usecases::invitation::{InvitationContext, InvitationError, Invitee, DEFAULT_INVITATION_DAYS};
+use openstudio_core::usecases::member::MemberError;
+use openstudio_core::usecases::policy::Action;
+use openstudio_core::usecases::subscription::watch_as_member;
+use uuid;
+use crate::routes::project::{AuthBearer, CurrentUser};
+use crate::routes::common::json_response;
+
+#[derive(Clone)]
+pub struct InvitationState {
+    pub repo: Arc<InMemoryInvitationRepo>,
+    pub users: Arc<InMemoryUserRepo>,
+    pub projects: Arc<dyn ProjectRepository + Send + Sync + 'static>,
+    pub subscriptions: Arc<InMemorySubscriptionRepo>,
+    pub stats: Arc<InMemoryStatsCache>,
+    pub access: ProjectAccess,
+}
+
+/// Exactement un des deux champs `username` et `email`.
+#[derive(Deserialize)]
+pub struct CreateInvitationInput {
+    pub username: Option<String>,
+    pub email: Option<String>,
+    pub role: ProjectRole,
+    pub expires_in_days: Option<i64>,
+}
+
+#[derive(Deserialize)]
+pub struct InvitationTokenInput {
+    pub token: String,
+}
+
+impl InvitationState {
+    fn context(&self) -> InvitationContext<'_> {
+        InvitationContext { invitations: &self.repo, users: &self.users, access: &self.access }
+    }
+
+    /// Projet visible par l'appelant et encore modifiable.
+    fn writable_project(&self, id: uuid::Uuid, caller: uuid::Uuid) -> Result<Project, (StatusCode, &'static str)> {
+        match self.projects.get_by_id(id) {
+            Ok(Some(project)) if !self.access.can_view(&project, Some(caller)) => Err((StatusCode::NOT_FOUND, "Project not found")),
+            Ok(Some(project)) if project.is_read_only() => Err((StatusCode::FORBIDDEN, "Project is archived")),
+            Ok(Some(project)) => Ok(project),
+            Ok(None) => Err((StatusCode::NOT_FOUND, "Project not found")),
+            Err(_) => Err((StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")),
+        }
+    }
+
+    fn accept(&self, invitation: Invitation, user: &User) -> axum::response::Response {
+        let project = match self.writable_project(invitation.project_id, user.id) {
+            Ok(project) => project,
+            // Un projet privé reste visible de celui qui y est invité
+            Err((StatusCode::NOT_FOUND, _)) => match self.projects.get_by_id(invitation.project_id) {
+                Ok(Some(project)) if !project.is_read_only() => project,
+                _ => return (StatusCode::NOT_FOUND, "Project not found").into_response(),
+            },
+            Err(rejection) => return rejection.into_response(),
+        };
+        match self.context().accept(invitation, &project, user) {
+            Ok(member) => {
+                self.stats.invalidate(project.id);
+                watch_as_member(&self.subscriptions, &project, user.id);
+                json_response(StatusCode::OK, &member)
+            },
+            Err(e) => invitation_error(e),
+        }
+    }
+
+    fn decline(&self, invitation: Invitation, user: &User) -> axum::response::Response {
+        match self.context().decline(invitation, user) {
+            Ok(invitation) => json_response(StatusCode::OK, &invitation),
+            Err(e) => invitation_error(e),
+        }
+    }
+}
+
+pub fn invitation_routes() -> Router<InvitationState> {
+    Router::new()
+        .route("/projects/{id}/invitations", post(create_invitation).get(list_project_invitations))
+        .route("/projects/{id}/invitations/{invitation_id}", delete(revoke_invitation))
+        .route("/invitations", get(list_my_invitations))
+        .route("/invitations/accept", post(accept_by_token))
+        .route("/invitations/decline", post(decline_by_token))
+        .route("/invitations/{id}/accept", post(accept_invitation))
+        .route("/invitations/{id}/decline", post(decline_invitation))
+}
+
+fn invitation_error(error: InvitationError) -> axum::response::Response {
+    let (status, message) = match error {
+        InvitationError::NotFound => (StatusCode::NOT_FOUND, "Invitation not found"),
+        InvitationError::Forbidden => (StatusCode::FORBIDDEN, "Forbidden"),
+        InvitationError::UnknownUser => (StatusCode::BAD_REQUEST, "Unknown user"),
+        InvitationError::AlreadyMember => (StatusCode::CONFLICT, "The user is already a member"),
+        InvitationError::AlreadyInvited => (StatusCode::CONFLICT, "The user already has a pending invitation"),
+        InvitationError::Expired => (StatusCode::GONE, "The invitation has expired"),
+        InvitationError::AlreadyAnswered => (StatusCode::CONFLICT, "The invitation is no longer pending"),
+        // Même réponse qu'une invitation inexistante, pour ne rien révéler
+        InvitationError::NotInvitee => (StatusCode::NOT_FOUND, "Invitation not found"),
+        InvitationError::Member(MemberError::Storage(_)) => (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error"),
+        InvitationError::Member(_) => (StatusCode::CONFLICT, "The membership cannot be changed"),
+    };
+    (status, message).into_response()
+}
+
+async fn create_invitation(
+    auth: AuthBearer,
+    State(state): State<InvitationState>,
+    axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
+    Json(input): Json<CreateInvitationInput>,
+) -> axum::response::Response {
+    let project = match state.writable_project(id, auth.user_id) {
+        Ok(project) => project,
+        Err(rejection) => return rejection.into_response(),
+    };
+    let invitee = match (input.username, input.email) {
+        (Some(username), None) => Invitee::Username(username),
+        (None, Some(email)) if email.contains('@') => Invitee::Email(email),
+        _ => return (StatusCode::BAD_REQUEST, "Provide either a username or a valid email").into_response(),
+    };
+    let ttl_days = input.expires_in_days.unwrap_or(DEFAULT_INVITATION_DAYS);
+    match state.context().invite(&project, auth.user_id, &invitee, input.role, ttl_days) {
+        Ok((invitation, token)) => {
+            // Le jeton n'est renvoyé qu'ici : seule son empreinte est conservée
+            let mut body = serde_json::to_value(&invitation).unwrap();
+            body["token"] = token.into();
+            json_response(StatusCode::CREATED, &body)
+        },
+        Err(e) => invitation_error(e),
+    }
+}
+
+async fn list_project_invitations(
+    caller: CurrentUser,
+    State(state): State<InvitationState>,
+    axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
+) -> axum::response::Response {
+    let project = match state.projects.get_by_id(id) {
+        Ok(Some(project)) => project,
+        Ok(None) => return (StatusCode::NOT_FOUND, "Project not found").into_response(),
+        Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error").into_response(),
+    };
+    if let Err(rejection) = caller.authorize(&project, Action::ManageMembers) {
+        return rejection.into_response();
+    }
+    json_response(StatusCode::OK, &state.repo.list_by_project(id))
+}
+
+async fn revoke_invitation(
+    auth: AuthBearer,
+    State(state): State<InvitationState>,
+    axum::extract::Path((id, invitation_id)): axum::extract::Path<(uuid::Uuid, uuid::Uuid)>,
+) -> axum::response::Response {
+    let project = match state.writable_project(id, auth.user_id) {
+        Ok(project) => project,
+        Err(rejection) => return rejection.into_response(),
+    };
+    match state.context().revoke(&project, auth.user_id, invitation_id) {
+        Ok(invitation) => json_response(StatusCode::OK, &invitation),
+        Err(e) => invitation_error(e),
+    }
+}
+
+async fn list_my_invitations(
+    caller: CurrentUser,
+    State(state): State<InvitationState>,
+) -> axum::response::Response {
+    json_response(StatusCode::OK, &state.context().pending_for(&caller.user))
+}
+
+async fn accept_invitation(
+    caller: CurrentUser,
+    State(state): State<InvitationState>,
+    axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
+) -> axum::response::Response {
+    let user = caller.user;
+    match state.repo.get(id) {
+        Some(invitation) => state.accept(invitation, &user),
+        None => invitation_error(InvitationError::NotFound),
+    }
+}
+
+async fn decline_invitation(
+    caller: CurrentUser,
+    State(state): State<InvitationState>,
+    axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
+) -> axum::response::Response {
+    let user = caller.user;
+    match state.repo.get(id) {
+        Some(invitation) => state.decline(invitation, &user),
+        None => invitation_error(InvitationError::NotFound),
+    }
+}
+
+/// Acceptation depuis le lien envoyé à l'invité.
+async fn accept_by_token(
+    caller: CurrentUser,
+    State(state): State<InvitationState>,
+    Json(input): Json<InvitationTokenInput>,
+) -> axum::response::Response {
+    let user = caller.user;
+    match state.context().find_by_token(&input.token) {
+        Some(invitation) => state.accept(invitation, &user),
+        None => invitation_error(InvitationError::NotFound),
+    }
+}
+
+async fn decline_by_token(
+    caller: CurrentUser,
+    State(state): State<InvitationState>,
+    Json(input): Json<InvitationTokenInput>,
+) -> axum::response::Response {
+    let user = caller.user;
+    match state.context().find_by_token(&input.token) {
+        Some(invitation) => state.decline(invitation, &user),
+        None => invitation_error(InvitationError::NotFound),
+    }
+}
@@ -1,8 +1,28 @@
+use axum::response::IntoResponse;
 use axum::{extract::State, http::StatusCode, routing::{get, post}, Json, Router};
 use serde::Deserialize;
 use std::sync::Arc;
+use openstudio_core::models::comment::Comment;
 use openstudio_core::models::issue::{Issue, IssueStatus};
+use openstudio_core::models::notification::NotificationKind;
+use openstudio_core::models::project::Project;
+use openstudio_core::models::subscription::{SubscriptionReason, SubscriptionTarget};
+use openstudio_core::repositories::in_memory_comment::InMemoryCommentRepo;
+use openstudio_core::repositories::in_memory_issue_history::InMemoryIssueHistoryRepo;
+use openstudio_core::repositories::in_memory_notification::{InMemoryMentionRepo, InMemoryNotificationRepo};
+use openstudio_core::repositories::in_memory_search::{InMemorySearchIndex, SearchDocument, SearchKind};
+use openstudio_core::repositories::in_memory_stats::InMemoryStatsCache;
+use openstudio_core::repositories::in_memory_subscription::InMemorySubscriptionRepo;
+use openstudio_core::repositories::in_memory_user::InMemoryUserRepo;
 use openstudio_core::repositories::issue_repository::IssueRepository;
+use openstudio_core::repositories::project_repository::ProjectRepository;
+use openstudio_core::usecases::issue::{diff_issue, revert_issue, update_issue, RevertError};
+use openstudio_core::usecases::issue_io::{export_issues, import_issues, ExchangeFormat, ImportedAs};
+use openstudio_core::usecases::access::ProjectAccess;
+use openstudio_core::usecases::mention::MentionContext;
+use openstudio_core::usecases::policy::Action;
+use openstudio_core::usecases::subscription::{auto_subscribe, WatcherContext};
+use crate::routes::project::{AuthBearer, CurrentUser};
 use uuid;
 use chrono::Utc;
 
@@ -11,6 +31,10 @@ pub struct CreateIssueInput {
     pub project_id: uuid::Uuid,
     pub title: String,
     pub description: String,
+    #[serde(default)]
+    pub assignee_id: Option<uuid::Uuid>,
+    #[serde(default)]
+    pub estimate_minutes: Option<u32>,
 }
 
 #[derive(Deserialize)]
@@ -18,11 +42,135 @@ pub struct UpdateIssueInput {
     pub title: Option<String>,
     pub description: Option<String>,
     pub status: Option<IssueStatus>,
+    /// Absent : inchangé, `null` : désassigner.
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub assignee_id: Option<Option<uuid::Uuid>>,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub estimate_minutes: Option<Option<u32>>,
+}
+
+/// Distingue un champ absent (`None`) d'un `null` explicite (`Some(None)`).
+pub(crate) fn deserialize_some<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
+
+#[derive(Deserialize)]
+pub struct CreateCommentInput {
+    pub body: String,
+}
+
+#[derive(Deserialize)]
+pub struct RevertIssueInput {
+    pub revision: u32,
+}
+
+#[derive(Deserialize)]
+pub struct ExportQuery {
+    #[serde(default)]
+    pub format: ExchangeFormat,
+}
+
+#[derive(Deserialize)]
+pub struct ImportQuery {
+    #[serde(default)]
+    pub format: ExchangeFormat,
+    #[serde(default)]
+    pub dry_run: bool,
 }
 
 #[derive(Clone)]
 pub struct IssueState {
     pub repo: Arc<dyn IssueRepository + Send + Sync + 'static>,
+    pub projects: Arc<dyn ProjectRepository + Send + Sync + 'static>,
+    pub history: Arc<InMemoryIssueHistoryRepo>,
+    pub users: Arc<InMemoryUserRepo>,
+    pub comments: Arc<InMemoryCommentRepo>,
+    pub mentions: Arc<InMemoryMentionRepo>,
+    pub notifications: Arc<InMemoryNotificationRepo>,
+    pub subscriptions: Arc<InMemorySubscriptionRepo>,
+    pub stats: Arc<InMemoryStatsCache>,
+    pub index: Arc<InMemorySearchIndex>,
+    pub access: ProjectAccess,
+}
+
+impl IssueState {
+    /// Traite les `@mentions` de `text` pour `issue` (description ou commentaire).
+    fn process_mentions(&self, issue: &Issue, comment_id: Option<uuid::Uuid>, text: &str, author_id: Option<uuid::Uuid>) {
+        let project = self.projects.get_by_id(issue.project_id).ok().flatten();
+        let context = MentionContext {
+            users: &self.users,
+            access: &self.access,
+            mentions: &self.mentions,
+            notifications: &self.notifications,
+        };
+        context.process(project.as_ref(), issue, comment_id, text, author_id);
+    }
+
+    fn notify_watchers(&self, issue: &Issue, actor_id: Option<uuid::Uuid>, kind: NotificationKind, message: &str) {
+        let project = self.projects.get_by_id(issue.project_id).ok().flatten();
+        let context = WatcherContext {
+            subscriptions: &self.subscriptions,
+            access: &self.access,
+            notifications: &self.notifications,
+        };
+        context.notify(project.as_ref(), issue, actor_id, kind, message);
+    }
+
+    /// Les issues d'un projet privé ne sont visibles que de ses membres. Une issue dont le
+    /// projet a été supprimé n'est plus lisible : on ne sait plus qui pouvait la voir.
+    fn project_visible(&self, project_id: uuid::Uuid, caller: Option<uuid::Uuid>) -> Result<bool, (StatusCode, &'static str)> {
+        match self.projects.get_by_id(project_id) {
+            Ok(Some(project)) => Ok(self.access.can_view(&project, caller)),
+            Ok(None) => Ok(false),
+            Err(_) => Err((StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")),
+        }
+    }
+
+    fn visible_issue(&self, id: uuid::Uuid, caller: Option<uuid::Uuid>) -> Result<Issue, (StatusCode, &'static str)> {
+        match self.repo.get_by_id(id) {
+            Ok(Some(issue)) if self.project_visible(issue.project_id, caller)? => Ok(issue),
+            Ok(_) => Err((StatusCode::NOT_FOUND, "Issue not found")),
+            Err(_) => Err((StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")),
+        }
+    }
+
+    /// Projet visé par une écriture : l'appelant doit y être autorisé et le projet ne doit
+    /// pas être archivé.
+    fn authorize(&self, caller: &CurrentUser, project_id: uuid::Uuid, action: Action) -> Result<Project, (StatusCode, &'static str)> {
+        let project = match self.projects.get_by_id(project_id) {
+            Ok(Some(project)) => project,
+            Ok(None) => return Err((StatusCode::NOT_FOUND, "Project not found")),
+            Err(_) => return Err((StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")),
+        };
+        caller.authorize(&project, action)?;
+        if project.is_read_only() {
+            return Err((StatusCode::FORBIDDEN, "Project is archived"));
+        }
+        Ok(project)
+    }
+
+    /// Comme `authorize`, pour l'issue `issue_id`. Une issue d'un projet invisible ou supprimé
+    /// n'est plus modifiable.
+    fn authorize_issue(&self, caller: &CurrentUser, issue_id: uuid::Uuid, action: Action) -> Result<(Issue, Project), (StatusCode, &'static str)> {
+        let issue = match self.repo.get_by_id(issue_id) {
+            Ok(Some(issue)) => issue,
+            Ok(None) => return Err((StatusCode::NOT_FOUND, "Issue not found")),
+            Err(_) => return Err((StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")),
+        };
+        match self.authorize(caller, issue.project_id, action) {
+            Ok(project) => Ok((issue, project)),
+            Err((StatusCode::NOT_FOUND, _)) => Err((StatusCode::NOT_FOUND, "Issue not found")),
+            Err(rejection) => Err(rejection),
+        }
+    }
+
+    fn assignee_exists(&self, assignee_id: Option<uuid::Uuid>) -> bool {
+        assignee_id.is_none_or(|id| self.users.get_user(id).is_some())
+    }
 }
 
 pub fn issue_routes() -> Router<IssueState> {
@@ -32,28 +180,62 @@ pub fn issue_routes() -> Router<IssueState> {
         .route("/issues/{id}", get(get_issue_by_id))
         .route("/issues/{id}", axum::routing::put(update_issue_by_id))
         .route("/issues/{id}", axum::routing::delete(delete_issue_by_id))
+        .route("/issues/{id}/history", get(get_issue_history))
+        .route("/issues/{id}/revert", post(revert_issue_by_id))
+        .route("/issues/{id}/comments", post(create_comment))
+        .route("/issues/{id}/comments", get(list_comments))
+        .route("/issues/{id}/mentions", get(list_mentions))
+        .route("/projects/{id}/issues/export", get(export_project_issues))
+        .route("/projects/{id}/issues/import", post(import_project_issues))
 }
 
 async fn create_issue(
+    caller: CurrentUser,
     State(state): State<IssueState>,
     Json(input): Json<CreateIssueInput>,
 ) -> axum::response::Response {
     use axum::body::Body;
     use axum::http::Response;
+    if !state.assignee_exists(input.assignee_id) {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from("Unknown assignee"))
+            .unwrap();
+    }
+    let project = match state.authorize(&caller, input.project_id, Action::OpenIssue) {
+        Ok(project) => project,
+        Err(rejection) => return rejection.into_response(),
+    };
+    let author_id = Some(caller.id());
+    let assignee_id = input.assignee_id.or(project.settings.default_assignee_id);
     let issue = Issue {
         id: uuid::Uuid::new_v4(),
         project_id: input.project_id,
         title: input.title,
         description: input.description,
         status: IssueStatus::Open,
+        author_id,
+        assignee_id,
+        estimate_minutes: input.estimate_minutes,
         created_at: Utc::now(),
         updated_at: Utc::now(),
     };
-    match state.repo.save(issue) {
-        Ok(_) => Response::builder()
-            .status(StatusCode::CREATED)
-            .body(Body::from("Issue created"))
-            .unwrap(),
+    match state.repo.save(issue.clone()) {
+        Ok(_) => {
+            state.stats.invalidate(issue.project_id);
+            state.index.upsert(SearchDocument::from(&issue));
+            if let Some(author_id) = author_id {
+                auto_subscribe(&state.subscriptions, Some(&project), author_id, issue.id, SubscriptionReason::Author);
+            }
+            if let Some(assignee_id) = issue.assignee_id {
+                auto_subscribe(&state.subscriptions, Some(&project), assignee_id, issue.id, SubscriptionReason::Assignee);
+            }
+            state.process_mentions(&issue, None, &issue.description, author_id);
+            Response::builder()
+                .status(StatusCode::CREATED)
+                .body(Body::from("Issue created"))
+                .unwrap()
+        },
         Err(_) => Response::builder()
             .status(StatusCode::INTERNAL_SERVER_ERROR)
             .body(Body::from("Internal server error"))
@@ -62,6 +244,7 @@ async fn create_issue(
 }
 
 async fn list_issues_by_project(
+    auth: Option<AuthBearer>,
     State(state): State<IssueState>,
     axum::extract::Query(params): axum::extract::Query<std::collections::HashMap<String, String>>,
 ) -> axum::response::Response {
@@ -84,6 +267,16 @@ async fn list_issues_by_project(
                 .unwrap();
         }
     };
+    match state.project_visible(project_id, auth.map(|a| a.user_id)) {
+        Ok(true) => {},
+        Ok(false) => {
+            return Response::builder()
+                .status(StatusCode::NOT_FOUND)
+                .body(Body::from("Project not found"))
+                .unwrap();
+        },
+        Err(rejection) => return rejection.into_response(),
+    }
     match state.repo.list_by_project(project_id) {
         Ok(issues) => {
             let body = serde_json::to_string(&issues).unwrap();
@@ -101,13 +294,14 @@ async fn list_issues_by_project(
 }
 
 async fn get_issue_by_id(
+    auth: Option<AuthBearer>,
     State(state): State<IssueState>,
     axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
 ) -> axum::response::Response {
     use axum::body::Body;
     use axum::http::Response;
-    match state.repo.get_by_id(id) {
-        Ok(Some(issue)) => {
+    match state.visible_issue(id, auth.map(|a| a.user_id)) {
+        Ok(issue) => {
             let body = serde_json::to_string(&issue).unwrap();
             Response::builder()
                 .status(StatusCode::OK)
@@ -115,54 +309,83 @@ async fn get_issue_by_id(
                 .body(Body::from(body))
                 .unwrap()
         },
-        Ok(None) => Response::builder()
-            .status(StatusCode::NOT_FOUND)
-            .body(Body::from("Issue not found"))
-            .unwrap(),
-        Err(_) => Response::builder()
-            .status(StatusCode::INTERNAL_SERVER_ERROR)
-            .body(Body::from("Internal server error"))
-            .unwrap(),
+        Err(rejection) => rejection.into_response(),
     }
 }
 
 async fn update_issue_by_id(
+    caller: CurrentUser,
     State(state): State<IssueState>,
     axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
     Json(input): Json<UpdateIssueInput>,
 ) -> axum::response::Response {
     use axum::body::Body;
     use axum::http::Response;
-    let existing = match state.repo.get_by_id(id) {
-        Ok(Some(i)) => i,
-        Ok(None) => {
-            return Response::builder()
-                .status(StatusCode::NOT_FOUND)
-                .body(Body::from("Issue not found"))
-                .unwrap();
-        },
-        Err(_) => {
-            return Response::builder()
-                .status(StatusCode::INTERNAL_SERVER_ERROR)
-                .body(Body::from("Internal server error"))
-                .unwrap();
-        }
+    let (existing, project) = match state.authorize_issue(&caller, id, Action::EditIssue) {
+        Ok(found) => found,
+        Err(rejection) => return rejection.into_response(),
     };
+    if let Some(status) = &input.status
+        && *status != existing.status
+        && !project.settings.allows_state(status)
+    {
+        return Response::builder()
+            .status(StatusCode::CONFLICT)
+            .body(Body::from(format!("The status {} is disabled for this project", status)))
+            .unwrap();
+    }
+    if let Some(status) = &input.status
+        && !project.allows_transition(&existing.status, status)
+    {
+        return Response::builder()
+            .status(StatusCode::CONFLICT)
+            .body(Body::from(format!(
+                "The project workflow does not allow moving from {} to {}",
+                existing.status, status
+            )))
+            .unwrap();
+    }
+    let assignee_id = input.assignee_id.unwrap_or(existing.assignee_id);
+    if !state.assignee_exists(assignee_id) {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from("Unknown assignee"))
+            .unwrap();
+    }
+    let before = existing.clone();
     let updated = Issue {
         id,
         project_id: existing.project_id,
         title: input.title.unwrap_or(existing.title),
         description: input.description.unwrap_or(existing.description),
         status: input.status.unwrap_or(existing.status),
+        author_id: existing.author_id,
+        assignee_id,
+        estimate_minutes: input.estimate_minutes.unwrap_or(existing.estimate_minutes),
         created_at: existing.created_at,
         updated_at: Utc::now(),
     };
-    match state.repo.update(updated) {
-        Ok(true) => Response::builder()
-            .status(StatusCode::OK)
-            .body(Body::from("Issue updated"))
-            .unwrap(),
-        Ok(false) => Response::builder()
+    let actor_id = Some(caller.id());
+    match update_issue(state.repo.as_ref(), &state.history, updated, actor_id) {
+        Ok(Some(issue)) => {
+            state.stats.invalidate(issue.project_id);
+            state.index.upsert(SearchDocument::from(&issue));
+            if let Some(assignee_id) = issue.assignee_id {
+                auto_subscribe(&state.subscriptions, Some(&project), assignee_id, id, SubscriptionReason::Assignee);
+            }
+            state.process_mentions(&issue, None, &issue.description, actor_id);
+            let changes = diff_issue(&before, &issue);
+            if !changes.is_empty() {
+                let fields: Vec<&str> = changes.iter().map(|c| c.field.as_str()).collect();
+                let message = format!("Issue \"{}\" was updated: {}", issue.title, fields.join(", "));
+                state.notify_watchers(&issue, actor_id, NotificationKind::IssueUpdated, &message);
+            }
+            Response::builder()
+                .status(StatusCode::OK)
+                .body(Body::from("Issue updated"))
+                .unwrap()
+        },
+        Ok(None) => Response::builder()
             .status(StatusCode::NOT_FOUND)
             .body(Body::from("Issue not found"))
             .unwrap(),
@@ -174,16 +397,29 @@ async fn update_issue_by_id(
 }
 
 async fn delete_issue_by_id(
+    caller: CurrentUser,
     State(state): State<IssueState>,
     axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
 ) -> axum::response::Response {
     use axum::body::Body;
     use axum::http::Response;
+    let project = match state.authorize_issue(&caller, id, Action::DeleteIssue) {
+        Ok((_, project)) => project,
+        Err(rejection) => return rejection.into_response(),
+    };
     match state.repo.delete(id) {
-        Ok(true) => Response::builder()
-            .status(StatusCode::OK)
-            .body(Body::from("Issue deleted"))
-            .unwrap(),
+        Ok(true) => {
+            state.stats.invalidate(project.id);
+            state.index.remove(SearchKind::Issue, id);
+            state.history.delete_for_issue(id);
+            state.comments.delete_for_issue(id);
+            state.mentions.delete_for_issue(id);
+            state.subscriptions.delete_target(SubscriptionTarget::Issue(id));
+            Response::builder()
+                .status(StatusCode::OK)
+                .body(Body::from("Issue deleted"))
+                .unwrap()
+        },
         Ok(false) => Response::builder()
             .status(StatusCode::NOT_FOUND)
             .body(Body::from("Issue not found"))
@@ -194,3 +430,237 @@ async fn delete_issue_by_id(
             .unwrap(),
     }
 }
+
+async fn export_project_issues(
+    auth: Option<AuthBearer>,
+    State(state): State<IssueState>,
+    axum::extract::Path(project_id): axum::extract::Path<uuid::Uuid>,
+    axum::extract::Query(query): axum::extract::Query<ExportQuery>,
+) -> axum::response::Response {
+    use axum::body::Body;
+    use axum::http::Response;
+    match state.projects.get_by_id(project_id) {
+        Ok(Some(project)) if state.access.can_view(&project, auth.map(|a| a.user_id)) => {},
+        Ok(_) => {
+            return Response::builder()
+                .status(StatusCode::NOT_FOUND)
+                .body(Body::from("Project not found"))
+                .unwrap();
+        },
+        Err(_) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from("Internal server error"))
+                .unwrap();
+        }
+    }
+    let exported = state
+        .repo
+        .list_by_project(project_id)
+        .and_then(|issues| export_issues(&issues, query.format));
+    match exported {
+        Ok(body) => {
+            let (content_type, extension) = match query.format {
+                ExchangeFormat::Csv => ("text/csv; charset=utf-8", "csv"),
+                ExchangeFormat::Json => ("application/json", "json"),
+            };
+            Response::builder()
+                .status(StatusCode::OK)
+                .header("content-type", content_type)
+                .header(
+                    "content-disposition",
+                    format!("attachment; filename=\"issues-{}.{}\"", project_id, extension),
+                )
+                .body(Body::from(body))
+                .unwrap()
+        },
+        Err(_) => Response::builder()
+            .status(StatusCode::INTERNAL_SERVER_ERROR)
+            .body(Body::from("Internal server error"))
+            .unwrap(),
+    }
+}
+
+async fn import_project_issues(
+    caller: CurrentUser,
+    State(state): State<IssueState>,
+    axum::extract::Path(project_id): axum::extract::Path<uuid::Uuid>,
+    axum::extract::Query(query): axum::extract::Query<ImportQuery>,
+    body: String,
+) -> axum::response::Response {
+    use axum::body::Body;
+    use axum::http::Response;
+    let project = match state.authorize(&caller, project_id, Action::ImportIssues) {
+        Ok(project) => project,
+        Err(rejection) => return rejection.into_response(),
+    };
+    let actor_id = Some(caller.id());
+    let result = import_issues(state.repo.as_ref(), &state.history, actor_id, &project, &body, query.format, query.dry_run);
+    match result {
+        Ok(report) => {
+            state.stats.invalidate(project_id);
+            if report.is_ok() && !report.dry_run {
+                for issue in state.repo.list_by_project(project_id).unwrap_or_default() {
+                    state.index.upsert(SearchDocument::from(&issue));
+                }
+            }
+            for (issue, imported_as) in &report.written {
+                let reason = match imported_as {
+                    ImportedAs::Created => SubscriptionReason::Author,
+                    ImportedAs::Updated => SubscriptionReason::Editor,
+                };
+                auto_subscribe(&state.subscriptions, Some(&project), caller.id(), issue.id, reason);
+                if let Some(assignee_id) = issue.assignee_id {
+                    auto_subscribe(&state.subscriptions, Some(&project), assignee_id, issue.id, SubscriptionReason::Assignee);
+                }
+            }
+            // Un import refusé renvoie quand même le rapport, pour corriger les lignes en une fois
+            let status = if report.is_ok() { StatusCode::OK } else { StatusCode::UNPROCESSABLE_ENTITY };
+            Response::builder()
+                .status(status)
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_string(&report).unwrap()))
+                .unwrap()
+        },
+        Err(e) => Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from(e))
+            .unwrap(),
+    }
+}
+
+async fn get_issue_history(
+    auth: Option<AuthBearer>,
+    State(state): State<IssueState>,
+    axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
+) -> axum::response::Response {
+    use axum::body::Body;
+    use axum::http::Response;
+    match state.visible_issue(id, auth.map(|a| a.user_id)) {
+        Ok(_) => {
+            let body = serde_json::to_string(&state.history.list(id)).unwrap();
+            Response::builder()
+                .status(StatusCode::OK)
+                .header("content-type", "application/json")
+                .body(Body::from(body))
+                .unwrap()
+        },
+        Err(rejection) => rejection.into_response(),
+    }
+}
+
+async fn revert_issue_by_id(
+    caller: CurrentUser,
+    State(state): State<IssueState>,
+    axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
+    Json(input): Json<RevertIssueInput>,
+) -> axum::response::Response {
+    use axum::body::Body;
+    use axum::http::Response;
+    let project = match state.authorize_issue(&caller, id, Action::EditIssue) {
+        Ok((_, project)) => project,
+        Err(rejection) => return rejection.into_response(),
+    };
+    let actor_id = Some(caller.id());
+    match revert_issue(state.repo.as_ref(), &state.history, id, input.revision, actor_id) {
+        Ok(issue) => {
+            state.stats.invalidate(issue.project_id);
+            state.index.upsert(SearchDocument::from(&issue));
+            auto_subscribe(&state.subscriptions, Some(&project), caller.id(), issue.id, SubscriptionReason::Editor);
+            if let Some(assignee_id) = issue.assignee_id {
+                auto_subscribe(&state.subscriptions, Some(&project), assignee_id, issue.id, SubscriptionReason::Assignee);
+            }
+            Response::builder()
+                .status(StatusCode::OK)
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_string(&issue).unwrap()))
+                .unwrap()
+        },
+        Err(RevertError::IssueNotFound) => Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Body::from("Issue not found"))
+            .unwrap(),
+        Err(RevertError::RevisionNotFound) => Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Body::from("Revision not found"))
+            .unwrap(),
+        Err(RevertError::Storage(_)) => Response::builder()
+            .status(StatusCode::INTERNAL_SERVER_ERROR)
+            .body(Body::from("Internal server error"))
+            .unwrap(),
+    }
+}
+
+async fn create_comment(
+    caller: CurrentUser,
+    State(state): State<IssueState>,
+    axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
+    Json(input): Json<CreateCommentInput>,
+) -> axum::response::Response {
+    use axum::body::Body;
+    use axum::http::Response;
+    let (issue, project) = match state.authorize_issue(&caller, id, Action::Comment) {
+        Ok(found) => found,
+        Err(rejection) => return rejection.into_response(),
+    };
+    if input.body.trim().is_empty() {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from("Comment body must not be empty"))
+            .unwrap();
+    }
+    let comment = Comment {
+        id: uuid::Uuid::new_v4(),
+        issue_id: issue.id,
+        author_id: caller.id(),
+        body: input.body,
+        created_at: Utc::now(),
+    };
+    state.comments.save(comment.clone());
+    state.stats.invalidate(issue.project_id);
+    auto_subscribe(&state.subscriptions, Some(&project), caller.id(), issue.id, SubscriptionReason::Commenter);
+    state.process_mentions(&issue, Some(comment.id), &comment.body, Some(caller.id()));
+    let message = format!("New comment on issue \"{}\"", issue.title);
+    state.notify_watchers(&issue, Some(caller.id()), NotificationKind::IssueCommented, &message);
+    Response::builder()
+        .status(StatusCode::CREATED)
+        .header("content-type", "application/json")
+        .body(Body::from(serde_json::to_string(&comment).unwrap()))
+        .unwrap()
+}
+
+async fn list_comments(
+    auth: Option<AuthBearer>,
+    State(state): State<IssueState>,
+    axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
+) -> axum::response::Response {
+    use axum::body::Body;
+    use axum::http::Response;
+    if let Err(rejection) = state.visible_issue(id, auth.map(|a| a.user_id)) {
+        return rejection.into_response();
+    }
+    let body = serde_json::to_string(&state.comments.list_by_issue(id)).unwrap();
+    Response::builder()
+        .status(StatusCode::OK)
+        .header("content-type", "application/json")
+        .body(Body::from(body))
+        .unwrap()
+}
+
+async fn list_mentions(
+    auth: Option<AuthBearer>,
+    State(state): State<IssueState>,
+    axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
+) -> axum::response::Response {
+    use axum::body::Body;
+    use axum::http::Response;
+    if let Err(rejection) = state.visible_issue(id, auth.map(|a| a.user_id)) {
+        return rejection.into_response();
+    }
+    let body = serde_json::to_string(&state.mentions.list_by_issue(id)).unwrap();
+    Response::builder()
+        .status(StatusCode::OK)
+        .header("content-type", "application/json")
+        .body(Body::from(body))
+        .unwrap()
+}
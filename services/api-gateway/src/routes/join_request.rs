@@ -0,0 +1,168 @@
+use axum::response::IntoResponse;
+use axum::{extract::State, http::StatusCode, routing::{get, post}, Json, Router};
+use serde::Deserialize;
+use std::sync::Arc;
+use openstudio_core::models::join_request::JoinRequestStatus;
+use openstudio_core::models::project::Project;
+use openstudio_core::models::user::ProjectRole;
+use openstudio_core::repositories::in_memory_join_request::InMemoryJoinRequestRepo;
+use openstudio_core::repositories::in_memory_notification::InMemoryNotificationRepo;
+use openstudio_core::repositories::in_memory_stats::InMemoryStatsCache;
+use openstudio_core::repositories::in_memory_subscription::InMemorySubscriptionRepo;
+use openstudio_core::repositories::in_memory_user::InMemoryUserRepo;
+use openstudio_core::repositories::project_repository::ProjectRepository;
+use openstudio_core::usecases::access::ProjectAccess;
+use openstudio_core::usecases::join_request::{JoinRequestContext, JoinRequestError};
+use openstudio_core::usecases::member::MemberError;
+use openstudio_core::usecases::subscription::watch_as_member;
+use uuid;
+use crate::routes::project::AuthBearer;
+use crate::routes::common::json_response;
+
+#[derive(Clone)]
+pub struct JoinRequestState {
+    pub repo: Arc<InMemoryJoinRequestRepo>,
+    pub users: Arc<InMemoryUserRepo>,
+    pub projects: Arc<dyn ProjectRepository + Send + Sync + 'static>,
+    pub notifications: Arc<InMemoryNotificationRepo>,
+    pub subscriptions: Arc<InMemorySubscriptionRepo>,
+    pub stats: Arc<InMemoryStatsCache>,
+    pub access: ProjectAccess,
+}
+
+#[derive(Deserialize, Default)]
+pub struct SubmitJoinRequestInput {
+    #[serde(default)]
+    pub message: String,
+}
+
+#[derive(Deserialize)]
+pub struct ApproveJoinRequestInput {
+    pub role: ProjectRole,
+}
+
+#[derive(Deserialize, Default)]
+pub struct JoinRequestQuery {
+    pub status: Option<JoinRequestStatus>,
+}
+
+impl JoinRequestState {
+    fn context(&self) -> JoinRequestContext<'_> {
+        JoinRequestContext {
+            requests: &self.repo,
+            users: &self.users,
+            notifications: &self.notifications,
+            access: &self.access,
+        }
+    }
+
+    /// Projet visible par l'appelant ; les membres d'un projet archivé sont figés.
+    fn project(&self, id: uuid::Uuid, caller: uuid::Uuid, writable: bool) -> Result<Project, (StatusCode, &'static str)> {
+        match self.projects.get_by_id(id) {
+            Ok(Some(project)) if !self.access.can_view(&project, Some(caller)) => Err((StatusCode::NOT_FOUND, "Project not found")),
+            Ok(Some(project)) if writable && project.is_read_only() => Err((StatusCode::FORBIDDEN, "Project is archived")),
+            Ok(Some(project)) => Ok(project),
+            Ok(None) => Err((StatusCode::NOT_FOUND, "Project not found")),
+            Err(_) => Err((StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")),
+        }
+    }
+}
+
+pub fn join_request_routes() -> Router<JoinRequestState> {
+    Router::new()
+        .route("/projects/{id}/join-requests", post(submit_join_request).get(list_join_requests))
+        .route("/projects/{id}/join-requests/{request_id}/approve", post(approve_join_request))
+        .route("/projects/{id}/join-requests/{request_id}/reject", post(reject_join_request))
+        .route("/join-requests", get(list_my_join_requests))
+}
+
+fn join_request_error(error: JoinRequestError) -> axum::response::Response {
+    let (status, message) = match error {
+        JoinRequestError::NotFound => (StatusCode::NOT_FOUND, "Join request not found"),
+        JoinRequestError::NotPublic => (StatusCode::FORBIDDEN, "Only public projects accept join requests"),
+        JoinRequestError::MessageTooLong => (StatusCode::BAD_REQUEST, "The message is too long"),
+        JoinRequestError::AlreadyMember => (StatusCode::CONFLICT, "The user is already a member"),
+        JoinRequestError::AlreadyRequested => (StatusCode::CONFLICT, "A join request is already pending"),
+        JoinRequestError::Forbidden => (StatusCode::FORBIDDEN, "Forbidden"),
+        JoinRequestError::AlreadyDecided => (StatusCode::CONFLICT, "The join request was already decided"),
+        JoinRequestError::Member(MemberError::Storage(_)) => (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error"),
+        JoinRequestError::Member(_) => (StatusCode::CONFLICT, "The membership cannot be changed"),
+    };
+    (status, message).into_response()
+}
+
+async fn submit_join_request(
+    auth: AuthBearer,
+    State(state): State<JoinRequestState>,
+    axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
+    input: Option<Json<SubmitJoinRequestInput>>,
+) -> axum::response::Response {
+    let project = match state.project(id, auth.user_id, true) {
+        Ok(project) => project,
+        Err(rejection) => return rejection.into_response(),
+    };
+    let input = input.map(|Json(input)| input).unwrap_or_default();
+    match state.context().submit(&project, auth.user_id, &input.message) {
+        Ok(request) => json_response(StatusCode::CREATED, &request),
+        Err(e) => join_request_error(e),
+    }
+}
+
+async fn list_join_requests(
+    auth: AuthBearer,
+    State(state): State<JoinRequestState>,
+    axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
+    axum::extract::Query(query): axum::extract::Query<JoinRequestQuery>,
+) -> axum::response::Response {
+    let project = match state.project(id, auth.user_id, false) {
+        Ok(project) => project,
+        Err(rejection) => return rejection.into_response(),
+    };
+    match state.context().list(&project, auth.user_id, query.status) {
+        Ok(requests) => json_response(StatusCode::OK, &requests),
+        Err(e) => join_request_error(e),
+    }
+}
+
+async fn approve_join_request(
+    auth: AuthBearer,
+    State(state): State<JoinRequestState>,
+    axum::extract::Path((id, request_id)): axum::extract::Path<(uuid::Uuid, uuid::Uuid)>,
+    Json(input): Json<ApproveJoinRequestInput>,
+) -> axum::response::Response {
+    let project = match state.project(id, auth.user_id, true) {
+        Ok(project) => project,
+        Err(rejection) => return rejection.into_response(),
+    };
+    match state.context().approve(&project, auth.user_id, request_id, input.role) {
+        Ok(member) => {
+            state.stats.invalidate(project.id);
+            watch_as_member(&state.subscriptions, &project, member.user_id);
+            json_response(StatusCode::OK, &member)
+        },
+        Err(e) => join_request_error(e),
+    }
+}
+
+async fn reject_join_request(
+    auth: AuthBearer,
+    State(state): State<JoinRequestState>,
+    axum::extract::Path((id, request_id)): axum::extract::Path<(uuid::Uuid, uuid::Uuid)>,
+) -> axum::response::Response {
+    let project = match state.project(id, auth.user_id, true) {
+        Ok(project) => project,
+        Err(rejection) => return rejection.into_response(),
+    };
+    match state.context().reject(&project, auth.user_id, request_id) {
+        Ok(request) => json_response(StatusCode::OK, &request),
+        Err(e) => join_request_error(e),
+    }
+}
+
+/// Demandes envoyées par l'appelant, pour en suivre l'issue.
+async fn list_my_join_requests(
+    auth: AuthBearer,
+    State(state): State<JoinRequestState>,
+) -> axum::response::Response {
+    json_response(StatusCode::OK, &state.repo.list_by_user(auth.user_id))
+}
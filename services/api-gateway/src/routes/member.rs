@@ -1,10 +1,19 @@
+use axum::response::IntoResponse;
 use axum::{extract::State, http::StatusCode, routing::{get, post, delete}, Json, Router};
 use serde::Deserialize;
 use std::sync::Arc;
-use openstudio_core::models::user::{ProjectMember, ProjectRole};
+use openstudio_core::models::project::Project;
+use openstudio_core::models::user::ProjectRole;
 use uuid;
-use chrono::Utc;
+use openstudio_core::repositories::in_memory_stats::InMemoryStatsCache;
+use openstudio_core::repositories::in_memory_subscription::InMemorySubscriptionRepo;
 use openstudio_core::repositories::in_memory_user::InMemoryUserRepo;
+use openstudio_core::repositories::project_repository::ProjectRepository;
+use openstudio_core::usecases::access::ProjectAccess;
+use openstudio_core::usecases::policy::Action;
+use openstudio_core::usecases::member::{self, MemberError};
+use openstudio_core::usecases::subscription::{unwatch_as_member, watch_as_member};
+use crate::routes::project::{AuthBearer, CurrentUser};
 
 #[derive(Deserialize)]
 pub struct AddMemberInput {
@@ -16,6 +25,10 @@ pub struct AddMemberInput {
 #[derive(Clone)]
 pub struct MemberState {
     pub repo: Arc<InMemoryUserRepo>,
+    pub projects: Arc<dyn ProjectRepository + Send + Sync + 'static>,
+    pub stats: Arc<InMemoryStatsCache>,
+    pub subscriptions: Arc<InMemorySubscriptionRepo>,
+    pub access: ProjectAccess,
 }
 
 pub fn member_routes() -> Router<MemberState> {
@@ -25,26 +38,61 @@ pub fn member_routes() -> Router<MemberState> {
         .route("/members", delete(remove_member))
 }
 
+/// Les membres d'un projet archivé sont figés.
+fn writable_project(state: &MemberState, project_id: uuid::Uuid) -> Result<Project, (StatusCode, &'static str)> {
+    match state.projects.get_by_id(project_id) {
+        Ok(Some(project)) if project.is_read_only() => Err((StatusCode::FORBIDDEN, "Project is archived")),
+        Ok(Some(project)) => Ok(project),
+        Ok(None) => Err((StatusCode::NOT_FOUND, "Project not found")),
+        Err(_) => Err((StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")),
+    }
+}
+
+fn member_error(error: MemberError) -> axum::response::Response {
+    let (status, message) = match error {
+        MemberError::LastOwner => (StatusCode::CONFLICT, "The project must keep at least one owner"),
+        MemberError::NamespaceOwner => (StatusCode::CONFLICT, "The namespace owner must transfer the project first"),
+        MemberError::Forbidden => (StatusCode::FORBIDDEN, "Forbidden"),
+        MemberError::Storage(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error"),
+    };
+    (status, message).into_response()
+}
+
+/// Ajout direct réservé aux owners et maintainers, qui restent libres de composer leur
+/// équipe sans attendre de réponse ; pour recueillir l'accord de la personne, passer par une
+/// invitation. Le compte ajouté doit exister.
 async fn add_member(
+    caller: CurrentUser,
     State(state): State<MemberState>,
     Json(input): Json<AddMemberInput>,
 ) -> axum::response::Response {
     use axum::body::Body;
     use axum::http::Response;
-    let member = ProjectMember {
-        user_id: input.user_id,
-        project_id: input.project_id,
-        role: input.role,
-        joined_at: Utc::now(),
+    let project = match writable_project(&state, input.project_id) {
+        Ok(project) => project,
+        Err(rejection) => return rejection.into_response(),
     };
-    state.repo.add_member(member);
-    Response::builder()
-        .status(StatusCode::CREATED)
-        .body(Body::from("Member added"))
-        .unwrap()
+    if let Err(rejection) = caller.authorize_grant(&project, input.role) {
+        return rejection.into_response();
+    }
+    if state.repo.get_user(input.user_id).is_none() {
+        return (StatusCode::NOT_FOUND, "User not found").into_response();
+    }
+    match member::add_member(&state.repo, &project, input.user_id, input.role) {
+        Ok(_) => {
+            state.stats.invalidate(project.id);
+            watch_as_member(&state.subscriptions, &project, input.user_id);
+            Response::builder()
+                .status(StatusCode::CREATED)
+                .body(Body::from("Member added"))
+                .unwrap()
+        },
+        Err(e) => member_error(e),
+    }
 }
 
 async fn list_members(
+    auth: Option<AuthBearer>,
     State(state): State<MemberState>,
     axum::extract::Query(params): axum::extract::Query<std::collections::HashMap<String, String>>,
 ) -> axum::response::Response {
@@ -67,6 +115,22 @@ async fn list_members(
                 .unwrap();
         }
     };
+    // Les membres d'un projet privé ne sont listés qu'à ses membres
+    match state.projects.get_by_id(project_id) {
+        Ok(Some(project)) if !state.access.can_view(&project, auth.map(|a| a.user_id)) => {
+            return Response::builder()
+                .status(StatusCode::NOT_FOUND)
+                .body(Body::from("Project not found"))
+                .unwrap();
+        },
+        Ok(_) => {},
+        Err(_) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from("Internal server error"))
+                .unwrap();
+        }
+    }
     let members = state.repo.list_members(project_id);
     let body = serde_json::to_string(&members).unwrap();
     Response::builder()
@@ -83,14 +147,41 @@ struct RemoveMemberInput {
 }
 
 async fn remove_member(
+    caller: CurrentUser,
     State(state): State<MemberState>,
     Json(input): Json<RemoveMemberInput>,
 ) -> axum::response::Response {
     use axum::body::Body;
     use axum::http::Response;
-    state.repo.remove_member(input.project_id, input.user_id);
-    Response::builder()
-        .status(StatusCode::OK)
-        .body(Body::from("Member removed"))
-        .unwrap()
+    let project = match writable_project(&state, input.project_id) {
+        Ok(project) => project,
+        Err(rejection) => return rejection.into_response(),
+    };
+    // Un membre peut toujours quitter le projet de lui-même
+    if caller.id() != input.user_id {
+        let target = state
+            .repo
+            .list_members(project.id)
+            .into_iter()
+            .find(|m| m.user_id == input.user_id)
+            .map(|m| m.role);
+        let check = match target {
+            Some(role) => caller.authorize_grant(&project, role),
+            None => caller.authorize(&project, Action::ManageMembers),
+        };
+        if let Err(rejection) = check {
+            return rejection.into_response();
+        }
+    }
+    match member::remove_member(state.projects.as_ref(), &state.repo, &project, input.user_id) {
+        Ok(()) => {
+            state.stats.invalidate(project.id);
+            unwatch_as_member(&state.subscriptions, project.id, input.user_id);
+            Response::builder()
+                .status(StatusCode::OK)
+                .body(Body::from("Member removed"))
+                .unwrap()
+        },
+        Err(e) => member_error(e),
+    }
 }
@@ -1,5 +1,19 @@
+pub mod access_token;
 pub mod auth;
+pub mod common;
+pub mod explore;
+pub mod invitation;
+pub mod join_request;
 pub mod member;
+pub mod notification;
+pub mod oauth;
+pub mod organization;
 pub mod project;
+pub mod search;
+pub mod social;
+pub mod stats;
 pub mod issue;
+pub mod subscription;
+pub mod time;
 pub mod user;
+pub mod wiki;
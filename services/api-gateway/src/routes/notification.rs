@@ -0,0 +1,51 @@
+use axum::{extract::State, http::StatusCode, routing::{get, post}, Router};
+use std::sync::Arc;
+use openstudio_core::repositories::in_memory_notification::InMemoryNotificationRepo;
+use uuid;
+use crate::routes::project::AuthBearer;
+
+#[derive(Clone)]
+pub struct NotificationState {
+    pub repo: Arc<InMemoryNotificationRepo>,
+}
+
+pub fn notification_routes() -> Router<NotificationState> {
+    Router::new()
+        .route("/notifications", get(list_notifications))
+        .route("/notifications/{id}/read", post(mark_notification_read))
+}
+
+async fn list_notifications(
+    auth: AuthBearer,
+    State(state): State<NotificationState>,
+) -> axum::response::Response {
+    use axum::body::Body;
+    use axum::http::Response;
+    let mut notifications = state.repo.list_for_user(auth.user_id);
+    notifications.sort_by_key(|n| std::cmp::Reverse(n.created_at));
+    Response::builder()
+        .status(StatusCode::OK)
+        .header("content-type", "application/json")
+        .body(Body::from(serde_json::to_string(&notifications).unwrap()))
+        .unwrap()
+}
+
+async fn mark_notification_read(
+    auth: AuthBearer,
+    State(state): State<NotificationState>,
+    axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
+) -> axum::response::Response {
+    use axum::body::Body;
+    use axum::http::Response;
+    if state.repo.mark_read(auth.user_id, id) {
+        Response::builder()
+            .status(StatusCode::OK)
+            .body(Body::from("Notification marked as read"))
+            .unwrap()
+    } else {
+        Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Body::from("Notification not found"))
+            .unwrap()
+    }
+}
@@ -0,0 +1,152 @@
+use axum::response::IntoResponse;
+use axum::{extract::{Path, Query, State}, http::StatusCode, routing::get, Json, Router};
+use chrono::{DateTime, Duration, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use openstudio_core::repositories::in_memory_identity::InMemoryIdentityRepo;
+use openstudio_core::usecases::identity::{IdentityContext, IdentityError};
+use crate::oauth::{pkce_pair, OAuthProviders};
+use crate::routes::auth::AuthState;
+
+/// Durée de validité d'une connexion entamée chez le fournisseur.
+const LOGIN_TTL_MINUTES: i64 = 10;
+/// Nombre de connexions en attente gardées en mémoire. `/authorize` est public : au-delà, les
+/// plus anciennes sont oubliées plutôt que de laisser la table grossir sans fin.
+const MAX_PENDING_LOGINS: usize = 10_000;
+
+/// Connexion en attente du retour du fournisseur, indexée par son paramètre `state`.
+struct PendingLogin {
+    provider: String,
+    code_verifier: String,
+    created_at: DateTime<Utc>,
+}
+
+#[derive(Clone)]
+pub struct OAuthState {
+    pub providers: Arc<OAuthProviders>,
+    pub identities: Arc<InMemoryIdentityRepo>,
+    pub auth: AuthState,
+    pending: Arc<Mutex<HashMap<String, PendingLogin>>>,
+}
+
+impl OAuthState {
+    pub fn new(providers: Arc<OAuthProviders>, identities: Arc<InMemoryIdentityRepo>, auth: AuthState) -> Self {
+        OAuthState { providers, identities, auth, pending: Arc::default() }
+    }
+
+    /// Consomme la connexion en attente : un `state` ne sert qu'une fois.
+    fn take_pending(&self, state: &str, provider: &str) -> Option<PendingLogin> {
+        let login = self.pending.lock().unwrap().remove(state)?;
+        let fresh = Utc::now() - login.created_at < Duration::minutes(LOGIN_TTL_MINUTES);
+        (fresh && login.provider == provider).then_some(login)
+    }
+}
+
+#[derive(Deserialize)]
+pub struct CallbackParams {
+    pub code: Option<String>,
+    pub state: Option<String>,
+    pub error: Option<String>,
+}
+
+pub fn oauth_routes() -> Router<OAuthState> {
+    Router::new()
+        .route("/auth/providers", get(list_providers))
+        .route("/auth/{provider}/authorize", get(authorize))
+        .route("/auth/{provider}/callback", get(callback))
+}
+
+async fn list_providers(State(state): State<OAuthState>) -> axum::response::Response {
+    let providers: Vec<serde_json::Value> = state
+        .providers
+        .list()
+        .into_iter()
+        .map(|p| serde_json::json!({ "name": p.name, "kind": p.kind, "authorize_url": format!("/auth/{}/authorize", p.name) }))
+        .collect();
+    Json(providers).into_response()
+}
+
+/// Redirige vers la page de connexion du fournisseur.
+async fn authorize(
+    State(state): State<OAuthState>,
+    Path(provider): Path<String>,
+) -> axum::response::Response {
+    let Some(provider) = state.providers.get(&provider) else {
+        return (StatusCode::NOT_FOUND, "Unknown identity provider").into_response();
+    };
+    let (code_verifier, code_challenge) = pkce_pair();
+    let login_state = format!("{}", uuid::Uuid::new_v4().simple());
+    let url = match state.providers.authorize_url(provider, &login_state, &code_challenge) {
+        Ok(url) => url,
+        Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error").into_response(),
+    };
+    {
+        let mut pending = state.pending.lock().unwrap();
+        let now = Utc::now();
+        pending.retain(|_, login| now - login.created_at < Duration::minutes(LOGIN_TTL_MINUTES));
+        if pending.len() >= MAX_PENDING_LOGINS
+            && let Some(oldest) = pending.iter().min_by_key(|(_, login)| login.created_at).map(|(key, _)| key.clone())
+        {
+            pending.remove(&oldest);
+        }
+        pending.insert(login_state, PendingLogin { provider: provider.name.clone(), code_verifier, created_at: now });
+    }
+    axum::response::Redirect::to(&url).into_response()
+}
+
+/// Retour du fournisseur : échange du code, lecture du profil, puis ouverture d'une session
+/// comme après `/login`.
+async fn callback(
+    State(state): State<OAuthState>,
+    Path(provider): Path<String>,
+    Query(params): Query<CallbackParams>,
+) -> axum::response::Response {
+    let Some(provider) = state.providers.get(&provider) else {
+        return (StatusCode::NOT_FOUND, "Unknown identity provider").into_response();
+    };
+    let login = match params.state.as_deref().and_then(|s| state.take_pending(s, &provider.name)) {
+        Some(login) => login,
+        None => return (StatusCode::BAD_REQUEST, "Invalid or expired login state").into_response(),
+    };
+    let code = match (params.error, params.code) {
+        (None, Some(code)) => code,
+        _ => return (StatusCode::UNAUTHORIZED, "The identity provider refused the login").into_response(),
+    };
+    let profile = match state.providers.exchange_code(provider, &code, &login.code_verifier).await {
+        Ok(access_token) => state.providers.profile(provider, &access_token).await,
+        Err(e) => Err(e),
+    };
+    let profile = match profile {
+        Ok(profile) => profile,
+        Err(e) => {
+            eprintln!("OAuth login with {} failed: {}", provider.name, e);
+            return (StatusCode::BAD_GATEWAY, "The identity provider login failed").into_response();
+        },
+    };
+    let ctx = IdentityContext { identities: &state.identities, users: &state.auth.repo, orgs: &state.auth.orgs };
+    match ctx.sign_in(&provider.name, &profile) {
+        Ok(sign_in) => {
+            let session = state.auth.sessions().start(sign_in.user.id);
+            let mut body = state.auth.token_pair(&sign_in.user, &session);
+            body["created"] = sign_in.created.into();
+            body["user"] = serde_json::json!({
+                "id": sign_in.user.id,
+                "username": sign_in.user.username,
+                "email": sign_in.user.email,
+                "email_verified": sign_in.user.email_verified,
+                "first_name": sign_in.user.first_name,
+                "last_name": sign_in.user.last_name
+            });
+            let status = if sign_in.created { StatusCode::CREATED } else { StatusCode::OK };
+            (status, Json(body)).into_response()
+        },
+        Err(IdentityError::MissingEmail) => (StatusCode::BAD_REQUEST, "The identity provider did not share an email address").into_response(),
+        Err(IdentityError::UnverifiedEmail) => {
+            (StatusCode::CONFLICT, "An account already uses this email; sign in with your password first").into_response()
+        },
+        Err(IdentityError::UnverifiedAccount) => {
+            (StatusCode::CONFLICT, "An account already uses this email but has not confirmed it yet").into_response()
+        },
+    }
+}
@@ -0,0 +1,338 @@
+use axum::response::IntoResponse;
+use axum::{extract::State, http::StatusCode, routing::{get, put}, Json, Router};
+use serde::Deserialize;
+use std::sync::Arc;
+use openstudio_core::models::organization::{OrgRole, Organization, Team};
+use openstudio_core::models::project::OwnerKind;
+use openstudio_core::models::user::ProjectRole;
+use openstudio_core::repositories::in_memory_org::InMemoryOrgRepo;
+use openstudio_core::repositories::in_memory_redirect::InMemoryProjectRedirectRepo;
+use openstudio_core::repositories::in_memory_user::InMemoryUserRepo;
+use openstudio_core::repositories::project_repository::ProjectRepository;
+use openstudio_core::usecases::access::ProjectAccess;
+use openstudio_core::usecases::organization::{OrgContext, OrgError};
+use uuid;
+use crate::routes::project::{AuthBearer, CurrentUser};
+use crate::routes::common::json_response;
+
+#[derive(Clone)]
+pub struct OrgState {
+    pub orgs: Arc<InMemoryOrgRepo>,
+    pub users: Arc<InMemoryUserRepo>,
+    pub projects: Arc<dyn ProjectRepository + Send + Sync + 'static>,
+    pub redirects: Arc<InMemoryProjectRedirectRepo>,
+    pub access: ProjectAccess,
+}
+
+#[derive(Deserialize)]
+pub struct CreateOrgInput {
+    pub slug: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+}
+
+#[derive(Deserialize)]
+pub struct SetOrgMemberInput {
+    pub role: OrgRole,
+}
+
+#[derive(Deserialize)]
+pub struct CreateTeamInput {
+    pub slug: String,
+    pub name: String,
+}
+
+#[derive(Deserialize)]
+pub struct CreateOrgProjectInput {
+    pub name: String,
+    pub description: String,
+}
+
+#[derive(Deserialize)]
+pub struct GrantTeamInput {
+    pub project_ids: Vec<uuid::Uuid>,
+    pub role: ProjectRole,
+}
+
+impl OrgState {
+    fn context(&self) -> OrgContext<'_> {
+        OrgContext { orgs: &self.orgs, users: &self.users }
+    }
+
+    fn load_org(&self, slug: &str) -> Result<Organization, (StatusCode, &'static str)> {
+        self.orgs.find_by_slug(slug).ok_or((StatusCode::NOT_FOUND, "Organization not found"))
+    }
+
+    /// Les équipes ne sont visibles que des membres de l'organisation.
+    fn load_team(&self, org: &Organization, slug: &str, caller: uuid::Uuid) -> Result<Team, (StatusCode, &'static str)> {
+        if self.orgs.member_role(org.id, caller).is_none() {
+            return Err((StatusCode::FORBIDDEN, "Only organization members can see its teams"));
+        }
+        self.orgs.find_team(org.id, slug).ok_or((StatusCode::NOT_FOUND, "Team not found"))
+    }
+}
+
+pub fn organization_routes() -> Router<OrgState> {
+    Router::new()
+        .route("/orgs", axum::routing::post(create_org))
+        .route("/orgs/{slug}", get(get_org))
+        .route("/orgs/{slug}/members", get(list_org_members))
+        .route("/orgs/{slug}/members/{user_id}", put(set_org_member).delete(remove_org_member))
+        .route("/orgs/{slug}/projects", get(list_org_projects).post(create_org_project))
+        .route("/orgs/{slug}/teams", get(list_teams).post(create_team))
+        .route("/orgs/{slug}/teams/{team}", get(get_team))
+        .route("/orgs/{slug}/teams/{team}/members/{user_id}", put(add_team_member).delete(remove_team_member))
+        .route("/orgs/{slug}/teams/{team}/projects", put(grant_team))
+        .route("/orgs/{slug}/teams/{team}/projects/{project_id}", axum::routing::delete(revoke_team))
+}
+
+fn org_error(error: OrgError) -> axum::response::Response {
+    let (status, message) = match error {
+        OrgError::NotFound => (StatusCode::NOT_FOUND, "Not found"),
+        OrgError::InvalidSlug => (StatusCode::BAD_REQUEST, "Slugs may only contain lowercase letters, digits and dashes"),
+        OrgError::SlugTaken => (StatusCode::CONFLICT, "This slug is already taken"),
+        OrgError::Forbidden => (StatusCode::FORBIDDEN, "Forbidden"),
+        OrgError::LastOwner => (StatusCode::CONFLICT, "The organization must keep at least one owner"),
+        OrgError::NotOrgMember => (StatusCode::CONFLICT, "The user must join the organization first"),
+        OrgError::ProjectNotInOrg => (StatusCode::BAD_REQUEST, "Teams can only be granted projects of their organization"),
+        OrgError::Storage(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error"),
+    };
+    (status, message).into_response()
+}
+
+async fn create_org(
+    caller: CurrentUser,
+    State(state): State<OrgState>,
+    Json(input): Json<CreateOrgInput>,
+) -> axum::response::Response {
+    if let Err(rejection) = caller.require_verified_email() {
+        return rejection.into_response();
+    }
+    match state.context().create_organization(caller.id(), &input.slug, &input.name, &input.description) {
+        Ok(org) => json_response(StatusCode::CREATED, &org),
+        Err(e) => org_error(e),
+    }
+}
+
+async fn get_org(
+    State(state): State<OrgState>,
+    axum::extract::Path(slug): axum::extract::Path<String>,
+) -> axum::response::Response {
+    match state.load_org(&slug) {
+        Ok(org) => json_response(StatusCode::OK, &org),
+        Err(rejection) => rejection.into_response(),
+    }
+}
+
+async fn list_org_members(
+    State(state): State<OrgState>,
+    axum::extract::Path(slug): axum::extract::Path<String>,
+) -> axum::response::Response {
+    match state.load_org(&slug) {
+        Ok(org) => json_response(StatusCode::OK, &state.orgs.list_members(org.id)),
+        Err(rejection) => rejection.into_response(),
+    }
+}
+
+async fn set_org_member(
+    auth: AuthBearer,
+    State(state): State<OrgState>,
+    axum::extract::Path((slug, user_id)): axum::extract::Path<(String, uuid::Uuid)>,
+    Json(input): Json<SetOrgMemberInput>,
+) -> axum::response::Response {
+    let org = match state.load_org(&slug) {
+        Ok(org) => org,
+        Err(rejection) => return rejection.into_response(),
+    };
+    match state.context().set_member(&org, auth.user_id, user_id, input.role) {
+        Ok(()) => (StatusCode::OK, "Member saved").into_response(),
+        Err(e) => org_error(e),
+    }
+}
+
+async fn remove_org_member(
+    auth: AuthBearer,
+    State(state): State<OrgState>,
+    axum::extract::Path((slug, user_id)): axum::extract::Path<(String, uuid::Uuid)>,
+) -> axum::response::Response {
+    let org = match state.load_org(&slug) {
+        Ok(org) => org,
+        Err(rejection) => return rejection.into_response(),
+    };
+    match state.context().remove_member(&org, auth.user_id, user_id) {
+        Ok(()) => (StatusCode::OK, "Member removed").into_response(),
+        Err(e) => org_error(e),
+    }
+}
+
+/// Projets de l'organisation que l'appelant peut voir listés.
+async fn list_org_projects(
+    auth: Option<AuthBearer>,
+    State(state): State<OrgState>,
+    axum::extract::Path(slug): axum::extract::Path<String>,
+) -> axum::response::Response {
+    let org = match state.load_org(&slug) {
+        Ok(org) => org,
+        Err(rejection) => return rejection.into_response(),
+    };
+    let caller = auth.map(|a| a.user_id);
+    match state.projects.list() {
+        Ok(mut projects) => {
+            projects.retain(|p| {
+                p.owner_kind == OwnerKind::Organization
+                    && p.owner_id == Some(org.id)
+                    && state.access.is_listed(p, caller)
+            });
+            json_response(StatusCode::OK, &projects)
+        },
+        Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error").into_response(),
+    }
+}
+
+async fn create_org_project(
+    caller: CurrentUser,
+    State(state): State<OrgState>,
+    axum::extract::Path(slug): axum::extract::Path<String>,
+    Json(input): Json<CreateOrgProjectInput>,
+) -> axum::response::Response {
+    if let Err(rejection) = caller.require_verified_email() {
+        return rejection.into_response();
+    }
+    let org = match state.load_org(&slug) {
+        Ok(org) => org,
+        Err(rejection) => return rejection.into_response(),
+    };
+    let created = state.context().create_project(
+        state.projects.as_ref(),
+        &state.redirects,
+        &org,
+        caller.id(),
+        &input.name,
+        &input.description,
+    );
+    match created {
+        Ok(project) => json_response(StatusCode::CREATED, &project),
+        Err(e) => org_error(e),
+    }
+}
+
+async fn list_teams(
+    auth: AuthBearer,
+    State(state): State<OrgState>,
+    axum::extract::Path(slug): axum::extract::Path<String>,
+) -> axum::response::Response {
+    let org = match state.load_org(&slug) {
+        Ok(org) => org,
+        Err(rejection) => return rejection.into_response(),
+    };
+    if state.orgs.member_role(org.id, auth.user_id).is_none() {
+        return (StatusCode::FORBIDDEN, "Only organization members can see its teams").into_response();
+    }
+    json_response(StatusCode::OK, &state.orgs.list_teams(org.id))
+}
+
+async fn create_team(
+    auth: AuthBearer,
+    State(state): State<OrgState>,
+    axum::extract::Path(slug): axum::extract::Path<String>,
+    Json(input): Json<CreateTeamInput>,
+) -> axum::response::Response {
+    let org = match state.load_org(&slug) {
+        Ok(org) => org,
+        Err(rejection) => return rejection.into_response(),
+    };
+    match state.context().create_team(&org, auth.user_id, &input.slug, &input.name) {
+        Ok(team) => json_response(StatusCode::CREATED, &team),
+        Err(e) => org_error(e),
+    }
+}
+
+/// L'équipe, avec les rôles qu'elle détient sur les projets.
+async fn get_team(
+    auth: AuthBearer,
+    State(state): State<OrgState>,
+    axum::extract::Path((slug, team)): axum::extract::Path<(String, String)>,
+) -> axum::response::Response {
+    let team = match state.load_org(&slug).and_then(|org| state.load_team(&org, &team, auth.user_id)) {
+        Ok(team) => team,
+        Err(rejection) => return rejection.into_response(),
+    };
+    let mut body = serde_json::to_value(&team).unwrap();
+    body["grants"] = serde_json::to_value(state.orgs.grants_for_team(team.id)).unwrap();
+    json_response(StatusCode::OK, &body)
+}
+
+async fn add_team_member(
+    auth: AuthBearer,
+    State(state): State<OrgState>,
+    axum::extract::Path((slug, team, user_id)): axum::extract::Path<(String, String, uuid::Uuid)>,
+) -> axum::response::Response {
+    let (org, team) = match state
+        .load_org(&slug)
+        .and_then(|org| state.load_team(&org, &team, auth.user_id).map(|team| (org, team)))
+    {
+        Ok(found) => found,
+        Err(rejection) => return rejection.into_response(),
+    };
+    match state.context().add_team_member(&org, auth.user_id, team, user_id) {
+        Ok(team) => json_response(StatusCode::OK, &team),
+        Err(e) => org_error(e),
+    }
+}
+
+async fn remove_team_member(
+    auth: AuthBearer,
+    State(state): State<OrgState>,
+    axum::extract::Path((slug, team, user_id)): axum::extract::Path<(String, String, uuid::Uuid)>,
+) -> axum::response::Response {
+    let (org, team) = match state
+        .load_org(&slug)
+        .and_then(|org| state.load_team(&org, &team, auth.user_id).map(|team| (org, team)))
+    {
+        Ok(found) => found,
+        Err(rejection) => return rejection.into_response(),
+    };
+    match state.context().remove_team_member(&org, auth.user_id, team, user_id) {
+        Ok(team) => json_response(StatusCode::OK, &team),
+        Err(e) => org_error(e),
+    }
+}
+
+/// Accorde un rôle à l'équipe sur plusieurs projets de l'organisation.
+async fn grant_team(
+    auth: AuthBearer,
+    State(state): State<OrgState>,
+    axum::extract::Path((slug, team)): axum::extract::Path<(String, String)>,
+    Json(input): Json<GrantTeamInput>,
+) -> axum::response::Response {
+    let (org, team) = match state
+        .load_org(&slug)
+        .and_then(|org| state.load_team(&org, &team, auth.user_id).map(|team| (org, team)))
+    {
+        Ok(found) => found,
+        Err(rejection) => return rejection.into_response(),
+    };
+    match state.context().grant_team(state.projects.as_ref(), &org, auth.user_id, &team, &input.project_ids, input.role) {
+        Ok(grants) => json_response(StatusCode::OK, &grants),
+        Err(e) => org_error(e),
+    }
+}
+
+async fn revoke_team(
+    auth: AuthBearer,
+    State(state): State<OrgState>,
+    axum::extract::Path((slug, team, project_id)): axum::extract::Path<(String, String, uuid::Uuid)>,
+) -> axum::response::Response {
+    let (org, team) = match state
+        .load_org(&slug)
+        .and_then(|org| state.load_team(&org, &team, auth.user_id).map(|team| (org, team)))
+    {
+        Ok(found) => found,
+        Err(rejection) => return rejection.into_response(),
+    };
+    match state.context().revoke_team(&org, auth.user_id, &team, project_id) {
+        Ok(()) => (StatusCode::OK, "Grant revoked").into_response(),
+        Err(e) => org_error(e),
+    }
+}
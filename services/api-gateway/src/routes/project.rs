@@ -7,19 +7,53 @@ pub fn project_routes() -> axum::Router<AppState> {
         .route("/projects/{id}", get(get_project_by_id))
         .route("/projects/{id}", axum::routing::delete(delete_project_by_id))
         .route("/projects/{id}", axum::routing::put(update_project_by_id))
+        .route("/projects/{id}/move", post(move_project_by_id))
+        .route("/projects/{id}/publish", post(publish_project_by_id))
+        .route("/projects/{id}/archive", post(archive_project_by_id))
+        .route("/projects/{id}/unarchive", post(unarchive_project_by_id))
+        .route("/projects/{id}/transfer", post(transfer_project_by_id))
+        .route("/projects/{id}/fork", post(fork_project_by_id))
+        .route("/projects/{id}/settings", get(get_project_settings).patch(update_project_settings))
+        .route("/projects/{owner}/{slug}", get(get_project_by_slug))
 }
 use axum::{
-    extract::{State, FromRequestParts},
+    extract::{FromRef, FromRequestParts, OptionalFromRequestParts, State},
     http::{StatusCode, request::Parts},
     Json,
 };
-use jsonwebtoken::{decode, DecodingKey, Validation};
-use serde::Deserialize;
+use crate::token::TokenService;
+use openstudio_core::repositories::in_memory_refresh_token::InMemoryRefreshTokenRepo;
+use openstudio_core::usecases::access_token::TOKEN_PREFIX;
+use openstudio_core::usecases::session::SessionContext;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
-use openstudio_core::usecases::project::create_project;
+use openstudio_core::usecases::project::{
+    archive_project, create_owned_project, find_project_by_slug, fork_project, move_project, move_project_to_org, normalize_topics,
+    publish_project, rename_project, unarchive_project, ForkOptions, LifecycleError, SlugLookup,
+};
+use openstudio_core::usecases::access::ProjectAccess;
+use openstudio_core::usecases::policy::Action;
+use openstudio_core::usecases::member::{transfer_ownership, MemberError};
+use openstudio_core::usecases::organization::OrgContext;
+use openstudio_core::usecases::subscription::watch_as_member;
+use openstudio_core::repositories::in_memory_invitation::InMemoryInvitationRepo;
+use openstudio_core::repositories::in_memory_join_request::InMemoryJoinRequestRepo;
+use openstudio_core::repositories::in_memory_org::InMemoryOrgRepo;
+use openstudio_core::repositories::in_memory_redirect::InMemoryProjectRedirectRepo;
+use openstudio_core::repositories::in_memory_social::InMemorySocialRepo;
+use openstudio_core::repositories::in_memory_search::{InMemorySearchIndex, SearchDocument};
+use openstudio_core::repositories::in_memory_stats::InMemoryStatsCache;
+use openstudio_core::repositories::in_memory_subscription::InMemorySubscriptionRepo;
+use openstudio_core::repositories::in_memory_user::InMemoryUserRepo;
+use openstudio_core::repositories::in_memory_wiki::InMemoryWikiRepo;
+use openstudio_core::repositories::issue_repository::IssueRepository;
 use openstudio_core::repositories::project_repository::ProjectRepository;
-use openstudio_core::models::project::Project;
+use openstudio_core::models::project::{OwnerKind, Project};
+use openstudio_core::models::planning::{IssueTemplate, Label, Milestone, WorkflowTransition};
+use openstudio_core::models::project_settings::{NotificationPolicy, ProjectSettings};
 use openstudio_core::models::project_status;
+use openstudio_core::models::issue::IssueStatus;
+use openstudio_core::models::user::{ProjectRole, User};
 use uuid;
 
 
@@ -27,24 +61,133 @@ use uuid;
 #[derive(Clone)]
 pub struct AppState {
     pub repo: Arc<dyn ProjectRepository + Send + Sync + 'static>,
+    pub users: Arc<InMemoryUserRepo>,
+    pub redirects: Arc<InMemoryProjectRedirectRepo>,
+    pub orgs: Arc<InMemoryOrgRepo>,
+    pub invitations: Arc<InMemoryInvitationRepo>,
+    pub join_requests: Arc<InMemoryJoinRequestRepo>,
+    pub issues: Arc<dyn IssueRepository + Send + Sync + 'static>,
+    pub stats: Arc<InMemoryStatsCache>,
+    pub index: Arc<InMemorySearchIndex>,
+    pub wiki: Arc<InMemoryWikiRepo>,
+    pub social: Arc<InMemorySocialRepo>,
+    pub subscriptions: Arc<InMemorySubscriptionRepo>,
+    pub access: ProjectAccess,
+}
+
+/// Projet tel qu'exposé par l'API, avec ses compteurs.
+#[derive(Serialize)]
+pub struct ProjectView<'a> {
+    #[serde(flatten)]
+    pub project: &'a Project,
+    pub stars_count: usize,
+}
+
+impl<'a> ProjectView<'a> {
+    pub fn new(project: &'a Project, social: &InMemorySocialRepo) -> Self {
+        Self { project, stars_count: social.star_count(project.id) }
+    }
 }
 
 #[derive(Deserialize)]
 pub struct UpdateProjectInput {
     pub name: Option<String>,
     pub description: Option<String>,
+    /// Refusé : le statut passe par `/publish`, `/archive` et `/unarchive`.
     pub status: Option<project_status::ProjectStatus>,
     pub visibility: Option<project_status::Visibility>,
+    pub topics: Option<Vec<String>>,
+    /// Une chaîne vide retire le langage.
+    pub language: Option<String>,
+    pub is_template: Option<bool>,
+    pub labels: Option<Vec<Label>>,
+    pub workflow: Option<Vec<WorkflowTransition>>,
+    pub issue_templates: Option<Vec<IssueTemplate>>,
+    pub milestones: Option<Vec<Milestone>>,
+}
+
+#[derive(Deserialize, Default)]
+pub struct ListProjectsQuery {
+    /// `true` pour ne lister que les modèles.
+    pub template: Option<bool>,
+}
+
+#[derive(Deserialize, Default)]
+pub struct ForkProjectInput {
+    pub name: Option<String>,
+    #[serde(default)]
+    pub include_open_issues: bool,
+}
+
+#[derive(Deserialize)]
+pub struct MoveProjectInput {
+    /// Nom d'utilisateur ou slug d'organisation du nouveau propriétaire.
+    pub owner: String,
+}
+
+#[derive(Deserialize)]
+pub struct TransferProjectInput {
+    /// Nom d'utilisateur du nouveau propriétaire.
+    pub owner: String,
 }
 
 #[derive(Deserialize)]
 pub struct CreateProjectInput {
     pub name: String,
     pub description: String,
+    /// Réglages initiaux ; le projet prend leur visibilité par défaut.
+    #[serde(default)]
+    pub settings: Option<ProjectSettings>,
+}
+
+#[derive(Deserialize)]
+pub struct UpdateSettingsInput {
+    pub default_visibility: Option<project_status::Visibility>,
+    pub non_members_can_open_issues: Option<bool>,
+    /// Absent : inchangé, `null` : aucun assigné par défaut.
+    #[serde(default, deserialize_with = "crate::routes::issue::deserialize_some")]
+    pub default_assignee_id: Option<Option<uuid::Uuid>>,
+    pub allowed_issue_states: Option<Vec<IssueStatus>>,
+    pub notification_policy: Option<NotificationPolicy>,
 }
 
 // --- AUTH EXTRACTOR ---
-pub struct AuthBearer;
+pub struct AuthBearer {
+    pub user_id: uuid::Uuid,
+    /// Session dont provient le jeton d'accès ; `None` pour un jeton personnel.
+    pub session_id: Option<uuid::Uuid>,
+    /// Projet auquel se limite un jeton personnel.
+    pub restricted_to: Option<uuid::Uuid>,
+}
+
+fn unauthorized() -> axum::response::Response {
+    axum::response::Response::builder()
+        .status(StatusCode::UNAUTHORIZED)
+        .body(axum::body::Body::from("Unauthorized"))
+        .unwrap()
+}
+
+/// Le `TokenService` et les sessions sont partagés par des couches `Extension` posées sur
+/// toute l'application. Un jeton d'accès meurt avec sa session ; un jeton personnel est
+/// vérifié par `access_token::authenticate`.
+fn decode_bearer(parts: &Parts, auth_str: &str) -> Result<AuthBearer, (StatusCode, &'static str)> {
+    let unauthorized = (StatusCode::UNAUTHORIZED, "Unauthorized");
+    let token = auth_str.strip_prefix("Bearer ").ok_or(unauthorized)?;
+    if token.starts_with(TOKEN_PREFIX) {
+        return crate::routes::access_token::authenticate(parts, token);
+    }
+    let claims = parts
+        .extensions
+        .get::<Arc<TokenService>>()
+        .and_then(|tokens| tokens.verify_access(token))
+        .ok_or(unauthorized)?;
+    match parts.extensions.get::<Arc<InMemoryRefreshTokenRepo>>() {
+        Some(sessions) if (SessionContext { tokens: sessions }).is_active(claims.sid) => {
+            Ok(AuthBearer { user_id: claims.sub, session_id: Some(claims.sid), restricted_to: None })
+        },
+        _ => Err(unauthorized),
+    }
+}
 
 impl<S> FromRequestParts<S> for AuthBearer
 where
@@ -52,36 +195,190 @@ where
 {
     type Rejection = axum::response::Response;
     async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
-        if let Some(auth_header) = parts.headers.get("authorization") {
-            if let Ok(auth_str) = auth_header.to_str() {
-                if let Some(token) = auth_str.strip_prefix("Bearer ") {
-                    let key = DecodingKey::from_secret(b"supersecretkey");
-                    if decode::<serde_json::Value>(token, &key, &Validation::default()).is_ok() {
-                        return Ok(AuthBearer);
-                    }
-                }
-            }
+        let header = parts.headers.get("authorization").and_then(|h| h.to_str().ok()).ok_or_else(unauthorized)?;
+        decode_bearer(parts, header).map_err(axum::response::IntoResponse::into_response)
+    }
+}
+
+/// `Option<AuthBearer>` : `None` sans en-tête Authorization, 401 si le jeton est invalide.
+impl<S> OptionalFromRequestParts<S> for AuthBearer
+where
+    S: Send + Sync,
+{
+    type Rejection = axum::response::Response;
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Option<Self>, Self::Rejection> {
+        match parts.headers.get("authorization") {
+            None => Ok(None),
+            Some(header) => {
+                let header = header.to_str().map_err(|_| unauthorized())?;
+                decode_bearer(parts, header).map(Some).map_err(axum::response::IntoResponse::into_response)
+            },
+        }
+    }
+}
+
+/// Appelant authentifié dont le compte existe encore. Ses rôles sont résolus projet par
+/// projet via `ProjectAccess`, selon `policy::minimum_role`.
+pub struct CurrentUser {
+    pub user: User,
+    access: ProjectAccess,
+    restricted_to: Option<uuid::Uuid>,
+}
+
+impl<S> FromRequestParts<S> for CurrentUser
+where
+    S: Send + Sync,
+    ProjectAccess: FromRef<S>,
+{
+    type Rejection = axum::response::Response;
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let auth = <AuthBearer as FromRequestParts<S>>::from_request_parts(parts, state).await?;
+        let access = ProjectAccess::from_ref(state);
+        match access.user(auth.user_id) {
+            Some(user) => Ok(CurrentUser { user, access, restricted_to: auth.restricted_to }),
+            None => Err(unauthorized()),
+        }
+    }
+}
+
+/// Message renvoyé quand le rôle de l'appelant ne suffit pas pour l'action.
+fn denial(action: Action) -> &'static str {
+    match action {
+        Action::ViewProject => "Project not found",
+        Action::OpenIssue => "Only project members can open issues",
+        Action::Comment => "You cannot comment on this project",
+        Action::EditIssue => "Only contributors can edit issues",
+        Action::LogTime => "Only contributors can log time",
+        Action::EditWiki => "Only project members can edit the wiki",
+        Action::DeleteIssue => "Only owners and maintainers can delete issues",
+        Action::ImportIssues => "Only owners and maintainers can import issues",
+        Action::EditProject => "Only owners and maintainers can edit the project",
+        Action::ManageSettings => "Only owners and maintainers can change the settings",
+        Action::ManageMembers => "Only owners and maintainers can manage members",
+        Action::ChangeStatus => "Only an owner can change the project status",
+        Action::TransferProject => "Only an owner can move or transfer the project",
+        Action::DeleteProject => "Only an owner can delete the project",
+        Action::GrantOwner => "Only an owner can grant the Owner role",
+    }
+}
+
+impl CurrentUser {
+    pub fn id(&self) -> uuid::Uuid {
+        self.user.id
+    }
+
+    pub fn can(&self, project: &Project, action: Action) -> bool {
+        self.access.can(project, Some(self.user.id), action)
+    }
+
+    /// 404 si le projet est invisible pour l'appelant, 403 si son rôle ne suffit pas.
+    pub fn authorize(&self, project: &Project, action: Action) -> Result<(), (StatusCode, &'static str)> {
+        if !self.can(project, Action::ViewProject) {
+            return Err((StatusCode::NOT_FOUND, "Project not found"));
+        }
+        if self.restricted_to.is_some_and(|id| id != project.id) {
+            return Err((StatusCode::FORBIDDEN, "The token is restricted to another project"));
+        }
+        if !self.can(project, action) {
+            return Err((StatusCode::FORBIDDEN, denial(action)));
+        }
+        Ok(())
+    }
+
+    /// Créer un projet ou une organisation demande une adresse email confirmée.
+    pub fn require_verified_email(&self) -> Result<(), (StatusCode, &'static str)> {
+        if !self.user.email_verified {
+            return Err((StatusCode::FORBIDDEN, "Verify your email address first"));
+        }
+        Ok(())
+    }
+
+    /// Ajout ou changement de rôle d'un membre.
+    pub fn authorize_grant(&self, project: &Project, role: ProjectRole) -> Result<(), (StatusCode, &'static str)> {
+        self.authorize(project, Action::ManageMembers)?;
+        if role == ProjectRole::Owner {
+            self.authorize(project, Action::GrantOwner)?;
+        }
+        Ok(())
+    }
+}
+
+impl AppState {
+    /// Namespace désigné par `name` : un utilisateur, sinon une organisation.
+    fn namespace_id(&self, name: &str) -> Option<uuid::Uuid> {
+        match self.users.find_by_username(name) {
+            Some(user) => Some(user.id),
+            None => self.orgs.find_by_slug(name).map(|org| org.id),
+        }
+    }
+
+    fn namespace_name(&self, project: &Project) -> Option<String> {
+        let owner_id = project.owner_id?;
+        match project.owner_kind {
+            OwnerKind::User => self.users.get_user(owner_id).map(|u| u.username),
+            OwnerKind::Organization => self.orgs.get(owner_id).map(|o| o.slug),
         }
-        Err(axum::response::Response::builder()
-            .status(StatusCode::UNAUTHORIZED)
-            .body(axum::body::Body::from("Unauthorized"))
-            .unwrap())
     }
+
+    /// Réponse JSON portant le projet sous sa forme publique, compteurs compris.
+    fn project_response(&self, status: StatusCode, project: &Project) -> axum::response::Response {
+        axum::response::Response::builder()
+            .status(status)
+            .header("content-type", "application/json")
+            .body(axum::body::Body::from(serde_json::to_string(&ProjectView::new(project, &self.social)).unwrap()))
+            .unwrap()
+    }
+}
+
+fn forbidden(message: &'static str) -> axum::response::Response {
+    axum::response::Response::builder()
+        .status(StatusCode::FORBIDDEN)
+        .body(axum::body::Body::from(message))
+        .unwrap()
 }
 
 // --- HANDLERS ---
 async fn delete_project_by_id(
-    _auth: AuthBearer,
+    caller: CurrentUser,
     State(state): State<AppState>,
     axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
 ) -> axum::response::Response {
     use axum::body::Body;
     use axum::http::Response;
+    let project = match state.repo.get_by_id(id) {
+        Ok(Some(p)) => p,
+        Ok(None) => {
+            return Response::builder()
+                .status(StatusCode::NOT_FOUND)
+                .body(Body::from("Project not found"))
+                .unwrap();
+        },
+        Err(_) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from("Internal server error"))
+                .unwrap();
+        }
+    };
+    if let Err((status, message)) = caller.authorize(&project, Action::DeleteProject) {
+        return Response::builder().status(status).body(Body::from(message)).unwrap();
+    }
     match state.repo.delete(id) {
-        Ok(true) => Response::builder()
-            .status(StatusCode::OK)
-            .body(Body::from("Project deleted"))
-            .unwrap(),
+        Ok(true) => {
+            state.redirects.delete_for_project(id);
+            state.users.delete_members_for_project(id);
+            state.stats.invalidate(id);
+            state.social.delete_stars_for_project(id);
+            state.orgs.delete_grants_for_project(id);
+            state.invitations.delete_for_project(id);
+            state.join_requests.delete_for_project(id);
+            state.wiki.delete_for_project(id);
+            state.index.remove_project(id);
+            Response::builder()
+                .status(StatusCode::OK)
+                .body(Body::from("Project deleted"))
+                .unwrap()
+        },
         Ok(false) => Response::builder()
             .status(StatusCode::NOT_FOUND)
             .body(Body::from("Project not found"))
@@ -94,7 +391,7 @@ async fn delete_project_by_id(
 }
 
 async fn update_project_by_id(
-    _auth: AuthBearer,
+    caller: CurrentUser,
     State(state): State<AppState>,
     axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
     Json(input): Json<UpdateProjectInput>,
@@ -116,20 +413,65 @@ async fn update_project_by_id(
                 .unwrap();
         }
     };
+    if let Err((status, message)) = caller.authorize(&existing, Action::EditProject) {
+        return Response::builder().status(status).body(Body::from(message)).unwrap();
+    }
+    if input.status.is_some() {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from("Use the publish, archive and unarchive endpoints to change the status"))
+            .unwrap();
+    }
+    if existing.is_read_only() {
+        return Response::builder()
+            .status(StatusCode::FORBIDDEN)
+            .body(Body::from("Project is archived"))
+            .unwrap();
+    }
+    let topics = match input.topics.as_deref().map(normalize_topics).transpose() {
+        Ok(topics) => topics.unwrap_or(existing.topics),
+        Err(e) => {
+            return Response::builder()
+                .status(StatusCode::BAD_REQUEST)
+                .body(Body::from(e))
+                .unwrap();
+        }
+    };
+    let language = match input.language {
+        Some(language) if language.trim().is_empty() => None,
+        Some(language) => Some(language.trim().to_string()),
+        None => existing.language,
+    };
+    let renamed = input.name.as_ref().is_some_and(|name| *name != existing.name);
     let updated = Project {
         id,
+        owner_id: existing.owner_id,
+        owner_kind: existing.owner_kind,
         name: input.name.unwrap_or(existing.name),
+        slug: existing.slug,
         description: input.description.unwrap_or(existing.description),
+        topics,
+        language,
+        is_template: input.is_template.unwrap_or(existing.is_template),
+        forked_from: existing.forked_from,
+        labels: input.labels.unwrap_or(existing.labels),
+        workflow: input.workflow.unwrap_or(existing.workflow),
+        issue_templates: input.issue_templates.unwrap_or(existing.issue_templates),
+        milestones: input.milestones.unwrap_or(existing.milestones),
+        settings: existing.settings,
         created_at: existing.created_at,
         visibility: input.visibility.unwrap_or(existing.visibility),
-        status: input.status.unwrap_or(existing.status),
+        status: existing.status,
     };
-    match state.repo.update(updated) {
-        Ok(true) => Response::builder()
-            .status(StatusCode::OK)
-            .body(Body::from("Project updated"))
-            .unwrap(),
-        Ok(false) => Response::builder()
+    let result = if renamed {
+        let name = updated.name.clone();
+        rename_project(state.repo.as_ref(), &state.redirects, updated, &name).map(Some)
+    } else {
+        state.repo.update(updated.clone()).map(|found| found.then_some(updated))
+    };
+    match result {
+        Ok(Some(project)) => state.project_response(StatusCode::OK, &project),
+        Ok(None) => Response::builder()
             .status(StatusCode::NOT_FOUND)
             .body(Body::from("Project not found"))
             .unwrap(),
@@ -141,14 +483,17 @@ async fn update_project_by_id(
 }
 
 async fn get_project_by_id(
+    auth: Option<AuthBearer>,
     State(state): State<AppState>,
     axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
 ) -> axum::response::Response {
     use axum::body::Body;
     use axum::http::Response;
-    match state.repo.get_by_id(id) {
+    let caller = auth.map(|a| a.user_id);
+    // Un projet privé est introuvable pour qui n'en est pas membre
+    match state.repo.get_by_id(id).map(|p| p.filter(|p| state.access.can_view(p, caller))) {
         Ok(Some(project)) => {
-            let body = serde_json::to_string(&project).unwrap();
+            let body = serde_json::to_string(&ProjectView::new(&project, &state.social)).unwrap();
             Response::builder()
                 .status(StatusCode::OK)
                 .header("content-type", "application/json")
@@ -171,13 +516,21 @@ async fn get_project_by_id(
 }
 
 async fn list_projects(
-    State(state): State<AppState>
+    auth: Option<AuthBearer>,
+    State(state): State<AppState>,
+    axum::extract::Query(query): axum::extract::Query<ListProjectsQuery>,
 ) -> axum::response::Response {
     use axum::body::Body;
     use axum::http::Response;
+    let caller = auth.map(|a| a.user_id);
     match state.repo.list() {
-        Ok(projects) => {
-            let body = serde_json::to_string(&projects).unwrap();
+        Ok(mut projects) => {
+            projects.retain(|p| state.access.is_listed(p, caller));
+            if let Some(template) = query.template {
+                projects.retain(|p| p.is_template == template);
+            }
+            let views: Vec<ProjectView> = projects.iter().map(|p| ProjectView::new(p, &state.social)).collect();
+            let body = serde_json::to_string(&views).unwrap();
             Response::builder()
                 .status(StatusCode::OK)
                 .header("content-type", "application/json")
@@ -195,21 +548,421 @@ async fn list_projects(
 }
 
 async fn handle_create_project(
-    _auth: AuthBearer,
+    caller: CurrentUser,
     State(state): State<AppState>,
     Json(payload): Json<CreateProjectInput>,
 ) -> axum::response::Response {
     use axum::body::Body;
     use axum::http::Response;
-    let project = create_project(&payload.name, &payload.description);
-    match state.repo.save(project) {
-        Ok(_) => Response::builder()
-            .status(StatusCode::CREATED)
-            .body(Body::from("Project created successfully"))
-            .unwrap(),
+    if let Err((status, message)) = caller.require_verified_email() {
+        return Response::builder().status(status).body(Body::from(message)).unwrap();
+    }
+    let settings = payload.settings.unwrap_or_default();
+    // Seul le créateur est membre à ce stade
+    let validation = settings.validate().and_then(|_| match settings.default_assignee_id {
+        Some(id) if id != caller.id() => Err("default_assignee_id must be a project member".to_string()),
+        _ => Ok(()),
+    });
+    if let Err(e) = validation {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from(e))
+            .unwrap();
+    }
+    let created = create_owned_project(
+        state.repo.as_ref(),
+        &state.redirects,
+        &state.users,
+        caller.id(),
+        &payload.name,
+        &payload.description,
+    )
+    .and_then(|mut project| {
+        project.visibility = settings.default_visibility.clone();
+        project.settings = settings;
+        state.repo.update(project.clone())?;
+        Ok(project)
+    });
+    match created {
+        Ok(project) => {
+            watch_as_member(&state.subscriptions, &project, caller.id());
+            state.project_response(StatusCode::CREATED, &project)
+        },
         Err(e) => Response::builder()
             .status(StatusCode::INTERNAL_SERVER_ERROR)
             .body(Body::from(e.to_string()))
             .unwrap(),
     }
 }
+
+async fn get_project_by_slug(
+    auth: Option<AuthBearer>,
+    State(state): State<AppState>,
+    axum::extract::Path((owner, slug)): axum::extract::Path<(String, String)>,
+) -> axum::response::Response {
+    use axum::body::Body;
+    use axum::http::Response;
+    let Some(owner_id) = state.namespace_id(&owner) else {
+        return Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Body::from("Project not found"))
+            .unwrap();
+    };
+    let caller = auth.map(|a| a.user_id);
+    let lookup = find_project_by_slug(state.repo.as_ref(), &state.redirects, owner_id, &slug).map(|found| {
+        found.filter(|lookup| match lookup {
+            SlugLookup::Found(p) | SlugLookup::Moved(p) => state.access.can_view(p, caller),
+        })
+    });
+    match lookup {
+        Ok(Some(SlugLookup::Found(project))) => {
+            let body = serde_json::to_string(&ProjectView::new(&project, &state.social)).unwrap();
+            Response::builder()
+                .status(StatusCode::OK)
+                .header("content-type", "application/json")
+                .body(Body::from(body))
+                .unwrap()
+        },
+        Ok(Some(SlugLookup::Moved(project))) => {
+            match state.namespace_name(&project) {
+                Some(namespace) => Response::builder()
+                    .status(StatusCode::MOVED_PERMANENTLY)
+                    .header("location", format!("/projects/{}/{}", namespace, project.slug))
+                    .body(Body::empty())
+                    .unwrap(),
+                None => Response::builder()
+                    .status(StatusCode::NOT_FOUND)
+                    .body(Body::from("Project not found"))
+                    .unwrap(),
+            }
+        },
+        Ok(None) => Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Body::from("Project not found"))
+            .unwrap(),
+        Err(_) => Response::builder()
+            .status(StatusCode::INTERNAL_SERVER_ERROR)
+            .body(Body::from("Internal server error"))
+            .unwrap(),
+    }
+}
+
+async fn move_project_by_id(
+    caller: CurrentUser,
+    State(state): State<AppState>,
+    axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
+    Json(input): Json<MoveProjectInput>,
+) -> axum::response::Response {
+    use axum::body::Body;
+    use axum::http::Response;
+    let project = match state.repo.get_by_id(id) {
+        Ok(Some(p)) => p,
+        Ok(None) => {
+            return Response::builder()
+                .status(StatusCode::NOT_FOUND)
+                .body(Body::from("Project not found"))
+                .unwrap();
+        },
+        Err(_) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from("Internal server error"))
+                .unwrap();
+        }
+    };
+    if let Err((status, message)) = caller.authorize(&project, Action::TransferProject) {
+        return Response::builder().status(status).body(Body::from(message)).unwrap();
+    }
+    let context = OrgContext { orgs: &state.orgs, users: &state.users };
+    if !context.can_release(&project, caller.id()) {
+        return forbidden("Only organization owners and admins can move projects out of it");
+    }
+    let moved = if let Some(new_owner) = state.users.find_by_username(&input.owner) {
+        // Le namespace d'accueil doit appartenir à un propriétaire du projet, sinon passer par `/transfer`
+        if state.access.role(&project, new_owner.id) != Some(ProjectRole::Owner) {
+            return Response::builder()
+                .status(StatusCode::CONFLICT)
+                .body(Body::from("The new namespace owner must already own the project"))
+                .unwrap();
+        }
+        move_project(state.repo.as_ref(), &state.redirects, project, new_owner.id)
+    } else if let Some(org) = state.orgs.find_by_slug(&input.owner) {
+        if !context.is_admin(org.id, caller.id()) {
+            return forbidden("Only organization owners and admins can move projects into it");
+        }
+        move_project_to_org(state.repo.as_ref(), &state.redirects, project, org.id)
+    } else {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from("Unknown owner"))
+            .unwrap();
+    };
+    match moved {
+        Ok(project) => state.project_response(StatusCode::OK, &project),
+        Err(_) => Response::builder()
+            .status(StatusCode::INTERNAL_SERVER_ERROR)
+            .body(Body::from("Internal server error"))
+            .unwrap(),
+    }
+}
+
+fn lifecycle_response(state: &AppState, result: Result<Project, LifecycleError>) -> axum::response::Response {
+    use axum::body::Body;
+    use axum::http::Response;
+    match result {
+        Ok(project) => state.project_response(StatusCode::OK, &project),
+        Err(LifecycleError::NotFound) => Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Body::from("Project not found"))
+            .unwrap(),
+        Err(LifecycleError::Forbidden) => Response::builder()
+            .status(StatusCode::FORBIDDEN)
+            .body(Body::from("Only an owner can change the project status"))
+            .unwrap(),
+        Err(LifecycleError::InvalidTransition { from, to }) => Response::builder()
+            .status(StatusCode::CONFLICT)
+            .body(Body::from(format!("Cannot change project status from {:?} to {:?}", from, to)))
+            .unwrap(),
+        Err(LifecycleError::Storage(_)) => Response::builder()
+            .status(StatusCode::INTERNAL_SERVER_ERROR)
+            .body(Body::from("Internal server error"))
+            .unwrap(),
+    }
+}
+
+async fn publish_project_by_id(
+    caller: CurrentUser,
+    State(state): State<AppState>,
+    axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
+) -> axum::response::Response {
+    lifecycle_response(&state, publish_project(state.repo.as_ref(), &state.access, id, caller.id()))
+}
+
+async fn archive_project_by_id(
+    caller: CurrentUser,
+    State(state): State<AppState>,
+    axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
+) -> axum::response::Response {
+    lifecycle_response(&state, archive_project(state.repo.as_ref(), &state.access, id, caller.id()))
+}
+
+async fn unarchive_project_by_id(
+    caller: CurrentUser,
+    State(state): State<AppState>,
+    axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
+) -> axum::response::Response {
+    lifecycle_response(&state, unarchive_project(state.repo.as_ref(), &state.access, id, caller.id()))
+}
+
+async fn transfer_project_by_id(
+    caller: CurrentUser,
+    State(state): State<AppState>,
+    axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
+    Json(input): Json<TransferProjectInput>,
+) -> axum::response::Response {
+    use axum::body::Body;
+    use axum::http::Response;
+    let project = match state.repo.get_by_id(id) {
+        Ok(Some(p)) if state.access.can_view(&p, Some(caller.id())) => p,
+        Ok(_) => {
+            return Response::builder()
+                .status(StatusCode::NOT_FOUND)
+                .body(Body::from("Project not found"))
+                .unwrap();
+        },
+        Err(_) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from("Internal server error"))
+                .unwrap();
+        }
+    };
+    if let Err((status, message)) = caller.authorize(&project, Action::TransferProject) {
+        return Response::builder().status(status).body(Body::from(message)).unwrap();
+    }
+    if project.is_read_only() {
+        return forbidden("Project is archived");
+    }
+    let Some(new_owner) = state.users.find_by_username(&input.owner) else {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from("Unknown owner"))
+            .unwrap();
+    };
+    let context = OrgContext { orgs: &state.orgs, users: &state.users };
+    if !context.can_release(&project, caller.id()) {
+        return forbidden("Only organization owners and admins can move projects out of it");
+    }
+    match transfer_ownership(state.repo.as_ref(), &state.redirects, &context, project, caller.id(), new_owner.id) {
+        Ok(project) => {
+            state.stats.invalidate(project.id);
+            state.project_response(StatusCode::OK, &project)
+        },
+        Err(MemberError::Forbidden) => forbidden("Only the namespace owner can transfer the project"),
+        Err(MemberError::LastOwner | MemberError::NamespaceOwner) => Response::builder()
+            .status(StatusCode::CONFLICT)
+            .body(Body::from("The project must keep an owner"))
+            .unwrap(),
+        Err(MemberError::Storage(_)) => Response::builder()
+            .status(StatusCode::INTERNAL_SERVER_ERROR)
+            .body(Body::from("Internal server error"))
+            .unwrap(),
+    }
+}
+
+async fn fork_project_by_id(
+    caller: CurrentUser,
+    State(state): State<AppState>,
+    axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
+    input: Option<Json<ForkProjectInput>>,
+) -> axum::response::Response {
+    use axum::body::Body;
+    use axum::http::Response;
+    if let Err((status, message)) = caller.require_verified_email() {
+        return Response::builder().status(status).body(Body::from(message)).unwrap();
+    }
+    let source = match state.repo.get_by_id(id) {
+        Ok(Some(p)) if state.access.can_view(&p, Some(caller.id())) => p,
+        Ok(_) => {
+            return Response::builder()
+                .status(StatusCode::NOT_FOUND)
+                .body(Body::from("Project not found"))
+                .unwrap();
+        },
+        Err(_) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from("Internal server error"))
+                .unwrap();
+        }
+    };
+    let input = input.map(|Json(i)| i).unwrap_or_default();
+    let options = ForkOptions { name: input.name, include_open_issues: input.include_open_issues };
+    match fork_project(
+        state.repo.as_ref(),
+        &state.redirects,
+        &state.users,
+        state.issues.as_ref(),
+        &source,
+        caller.id(),
+        &options,
+    ) {
+        Ok(fork) => {
+            watch_as_member(&state.subscriptions, &fork, caller.id());
+            for issue in state.issues.list_by_project(fork.id).unwrap_or_default() {
+                state.index.upsert(SearchDocument::from(&issue));
+            }
+            state.project_response(StatusCode::CREATED, &fork)
+        },
+        Err(_) => Response::builder()
+            .status(StatusCode::INTERNAL_SERVER_ERROR)
+            .body(Body::from("Internal server error"))
+            .unwrap(),
+    }
+}
+
+async fn get_project_settings(
+    auth: Option<AuthBearer>,
+    State(state): State<AppState>,
+    axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
+) -> axum::response::Response {
+    use axum::body::Body;
+    use axum::http::Response;
+    match state.repo.get_by_id(id).map(|p| p.filter(|p| state.access.can_view(p, auth.map(|a| a.user_id)))) {
+        Ok(Some(project)) => Response::builder()
+            .status(StatusCode::OK)
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::to_string(&project.settings).unwrap()))
+            .unwrap(),
+        Ok(None) => Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Body::from("Project not found"))
+            .unwrap(),
+        Err(_) => Response::builder()
+            .status(StatusCode::INTERNAL_SERVER_ERROR)
+            .body(Body::from("Internal server error"))
+            .unwrap(),
+    }
+}
+
+async fn update_project_settings(
+    caller: CurrentUser,
+    State(state): State<AppState>,
+    axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
+    Json(input): Json<UpdateSettingsInput>,
+) -> axum::response::Response {
+    use axum::body::Body;
+    use axum::http::Response;
+    let mut project = match state.repo.get_by_id(id) {
+        Ok(Some(p)) if state.access.can_view(&p, Some(caller.id())) => p,
+        Ok(_) => {
+            return Response::builder()
+                .status(StatusCode::NOT_FOUND)
+                .body(Body::from("Project not found"))
+                .unwrap();
+        },
+        Err(_) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from("Internal server error"))
+                .unwrap();
+        }
+    };
+    if let Err((status, message)) = caller.authorize(&project, Action::ManageSettings) {
+        return Response::builder().status(status).body(Body::from(message)).unwrap();
+    }
+    if project.is_read_only() {
+        return forbidden("Project is archived");
+    }
+    let settings = &mut project.settings;
+    if let Some(visibility) = input.default_visibility {
+        settings.default_visibility = visibility;
+    }
+    if let Some(allowed) = input.non_members_can_open_issues {
+        settings.non_members_can_open_issues = allowed;
+    }
+    if let Some(assignee_id) = input.default_assignee_id {
+        settings.default_assignee_id = assignee_id;
+    }
+    if let Some(states) = input.allowed_issue_states {
+        settings.allowed_issue_states = states;
+    }
+    if let Some(policy) = input.notification_policy {
+        settings.notification_policy = policy;
+    }
+    let assignee_is_member = settings
+        .default_assignee_id
+        .is_none_or(|id| state.access.role(&project, id).is_some());
+    let validation = if assignee_is_member {
+        project.settings.validate()
+    } else {
+        Err("default_assignee_id must be a project member".to_string())
+    };
+    if let Err(e) = validation {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from(e))
+            .unwrap();
+    }
+    match state.repo.update(project.clone()) {
+        Ok(true) => {
+            // Passer à `Watching` abonne les membres déjà présents
+            for member in state.users.list_members(id) {
+                watch_as_member(&state.subscriptions, &project, member.user_id);
+            }
+            Response::builder()
+                .status(StatusCode::OK)
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_string(&project.settings).unwrap()))
+                .unwrap()
+        },
+        Ok(false) => Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Body::from("Project not found"))
+            .unwrap(),
+        Err(_) => Response::builder()
+            .status(StatusCode::INTERNAL_SERVER_ERROR)
+            .body(Body::from("Internal server error"))
+            .unwrap(),
+    }
+}
@@ -0,0 +1,59 @@
+use axum::{extract::State, http::StatusCode, routing::get, Router};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use openstudio_core::repositories::in_memory_search::InMemorySearchIndex;
+use openstudio_core::repositories::project_repository::ProjectRepository;
+use openstudio_core::usecases::access::ProjectAccess;
+use uuid;
+use crate::routes::project::AuthBearer;
+
+#[derive(Deserialize)]
+pub struct SearchQuery {
+    pub q: String,
+    pub project_id: Option<uuid::Uuid>,
+}
+
+#[derive(Clone)]
+pub struct SearchState {
+    pub index: Arc<InMemorySearchIndex>,
+    pub projects: Arc<dyn ProjectRepository + Send + Sync + 'static>,
+    pub access: ProjectAccess,
+}
+
+pub fn search_routes() -> Router<SearchState> {
+    Router::new().route("/search", get(search))
+}
+
+/// Recherche dans les issues et les pages de wiki. La recherche globale ne couvre que les
+/// projets listés pour l'appelant ; un projet non listé n'est fouillé que si on le nomme
+/// par `project_id`.
+async fn search(
+    auth: Option<AuthBearer>,
+    State(state): State<SearchState>,
+    axum::extract::Query(query): axum::extract::Query<SearchQuery>,
+) -> axum::response::Response {
+    use axum::body::Body;
+    use axum::http::Response;
+    let caller = auth.map(|a| a.user_id);
+    let mut visible: HashMap<uuid::Uuid, bool> = HashMap::new();
+    let hits: Vec<_> = state
+        .index
+        .search(&query.q, query.project_id)
+        .into_iter()
+        .filter(|hit| {
+            *visible.entry(hit.project_id).or_insert_with(|| {
+                match state.projects.get_by_id(hit.project_id) {
+                    Ok(Some(project)) if query.project_id.is_some() => state.access.can_view(&project, caller),
+                    Ok(Some(project)) => state.access.is_listed(&project, caller),
+                    _ => false,
+                }
+            })
+        })
+        .collect();
+    Response::builder()
+        .status(StatusCode::OK)
+        .header("content-type", "application/json")
+        .body(Body::from(serde_json::to_string(&hits).unwrap()))
+        .unwrap()
+}
@@ -0,0 +1,186 @@
+use axum::{extract::State, http::StatusCode, routing::{get, post}, Router};
+use serde::Deserialize;
+use std::sync::Arc;
+use openstudio_core::repositories::in_memory_comment::InMemoryCommentRepo;
+use openstudio_core::repositories::in_memory_social::InMemorySocialRepo;
+use openstudio_core::repositories::in_memory_user::InMemoryUserRepo;
+use openstudio_core::repositories::issue_repository::IssueRepository;
+use openstudio_core::repositories::project_repository::ProjectRepository;
+use openstudio_core::models::project::Project;
+use openstudio_core::usecases::access::ProjectAccess;
+use openstudio_core::usecases::timeline::{TimelineContext, DEFAULT_TIMELINE_LIMIT};
+use uuid;
+use crate::routes::project::{AuthBearer, ProjectView};
+use crate::routes::common::{json_response, text_response};
+
+#[derive(Clone)]
+pub struct SocialState {
+    pub repo: Arc<InMemorySocialRepo>,
+    pub users: Arc<InMemoryUserRepo>,
+    pub projects: Arc<dyn ProjectRepository + Send + Sync + 'static>,
+    pub issues: Arc<dyn IssueRepository + Send + Sync + 'static>,
+    pub comments: Arc<InMemoryCommentRepo>,
+    pub access: ProjectAccess,
+}
+
+#[derive(Deserialize, Default)]
+pub struct TimelineQuery {
+    pub limit: Option<usize>,
+}
+
+pub fn social_routes() -> Router<SocialState> {
+    Router::new()
+        .route("/projects/{id}/star", post(star_project).delete(unstar_project))
+        .route("/projects/{id}/stargazers", get(list_stargazers))
+        .route("/users/{id}/starred", get(list_starred_projects))
+        .route("/users/{id}/follow", post(follow_user).delete(unfollow_user))
+        .route("/users/{id}/followers", get(list_followers))
+        .route("/users/{id}/following", get(list_following))
+        .route("/timeline", get(get_timeline))
+}
+
+impl SocialState {
+    /// Le projet, s'il existe et que l'appelant peut le voir.
+    fn visible_project(&self, id: uuid::Uuid, caller: Option<uuid::Uuid>) -> Result<Project, (StatusCode, &'static str)> {
+        match self.projects.get_by_id(id) {
+            Ok(Some(project)) if self.access.can_view(&project, caller) => Ok(project),
+            Ok(_) => Err((StatusCode::NOT_FOUND, "Project not found")),
+            Err(_) => Err((StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")),
+        }
+    }
+
+    fn existing_user(&self, id: uuid::Uuid) -> Result<(), (StatusCode, &'static str)> {
+        match self.users.get_user(id) {
+            Some(_) => Ok(()),
+            None => Err((StatusCode::NOT_FOUND, "User not found")),
+        }
+    }
+}
+
+async fn star_project(
+    auth: AuthBearer,
+    State(state): State<SocialState>,
+    axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
+) -> axum::response::Response {
+    if let Err((status, message)) = state.visible_project(id, Some(auth.user_id)) {
+        return text_response(status, message);
+    }
+    if state.repo.star(auth.user_id, id) {
+        text_response(StatusCode::CREATED, "Starred")
+    } else {
+        text_response(StatusCode::OK, "Already starred")
+    }
+}
+
+async fn unstar_project(
+    auth: AuthBearer,
+    State(state): State<SocialState>,
+    axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
+) -> axum::response::Response {
+    if state.repo.unstar(auth.user_id, id) {
+        text_response(StatusCode::OK, "Unstarred")
+    } else {
+        text_response(StatusCode::NOT_FOUND, "Not starred")
+    }
+}
+
+async fn list_stargazers(
+    auth: Option<AuthBearer>,
+    State(state): State<SocialState>,
+    axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
+) -> axum::response::Response {
+    match state.visible_project(id, auth.map(|a| a.user_id)) {
+        Ok(_) => json_response(StatusCode::OK, &state.repo.stargazers(id)),
+        Err((status, message)) => text_response(status, message),
+    }
+}
+
+/// Projets étoilés par l'utilisateur, limités à ceux que l'appelant peut voir.
+async fn list_starred_projects(
+    auth: Option<AuthBearer>,
+    State(state): State<SocialState>,
+    axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
+) -> axum::response::Response {
+    if let Err((status, message)) = state.existing_user(id) {
+        return text_response(status, message);
+    }
+    let caller = auth.map(|a| a.user_id);
+    let projects: Vec<Project> = state
+        .repo
+        .starred_by(id)
+        .iter()
+        .filter_map(|star| state.visible_project(star.project_id, caller).ok())
+        .filter(|project| state.access.is_listed(project, caller))
+        .collect();
+    let views: Vec<ProjectView> = projects.iter().map(|p| ProjectView::new(p, &state.repo)).collect();
+    json_response(StatusCode::OK, &views)
+}
+
+async fn follow_user(
+    auth: AuthBearer,
+    State(state): State<SocialState>,
+    axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
+) -> axum::response::Response {
+    if id == auth.user_id {
+        return text_response(StatusCode::BAD_REQUEST, "You cannot follow yourself");
+    }
+    if let Err((status, message)) = state.existing_user(id) {
+        return text_response(status, message);
+    }
+    if state.repo.follow(auth.user_id, id) {
+        text_response(StatusCode::CREATED, "Following")
+    } else {
+        text_response(StatusCode::OK, "Already following")
+    }
+}
+
+async fn unfollow_user(
+    auth: AuthBearer,
+    State(state): State<SocialState>,
+    axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
+) -> axum::response::Response {
+    if state.repo.unfollow(auth.user_id, id) {
+        text_response(StatusCode::OK, "Unfollowed")
+    } else {
+        text_response(StatusCode::NOT_FOUND, "Not following")
+    }
+}
+
+async fn list_followers(
+    State(state): State<SocialState>,
+    axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
+) -> axum::response::Response {
+    match state.existing_user(id) {
+        Ok(()) => json_response(StatusCode::OK, &state.repo.followers(id)),
+        Err((status, message)) => text_response(status, message),
+    }
+}
+
+async fn list_following(
+    State(state): State<SocialState>,
+    axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
+) -> axum::response::Response {
+    match state.existing_user(id) {
+        Ok(()) => json_response(StatusCode::OK, &state.repo.following(id)),
+        Err((status, message)) => text_response(status, message),
+    }
+}
+
+/// Activité récente des utilisateurs suivis par l'appelant.
+async fn get_timeline(
+    auth: AuthBearer,
+    State(state): State<SocialState>,
+    axum::extract::Query(query): axum::extract::Query<TimelineQuery>,
+) -> axum::response::Response {
+    let context = TimelineContext {
+        projects: state.projects.as_ref(),
+        issues: state.issues.as_ref(),
+        comments: &state.comments,
+        social: &state.repo,
+        access: &state.access,
+    };
+    match context.timeline(auth.user_id, query.limit.unwrap_or(DEFAULT_TIMELINE_LIMIT)) {
+        Ok(events) => json_response(StatusCode::OK, &events),
+        Err(_) => text_response(StatusCode::INTERNAL_SERVER_ERROR, "Internal server error"),
+    }
+}
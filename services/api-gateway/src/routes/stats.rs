@@ -0,0 +1,68 @@
+use axum::{extract::State, http::StatusCode, routing::get, Router};
+use std::sync::Arc;
+use openstudio_core::repositories::in_memory_comment::InMemoryCommentRepo;
+use openstudio_core::repositories::in_memory_issue_history::InMemoryIssueHistoryRepo;
+use openstudio_core::repositories::in_memory_stats::InMemoryStatsCache;
+use openstudio_core::repositories::in_memory_user::InMemoryUserRepo;
+use openstudio_core::repositories::issue_repository::IssueRepository;
+use openstudio_core::repositories::project_repository::ProjectRepository;
+use openstudio_core::usecases::access::ProjectAccess;
+use openstudio_core::usecases::stats::StatsContext;
+use uuid;
+use crate::routes::project::AuthBearer;
+
+#[derive(Clone)]
+pub struct StatsState {
+    pub projects: Arc<dyn ProjectRepository + Send + Sync + 'static>,
+    pub issues: Arc<dyn IssueRepository + Send + Sync + 'static>,
+    pub history: Arc<InMemoryIssueHistoryRepo>,
+    pub comments: Arc<InMemoryCommentRepo>,
+    pub users: Arc<InMemoryUserRepo>,
+    pub cache: Arc<InMemoryStatsCache>,
+    pub access: ProjectAccess,
+}
+
+pub fn stats_routes() -> Router<StatsState> {
+    Router::new().route("/projects/{id}/stats", get(get_project_stats))
+}
+
+async fn get_project_stats(
+    auth: Option<AuthBearer>,
+    State(state): State<StatsState>,
+    axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
+) -> axum::response::Response {
+    use axum::body::Body;
+    use axum::http::Response;
+    match state.projects.get_by_id(id) {
+        Ok(Some(project)) if state.access.can_view(&project, auth.map(|a| a.user_id)) => {},
+        Ok(_) => {
+            return Response::builder()
+                .status(StatusCode::NOT_FOUND)
+                .body(Body::from("Project not found"))
+                .unwrap();
+        },
+        Err(_) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from("Internal server error"))
+                .unwrap();
+        }
+    }
+    let context = StatsContext {
+        issues: state.issues.as_ref(),
+        history: &state.history,
+        comments: &state.comments,
+        users: &state.users,
+    };
+    match context.cached(&state.cache, id) {
+        Ok(stats) => Response::builder()
+            .status(StatusCode::OK)
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::to_string(&stats).unwrap()))
+            .unwrap(),
+        Err(_) => Response::builder()
+            .status(StatusCode::INTERNAL_SERVER_ERROR)
+            .body(Body::from("Internal server error"))
+            .unwrap(),
+    }
+}
@@ -0,0 +1,173 @@
+use axum::{extract::State, http::StatusCode, routing::{get, post}, Router};
+use std::sync::Arc;
+use openstudio_core::models::subscription::{SubscriptionReason, SubscriptionTarget};
+use openstudio_core::repositories::in_memory_subscription::InMemorySubscriptionRepo;
+use openstudio_core::repositories::issue_repository::IssueRepository;
+use openstudio_core::repositories::project_repository::ProjectRepository;
+use openstudio_core::usecases::access::ProjectAccess;
+use uuid;
+use crate::routes::project::AuthBearer;
+
+#[derive(Clone)]
+pub struct SubscriptionState {
+    pub repo: Arc<InMemorySubscriptionRepo>,
+    pub issues: Arc<dyn IssueRepository + Send + Sync + 'static>,
+    pub projects: Arc<dyn ProjectRepository + Send + Sync + 'static>,
+    pub access: ProjectAccess,
+}
+
+impl SubscriptionState {
+    /// La cible existe et l'appelant peut voir le projet auquel elle appartient.
+    fn target_visible(&self, target: SubscriptionTarget, caller: Option<uuid::Uuid>) -> Result<bool, ()> {
+        let project_id = match target {
+            SubscriptionTarget::Issue(id) => match self.issues.get_by_id(id).map_err(|_| ())? {
+                Some(issue) => issue.project_id,
+                None => return Ok(false),
+            },
+            SubscriptionTarget::Project(id) => id,
+        };
+        Ok(self.projects.get_by_id(project_id).map_err(|_| ())?
+            .is_some_and(|project| self.access.can_view(&project, caller)))
+    }
+}
+
+pub fn subscription_routes() -> Router<SubscriptionState> {
+    Router::new()
+        .route("/issues/{id}/watch", post(watch_issue).delete(unwatch_issue))
+        .route("/issues/{id}/watchers", get(list_issue_watchers))
+        .route("/projects/{id}/watch", post(watch_project).delete(unwatch_project))
+        .route("/projects/{id}/watchers", get(list_project_watchers))
+        .route("/subscriptions", get(list_my_subscriptions))
+}
+
+fn watch(state: &SubscriptionState, user_id: uuid::Uuid, target: SubscriptionTarget) -> axum::response::Response {
+    use axum::body::Body;
+    use axum::http::Response;
+    match state.target_visible(target, Some(user_id)) {
+        Ok(true) => {},
+        Ok(false) => {
+            return Response::builder()
+                .status(StatusCode::NOT_FOUND)
+                .body(Body::from("Not found"))
+                .unwrap();
+        },
+        Err(_) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from("Internal server error"))
+                .unwrap();
+        }
+    }
+    if state.repo.subscribe(user_id, target, SubscriptionReason::Manual) {
+        Response::builder()
+            .status(StatusCode::CREATED)
+            .body(Body::from("Watching"))
+            .unwrap()
+    } else {
+        Response::builder()
+            .status(StatusCode::OK)
+            .body(Body::from("Already watching"))
+            .unwrap()
+    }
+}
+
+fn unwatch(state: &SubscriptionState, user_id: uuid::Uuid, target: SubscriptionTarget) -> axum::response::Response {
+    use axum::body::Body;
+    use axum::http::Response;
+    if state.repo.unsubscribe(user_id, target) {
+        Response::builder()
+            .status(StatusCode::OK)
+            .body(Body::from("Unwatched"))
+            .unwrap()
+    } else {
+        Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Body::from("Not watching"))
+            .unwrap()
+    }
+}
+
+fn watchers(state: &SubscriptionState, target: SubscriptionTarget, caller: Option<uuid::Uuid>) -> axum::response::Response {
+    use axum::body::Body;
+    use axum::http::Response;
+    match state.target_visible(target, caller) {
+        Ok(true) => {
+            let body = serde_json::to_string(&state.repo.watchers(target)).unwrap();
+            Response::builder()
+                .status(StatusCode::OK)
+                .header("content-type", "application/json")
+                .body(Body::from(body))
+                .unwrap()
+        },
+        Ok(false) => Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Body::from("Not found"))
+            .unwrap(),
+        Err(_) => Response::builder()
+            .status(StatusCode::INTERNAL_SERVER_ERROR)
+            .body(Body::from("Internal server error"))
+            .unwrap(),
+    }
+}
+
+async fn watch_issue(
+    auth: AuthBearer,
+    State(state): State<SubscriptionState>,
+    axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
+) -> axum::response::Response {
+    watch(&state, auth.user_id, SubscriptionTarget::Issue(id))
+}
+
+async fn unwatch_issue(
+    auth: AuthBearer,
+    State(state): State<SubscriptionState>,
+    axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
+) -> axum::response::Response {
+    unwatch(&state, auth.user_id, SubscriptionTarget::Issue(id))
+}
+
+async fn list_issue_watchers(
+    auth: Option<AuthBearer>,
+    State(state): State<SubscriptionState>,
+    axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
+) -> axum::response::Response {
+    watchers(&state, SubscriptionTarget::Issue(id), auth.map(|a| a.user_id))
+}
+
+async fn watch_project(
+    auth: AuthBearer,
+    State(state): State<SubscriptionState>,
+    axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
+) -> axum::response::Response {
+    watch(&state, auth.user_id, SubscriptionTarget::Project(id))
+}
+
+async fn unwatch_project(
+    auth: AuthBearer,
+    State(state): State<SubscriptionState>,
+    axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
+) -> axum::response::Response {
+    unwatch(&state, auth.user_id, SubscriptionTarget::Project(id))
+}
+
+async fn list_project_watchers(
+    auth: Option<AuthBearer>,
+    State(state): State<SubscriptionState>,
+    axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
+) -> axum::response::Response {
+    watchers(&state, SubscriptionTarget::Project(id), auth.map(|a| a.user_id))
+}
+
+async fn list_my_subscriptions(
+    auth: AuthBearer,
+    State(state): State<SubscriptionState>,
+) -> axum::response::Response {
+    use axum::body::Body;
+    use axum::http::Response;
+    let body = serde_json::to_string(&state.repo.list_for_user(auth.user_id)).unwrap();
+    Response::builder()
+        .status(StatusCode::OK)
+        .header("content-type", "application/json")
+        .body(Body::from(body))
+        .unwrap()
+}
@@ -0,0 +1,283 @@
+use axum::response::IntoResponse;
+use axum::{extract::State, http::StatusCode, routing::{delete, get, post}, Json, Router};
+use chrono::{NaiveDate, Utc};
+use serde::Deserialize;
+use std::sync::Arc;
+use openstudio_core::models::issue::Issue;
+use openstudio_core::models::project::Project;
+use openstudio_core::models::time_entry::TimeEntry;
+use openstudio_core::repositories::in_memory_time::InMemoryTimeRepo;
+use openstudio_core::repositories::in_memory_user::InMemoryUserRepo;
+use openstudio_core::repositories::issue_repository::IssueRepository;
+use openstudio_core::repositories::project_repository::ProjectRepository;
+use openstudio_core::usecases::access::ProjectAccess;
+use openstudio_core::usecases::issue_io::ExchangeFormat;
+use openstudio_core::usecases::policy::Action;
+use openstudio_core::usecases::time_tracking::{entry_from_timer, report_csv, summarize_issue, time_report, MAX_MINUTES_PER_ENTRY};
+use uuid;
+use crate::routes::project::{AuthBearer, CurrentUser};
+use crate::routes::common::json_response;
+
+#[derive(Deserialize)]
+pub struct LogTimeInput {
+    pub minutes: u32,
+    pub date: Option<NaiveDate>,
+    #[serde(default)]
+    pub note: String,
+}
+
+#[derive(Deserialize, Default)]
+pub struct StopTimerInput {
+    #[serde(default)]
+    pub note: String,
+}
+
+#[derive(Deserialize)]
+pub struct TimeReportQuery {
+    #[serde(default)]
+    pub format: ExchangeFormat,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+#[derive(Clone)]
+pub struct TimeState {
+    pub repo: Arc<InMemoryTimeRepo>,
+    pub issues: Arc<dyn IssueRepository + Send + Sync + 'static>,
+    pub projects: Arc<dyn ProjectRepository + Send + Sync + 'static>,
+    pub users: Arc<InMemoryUserRepo>,
+    pub access: ProjectAccess,
+}
+
+pub fn time_routes() -> Router<TimeState> {
+    Router::new()
+        .route("/issues/{id}/time", post(log_time))
+        .route("/issues/{id}/time", get(get_issue_time))
+        .route("/issues/{id}/timer/start", post(start_timer))
+        .route("/issues/{id}/timer/stop", post(stop_timer))
+        .route("/timer", get(get_running_timer))
+        .route("/time-entries/{id}", delete(delete_time_entry))
+        .route("/projects/{id}/time-report", get(get_time_report))
+}
+
+/// Charge l'issue si l'appelant peut voir son projet.
+fn load_issue(state: &TimeState, id: uuid::Uuid, caller: Option<uuid::Uuid>) -> Result<Issue, (StatusCode, &'static str)> {
+    match state.issues.get_by_id(id) {
+        Ok(Some(issue)) => {
+            match state.projects.get_by_id(issue.project_id) {
+                Ok(Some(project)) if state.access.can_view(&project, caller) => Ok(issue),
+                Ok(_) => Err((StatusCode::NOT_FOUND, "Issue not found")),
+                Err(_) => Err((StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")),
+            }
+        },
+        Ok(None) => Err((StatusCode::NOT_FOUND, "Issue not found")),
+        Err(_) => Err((StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")),
+    }
+}
+
+/// Projet de l'issue, pour une écriture : il ne doit pas être archivé.
+fn writable_project(state: &TimeState, issue: &Issue) -> Result<Project, (StatusCode, &'static str)> {
+    match state.projects.get_by_id(issue.project_id) {
+        Ok(Some(project)) if project.is_read_only() => Err((StatusCode::FORBIDDEN, "Project is archived")),
+        Ok(Some(project)) => Ok(project),
+        Ok(None) => Err((StatusCode::NOT_FOUND, "Issue not found")),
+        Err(_) => Err((StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")),
+    }
+}
+
+/// Charge l'issue pour une saisie de temps, que le rôle de l'appelant doit autoriser.
+fn authorize_issue(state: &TimeState, caller: &CurrentUser, id: uuid::Uuid) -> Result<Issue, (StatusCode, &'static str)> {
+    let issue = load_issue(state, id, Some(caller.id()))?;
+    let project = writable_project(state, &issue)?;
+    caller.authorize(&project, Action::LogTime)?;
+    Ok(issue)
+}
+
+async fn log_time(
+    caller: CurrentUser,
+    State(state): State<TimeState>,
+    axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
+    Json(input): Json<LogTimeInput>,
+) -> axum::response::Response {
+    use axum::body::Body;
+    use axum::http::Response;
+    let issue = match authorize_issue(&state, &caller, id) {
+        Ok(issue) => issue,
+        Err(rejection) => return rejection.into_response(),
+    };
+    if !(1..=MAX_MINUTES_PER_ENTRY).contains(&input.minutes) {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from("Duration must be between one minute and 24 hours"))
+            .unwrap();
+    }
+    let entry = TimeEntry {
+        id: uuid::Uuid::new_v4(),
+        issue_id: issue.id,
+        project_id: issue.project_id,
+        user_id: caller.id(),
+        minutes: input.minutes,
+        date: input.date.unwrap_or_else(|| Utc::now().date_naive()),
+        note: input.note,
+        created_at: Utc::now(),
+    };
+    state.repo.save_entry(entry.clone());
+    json_response(StatusCode::CREATED, &entry)
+}
+
+async fn get_issue_time(
+    auth: Option<AuthBearer>,
+    State(state): State<TimeState>,
+    axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
+) -> axum::response::Response {
+    let issue = match load_issue(&state, id, auth.map(|a| a.user_id)) {
+        Ok(issue) => issue,
+        Err(rejection) => return rejection.into_response(),
+    };
+    let entries = state.repo.list_by_issue(issue.id);
+    let body = serde_json::json!({
+        "summary": summarize_issue(&issue, &entries),
+        "entries": entries,
+    });
+    json_response(StatusCode::OK, &body)
+}
+
+async fn start_timer(
+    caller: CurrentUser,
+    State(state): State<TimeState>,
+    axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
+) -> axum::response::Response {
+    let issue = match authorize_issue(&state, &caller, id) {
+        Ok(issue) => issue,
+        Err(rejection) => return rejection.into_response(),
+    };
+    match state.repo.start_timer(caller.id(), issue.id) {
+        Ok(timer) => json_response(StatusCode::CREATED, &timer),
+        Err(running) => json_response(StatusCode::CONFLICT, &running),
+    }
+}
+
+async fn stop_timer(
+    auth: AuthBearer,
+    State(state): State<TimeState>,
+    axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
+    input: Option<Json<StopTimerInput>>,
+) -> axum::response::Response {
+    use axum::body::Body;
+    use axum::http::Response;
+    // Un minuteur déjà lancé peut toujours être arrêté, même après un changement de rôle,
+    // mais pas sur un projet archivé puisque l'arrêt enregistre une saisie
+    let issue = match load_issue(&state, id, Some(auth.user_id)) {
+        Ok(issue) => issue,
+        Err(rejection) => return rejection.into_response(),
+    };
+    if let Err(rejection) = writable_project(&state, &issue) {
+        return rejection.into_response();
+    }
+    let timer = match state.repo.stop_timer(auth.user_id, issue.id) {
+        Some(timer) => timer,
+        None => {
+            return Response::builder()
+                .status(StatusCode::NOT_FOUND)
+                .body(Body::from("No timer running on this issue"))
+                .unwrap();
+        }
+    };
+    let note = input.map(|Json(i)| i.note).unwrap_or_default();
+    let entry = entry_from_timer(&timer, issue.project_id, note);
+    state.repo.save_entry(entry.clone());
+    json_response(StatusCode::CREATED, &entry)
+}
+
+async fn get_running_timer(
+    auth: AuthBearer,
+    State(state): State<TimeState>,
+) -> axum::response::Response {
+    use axum::body::Body;
+    use axum::http::Response;
+    match state.repo.timer_for(auth.user_id) {
+        Some(timer) => json_response(StatusCode::OK, &timer),
+        None => Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Body::from("No timer running"))
+            .unwrap(),
+    }
+}
+
+async fn delete_time_entry(
+    auth: AuthBearer,
+    State(state): State<TimeState>,
+    axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
+) -> axum::response::Response {
+    use axum::body::Body;
+    use axum::http::Response;
+    match state.repo.get_entry(id) {
+        Some(entry) if entry.user_id == auth.user_id => {
+            match state.projects.get_by_id(entry.project_id) {
+                Ok(Some(project)) if project.is_read_only() => {
+                    return (StatusCode::FORBIDDEN, "Project is archived").into_response();
+                },
+                Ok(_) => {},
+                Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error").into_response(),
+            }
+            state.repo.delete_entry(id);
+            Response::builder()
+                .status(StatusCode::OK)
+                .body(Body::from("Time entry deleted"))
+                .unwrap()
+        },
+        Some(_) => Response::builder()
+            .status(StatusCode::FORBIDDEN)
+            .body(Body::from("Only the author can delete a time entry"))
+            .unwrap(),
+        None => Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Body::from("Time entry not found"))
+            .unwrap(),
+    }
+}
+
+async fn get_time_report(
+    auth: Option<AuthBearer>,
+    State(state): State<TimeState>,
+    axum::extract::Path(project_id): axum::extract::Path<uuid::Uuid>,
+    axum::extract::Query(query): axum::extract::Query<TimeReportQuery>,
+) -> axum::response::Response {
+    use axum::body::Body;
+    use axum::http::Response;
+    match state.projects.get_by_id(project_id) {
+        Ok(Some(project)) if state.access.can_view(&project, auth.map(|a| a.user_id)) => {},
+        Ok(_) => {
+            return Response::builder()
+                .status(StatusCode::NOT_FOUND)
+                .body(Body::from("Project not found"))
+                .unwrap();
+        },
+        Err(_) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from("Internal server error"))
+                .unwrap();
+        }
+    }
+    let entries = state.repo.list_by_project(project_id);
+    let report = time_report(project_id, &entries, &state.users, query.from, query.to);
+    match query.format {
+        ExchangeFormat::Json => json_response(StatusCode::OK, &report),
+        ExchangeFormat::Csv => match report_csv(&report) {
+            Ok(body) => Response::builder()
+                .status(StatusCode::OK)
+                .header("content-type", "text/csv; charset=utf-8")
+                .header(
+                    "content-disposition",
+                    format!("attachment; filename=\"time-report-{}.csv\"", project_id),
+                )
+                .body(Body::from(body))
+                .unwrap(),
+            Err(_) => Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from("Internal server error"))
+                .unwrap(),
+        },
+    }
+}
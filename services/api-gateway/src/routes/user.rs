@@ -1,97 +1,76 @@
-use jsonwebtoken::decode;
-use axum::response::IntoResponse;
-use axum::{extract::State, http::StatusCode, routing::{get, post}, Json, Router};
-use axum_extra::extract::TypedHeader;
-use headers::{authorization::Bearer, Authorization};
-const JWT_SECRET: &str = "supersecretkey";
+use axum::{extract::State, http::StatusCode, routing::get, Router};
+use crate::routes::project::AuthBearer;
 
 
-use serde::Deserialize;
 use std::sync::Arc;
 use openstudio_core::models::user::User;
-use argon2::{Argon2, PasswordHasher};
-use rand::rngs::OsRng;
-use argon2::password_hash::{SaltString, PasswordHash, PasswordVerifier};
 use uuid;
-use chrono::Utc;
+use openstudio_core::repositories::in_memory_social::InMemorySocialRepo;
 use openstudio_core::repositories::in_memory_user::InMemoryUserRepo;
+use openstudio_core::repositories::project_repository::ProjectRepository;
+use openstudio_core::usecases::access::ProjectAccess;
 
-#[derive(Deserialize)]
-pub struct CreateUserInput {
-    pub username: String,
-    pub email: String,
-    pub password: String,
-}
-#[derive(serde::Deserialize)]
-struct Claims {
-    sub: String,
-    email: String,
-    exp: usize,
-}
 #[derive(Clone)]
 pub struct UserState {
     pub repo: Arc<InMemoryUserRepo>,
+    pub social: Arc<InMemorySocialRepo>,
+    pub projects: Arc<dyn ProjectRepository + Send + Sync + 'static>,
+    pub access: ProjectAccess,
+}
+
+impl UserState {
+    /// Vue publique de l'utilisateur avec ses compteurs d'abonnés, d'abonnements et
+    /// d'étoiles. Seules comptent les étoiles sur des projets que `caller` voit dans les
+    /// listes. Le hash du mot de passe n'est jamais exposé, l'adresse seulement à son
+    /// titulaire.
+    fn user_json(&self, user: User, caller: uuid::Uuid) -> serde_json::Value {
+        let starred = self
+            .social
+            .starred_by(user.id)
+            .iter()
+            .filter_map(|star| self.projects.get_by_id(star.project_id).ok().flatten())
+            .filter(|project| self.access.is_listed(project, Some(caller)))
+            .count();
+        let mut value = serde_json::json!({
+            "id": user.id,
+            "username": user.username,
+            "first_name": user.first_name,
+            "last_name": user.last_name,
+            "created_at": user.created_at,
+            "followers_count": self.social.followers(user.id).len(),
+            "following_count": self.social.following(user.id).len(),
+            "starred_count": starred,
+        });
+        if user.id == caller {
+            value["email"] = user.email.into();
+            value["email_verified"] = user.email_verified.into();
+        }
+        value
+    }
 }
 
+/// Les comptes se créent par `/register`, qui vérifie l'adresse et le nom.
 pub fn user_routes() -> Router<UserState> {
     Router::new()
-        .route("/users", post(create_user))
         .route("/users", get(list_users_authenticated))
         .route("/users/{id}", get(get_user_by_id_authenticated))
 }
 
-async fn create_user(
-    State(state): State<UserState>,
-    Json(input): Json<CreateUserInput>,
-) -> axum::response::Response {
-    use axum::body::Body;
-    use axum::http::Response;
-    // Hash le mot de passe
-    let salt = SaltString::generate(&mut OsRng);
-    let argon2 = Argon2::default();
-    let password_hash = argon2.hash_password(input.password.as_bytes(), &salt).unwrap().to_string();
-    let user = User {
-        id: uuid::Uuid::new_v4(),
-        username: input.username,
-        email: input.email,
-        password: password_hash,
-        first_name: None,
-        last_name: None,
-        created_at: Utc::now(),
-        updated_at: Utc::now(),
-    };
-    state.repo.save_user(user);
-    Response::builder()
-        .status(StatusCode::CREATED)
-        .body(Body::from("User created"))
-        .unwrap()
-}
-
 async fn list_users_authenticated(
     State(state): State<UserState>,
-    TypedHeader(Authorization(bearer)): TypedHeader<Authorization<Bearer>>,
-) -> Result<axum::Json<Vec<User>>, StatusCode> {
-    let _ = decode::<Claims>(
-        bearer.token(),
-        &jsonwebtoken::DecodingKey::from_secret(JWT_SECRET.as_bytes()),
-        &jsonwebtoken::Validation::default(),
-    ).map_err(|_| StatusCode::UNAUTHORIZED)?;
-    let users = state.repo.list_users();
+    auth: AuthBearer,
+) -> Result<axum::Json<Vec<serde_json::Value>>, StatusCode> {
+    let users = state.repo.list_users().into_iter().map(|u| state.user_json(u, auth.user_id)).collect();
     Ok(axum::Json(users))
 }
 
 async fn get_user_by_id_authenticated(
     State(state): State<UserState>,
     axum::extract::Path(id): axum::extract::Path<uuid::Uuid>,
-    TypedHeader(Authorization(bearer)): TypedHeader<Authorization<Bearer>>,
+    auth: AuthBearer,
 ) -> Result<axum::Json<serde_json::Value>, StatusCode> {
-    let _ = decode::<Claims>(
-        bearer.token(),
-        &jsonwebtoken::DecodingKey::from_secret(JWT_SECRET.as_bytes()),
-        &jsonwebtoken::Validation::default(),
-    ).map_err(|_| StatusCode::UNAUTHORIZED)?;
     match state.repo.get_user(id) {
-        Some(user) => Ok(axum::Json(serde_json::to_value(user).unwrap())),
+        Some(user) => Ok(axum::Json(state.user_json(user, auth.user_id))),
         None => Err(StatusCode::NOT_FOUND),
     }
 }
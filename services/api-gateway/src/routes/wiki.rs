@@ -0,0 +1,289 @@
+use axum::response::IntoResponse;
+use axum::{extract::State, http::StatusCode, routing::{get, post}, Json, Router};
+use serde::Deserialize;
+use std::sync::Arc;
+use openstudio_core::models::project::Project;
+use openstudio_core::models::wiki::WikiPage;
+use openstudio_core::repositories::in_memory_search::InMemorySearchIndex;
+use openstudio_core::repositories::in_memory_wiki::InMemoryWikiRepo;
+use openstudio_core::repositories::project_repository::ProjectRepository;
+use openstudio_core::usecases::access::ProjectAccess;
+use openstudio_core::usecases::policy::Action;
+use openstudio_core::usecases::wiki::{line_diff, PageEdit, WikiContext, WikiError};
+use uuid;
+use crate::routes::issue::deserialize_some;
+use crate::routes::project::{AuthBearer, CurrentUser};
+use crate::routes::common::json_response;
+
+#[derive(Deserialize)]
+pub struct CreatePageInput {
+    pub title: String,
+    #[serde(default)]
+    pub content: String,
+    pub parent_id: Option<uuid::Uuid>,
+}
+
+#[derive(Deserialize)]
+pub struct UpdatePageInput {
+    pub title: Option<String>,
+    pub content: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub parent_id: Option<Option<uuid::Uuid>>,
+}
+
+#[derive(Deserialize)]
+pub struct RestorePageInput {
+    pub revision: u32,
+}
+
+#[derive(Deserialize)]
+pub struct DiffQuery {
+    pub from: Option<u32>,
+    pub to: Option<u32>,
+}
+
+#[derive(Clone)]
+pub struct WikiState {
+    pub repo: Arc<InMemoryWikiRepo>,
+    pub index: Arc<InMemorySearchIndex>,
+    pub projects: Arc<dyn ProjectRepository + Send + Sync + 'static>,
+    pub access: ProjectAccess,
+}
+
+impl WikiState {
+    fn context(&self) -> WikiContext<'_> {
+        WikiContext { wiki: &self.repo, index: &self.index }
+    }
+
+    /// Projet visible par l'appelant.
+    fn load_project(&self, project_id: uuid::Uuid, caller: Option<uuid::Uuid>) -> Result<Project, (StatusCode, &'static str)> {
+        match self.projects.get_by_id(project_id) {
+            Ok(Some(project)) if self.access.can_view(&project, caller) => Ok(project),
+            Ok(_) => Err((StatusCode::NOT_FOUND, "Project not found")),
+            Err(_) => Err((StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")),
+        }
+    }
+
+    /// Projet dont on modifie le wiki : l'appelant doit pouvoir l'éditer et le projet ne doit
+    /// pas être archivé.
+    fn writable_project(&self, caller: &CurrentUser, project_id: uuid::Uuid) -> Result<Project, (StatusCode, &'static str)> {
+        let project = match self.projects.get_by_id(project_id) {
+            Ok(Some(project)) => project,
+            Ok(None) => return Err((StatusCode::NOT_FOUND, "Project not found")),
+            Err(_) => return Err((StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")),
+        };
+        caller.authorize(&project, Action::EditWiki)?;
+        if project.is_read_only() {
+            return Err((StatusCode::FORBIDDEN, "Project is archived"));
+        }
+        Ok(project)
+    }
+
+    fn load_page(&self, project_id: uuid::Uuid, slug: &str) -> Result<WikiPage, (StatusCode, &'static str)> {
+        self.repo.find_by_slug(project_id, slug).ok_or((StatusCode::NOT_FOUND, "Page not found"))
+    }
+
+    fn page_response(&self, status: StatusCode, page: &WikiPage) -> axum::response::Response {
+        let mut body = serde_json::to_value(page).unwrap();
+        body["path"] = serde_json::Value::String(self.context().path(page));
+        json_response(status, &body)
+    }
+}
+
+fn wiki_error(error: WikiError) -> axum::response::Response {
+    let (status, message) = match error {
+        WikiError::PageNotFound => (StatusCode::NOT_FOUND, "Page not found"),
+        WikiError::RevisionNotFound => (StatusCode::NOT_FOUND, "Revision not found"),
+        WikiError::EmptyTitle => (StatusCode::BAD_REQUEST, "Title must not be empty"),
+        WikiError::InvalidParent => (StatusCode::BAD_REQUEST, "Invalid parent page"),
+        WikiError::HasChildren => (StatusCode::CONFLICT, "Move or delete the sub-pages first"),
+        WikiError::PageTooLarge => (StatusCode::PAYLOAD_TOO_LARGE, "Page content is too large"),
+    };
+    (status, message).into_response()
+}
+
+pub fn wiki_routes() -> Router<WikiState> {
+    Router::new()
+        .route("/projects/{id}/wiki", get(list_pages).post(create_page))
+        .route("/projects/{id}/wiki/{slug}", get(get_page).put(update_page).delete(delete_page))
+        .route("/projects/{id}/wiki/{slug}/revisions", get(list_revisions))
+        .route("/projects/{id}/wiki/{slug}/revisions/{revision}", get(get_revision))
+        .route("/projects/{id}/wiki/{slug}/diff", get(diff_revisions))
+        .route("/projects/{id}/wiki/{slug}/restore", post(restore_page))
+}
+
+async fn list_pages(
+    auth: Option<AuthBearer>,
+    State(state): State<WikiState>,
+    axum::extract::Path(project_id): axum::extract::Path<uuid::Uuid>,
+) -> axum::response::Response {
+    if let Err(rejection) = state.load_project(project_id, auth.map(|a| a.user_id)) {
+        return rejection.into_response();
+    }
+    let context = state.context();
+    let mut pages: Vec<serde_json::Value> = state
+        .repo
+        .list_by_project(project_id)
+        .iter()
+        .map(|page| {
+            serde_json::json!({
+                "id": page.id,
+                "parent_id": page.parent_id,
+                "slug": page.slug,
+                "title": page.title,
+                "path": context.path(page),
+                "revision": page.revision,
+                "updated_at": page.updated_at,
+            })
+        })
+        .collect();
+    // Tri par chemin : chaque page suit son parent
+    pages.sort_by(|a, b| a["path"].as_str().cmp(&b["path"].as_str()));
+    json_response(StatusCode::OK, &pages)
+}
+
+async fn create_page(
+    caller: CurrentUser,
+    State(state): State<WikiState>,
+    axum::extract::Path(project_id): axum::extract::Path<uuid::Uuid>,
+    Json(input): Json<CreatePageInput>,
+) -> axum::response::Response {
+    if let Err(rejection) = state.writable_project(&caller, project_id) {
+        return rejection.into_response();
+    }
+    match state.context().create_page(project_id, input.parent_id, &input.title, &input.content, Some(caller.id())) {
+        Ok(page) => state.page_response(StatusCode::CREATED, &page),
+        Err(e) => wiki_error(e),
+    }
+}
+
+async fn get_page(
+    auth: Option<AuthBearer>,
+    State(state): State<WikiState>,
+    axum::extract::Path((project_id, slug)): axum::extract::Path<(uuid::Uuid, String)>,
+) -> axum::response::Response {
+    let page = state
+        .load_project(project_id, auth.map(|a| a.user_id))
+        .and_then(|_| state.load_page(project_id, &slug));
+    match page {
+        Ok(page) => state.page_response(StatusCode::OK, &page),
+        Err(rejection) => rejection.into_response(),
+    }
+}
+
+async fn update_page(
+    caller: CurrentUser,
+    State(state): State<WikiState>,
+    axum::extract::Path((project_id, slug)): axum::extract::Path<(uuid::Uuid, String)>,
+    Json(input): Json<UpdatePageInput>,
+) -> axum::response::Response {
+    let page = match state
+        .writable_project(&caller, project_id)
+        .and_then(|_| state.load_page(project_id, &slug))
+    {
+        Ok(page) => page,
+        Err(rejection) => return rejection.into_response(),
+    };
+    let edit = PageEdit { title: input.title, content: input.content, parent_id: input.parent_id };
+    match state.context().update_page(page, edit, Some(caller.id())) {
+        Ok(page) => state.page_response(StatusCode::OK, &page),
+        Err(e) => wiki_error(e),
+    }
+}
+
+async fn delete_page(
+    caller: CurrentUser,
+    State(state): State<WikiState>,
+    axum::extract::Path((project_id, slug)): axum::extract::Path<(uuid::Uuid, String)>,
+) -> axum::response::Response {
+    let page = match state
+        .writable_project(&caller, project_id)
+        .and_then(|_| state.load_page(project_id, &slug))
+    {
+        Ok(page) => page,
+        Err(rejection) => return rejection.into_response(),
+    };
+    match state.context().delete_page(&page) {
+        Ok(()) => (StatusCode::OK, "Page deleted").into_response(),
+        Err(e) => wiki_error(e),
+    }
+}
+
+async fn list_revisions(
+    auth: Option<AuthBearer>,
+    State(state): State<WikiState>,
+    axum::extract::Path((project_id, slug)): axum::extract::Path<(uuid::Uuid, String)>,
+) -> axum::response::Response {
+    let page = match state
+        .load_project(project_id, auth.map(|a| a.user_id))
+        .and_then(|_| state.load_page(project_id, &slug))
+    {
+        Ok(page) => page,
+        Err(rejection) => return rejection.into_response(),
+    };
+    json_response(StatusCode::OK, &state.repo.list_revisions(page.id))
+}
+
+async fn get_revision(
+    auth: Option<AuthBearer>,
+    State(state): State<WikiState>,
+    axum::extract::Path((project_id, slug, revision)): axum::extract::Path<(uuid::Uuid, String, u32)>,
+) -> axum::response::Response {
+    let page = match state
+        .load_project(project_id, auth.map(|a| a.user_id))
+        .and_then(|_| state.load_page(project_id, &slug))
+    {
+        Ok(page) => page,
+        Err(rejection) => return rejection.into_response(),
+    };
+    match state.repo.get_revision(page.id, revision) {
+        Some(revision) => json_response(StatusCode::OK, &revision),
+        None => wiki_error(WikiError::RevisionNotFound),
+    }
+}
+
+async fn diff_revisions(
+    auth: Option<AuthBearer>,
+    State(state): State<WikiState>,
+    axum::extract::Path((project_id, slug)): axum::extract::Path<(uuid::Uuid, String)>,
+    axum::extract::Query(query): axum::extract::Query<DiffQuery>,
+) -> axum::response::Response {
+    let page = match state
+        .load_project(project_id, auth.map(|a| a.user_id))
+        .and_then(|_| state.load_page(project_id, &slug))
+    {
+        Ok(page) => page,
+        Err(rejection) => return rejection.into_response(),
+    };
+    // Par défaut : la dernière révision comparée à la précédente
+    let to = query.to.unwrap_or(page.revision);
+    let from = query.from.unwrap_or(to.saturating_sub(1));
+    let old = if from == 0 { Some(String::new()) } else { state.repo.get_revision(page.id, from).map(|r| r.content) };
+    let new = state.repo.get_revision(page.id, to).map(|r| r.content);
+    match (old, new) {
+        (Some(old), Some(new)) => {
+            let body = serde_json::json!({ "from": from, "to": to, "lines": line_diff(&old, &new) });
+            json_response(StatusCode::OK, &body)
+        },
+        _ => wiki_error(WikiError::RevisionNotFound),
+    }
+}
+
+async fn restore_page(
+    caller: CurrentUser,
+    State(state): State<WikiState>,
+    axum::extract::Path((project_id, slug)): axum::extract::Path<(uuid::Uuid, String)>,
+    Json(input): Json<RestorePageInput>,
+) -> axum::response::Response {
+    let page = match state
+        .writable_project(&caller, project_id)
+        .and_then(|_| state.load_page(project_id, &slug))
+    {
+        Ok(page) => page,
+        Err(rejection) => return rejection.into_response(),
+    };
+    match state.context().restore(page, input.revision, Some(caller.id())) {
+        Ok(page) => state.page_response(StatusCode::OK, &page),
+        Err(e) => wiki_error(e),
+    }
+}
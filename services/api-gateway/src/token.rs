@@ -0,0 +1,382 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use jsonwebtoken::jwk::{
+    AlgorithmParameters, CommonParameters, EllipticCurve, Jwk, JwkSet, KeyAlgorithm, OctetKeyPairParameters,
+    OctetKeyPairType, PublicKeyUse, RSAKeyParameters, RSAKeyType,
+};
+use jsonwebtoken::{decode, decode_header, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use ring::signature::{Ed25519KeyPair, KeyPair, RsaKeyPair, RsaPublicKeyComponents};
+use serde::de::DeserializeOwned;
+use simple_asn1::ASN1Block;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use openstudio_core::models::session::RefreshToken;
+use openstudio_core::usecases::email_verification::VERIFICATION_LINK_HOURS;
+
+pub const ACCESS_TOKEN_SECONDS: i64 = 3600;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenType {
+    Access,
+    Refresh,
+    EmailVerification,
+}
+
+/// Jeton d'accès, présenté en `Authorization: Bearer`. `sid` désigne la session (famille de
+/// refresh tokens) qui l'a produit : fermer la session l'invalide aussitôt.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AccessClaims {
+    pub sub: Uuid,
+    pub email: String,
+    pub sid: Uuid,
+    pub typ: TokenType,
+    pub iat: i64,
+    pub exp: i64,
+}
+
+/// Refresh token : `jti` référence l'enregistrement conservé côté serveur.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RefreshClaims {
+    pub sub: Uuid,
+    pub sid: Uuid,
+    pub jti: Uuid,
+    pub typ: TokenType,
+    pub iat: i64,
+    pub exp: i64,
+}
+
+/// Lien de vérification envoyé par email : il ne vaut que pour l'adresse `email`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EmailVerificationClaims {
+    pub sub: Uuid,
+    pub email: String,
+    pub typ: TokenType,
+    pub iat: i64,
+    pub exp: i64,
+}
+
+/// Clé décrite dans la configuration. Une clé HS256 porte un `secret` ; une clé RS256 ou
+/// EdDSA porte sa clé privée (PEM), ou seulement sa clé publique si elle ne sert plus
+/// qu'à vérifier les jetons déjà émis.
+#[derive(Debug, Deserialize)]
+pub struct KeyConfig {
+    pub kid: String,
+    pub alg: Algorithm,
+    pub secret: Option<String>,
+    pub private_key: Option<PathBuf>,
+    pub public_key: Option<PathBuf>,
+}
+
+/// Fichier pointé par `OPENSTUDIO_JWT_CONFIG`. `active` désigne la clé de signature ;
+/// les autres clés restent acceptées en vérification pendant une rotation.
+#[derive(Debug, Deserialize)]
+pub struct TokenConfig {
+    pub active: String,
+    pub keys: Vec<KeyConfig>,
+}
+
+struct VerifyingKey {
+    alg: Algorithm,
+    decoding: DecodingKey,
+    /// Clé publique exposée dans le JWKS ; absente pour un secret HS256.
+    jwk: Option<Jwk>,
+}
+
+/// Émission et vérification des JWT de l'API. Chaque jeton porte le `kid` de la clé qui
+/// l'a signé, ce qui permet d'en changer sans invalider les sessions en cours.
+pub struct TokenService {
+    kid: String,
+    alg: Algorithm,
+    encoding: EncodingKey,
+    keys: HashMap<String, VerifyingKey>,
+}
+
+impl TokenService {
+    /// Service à une seule clé HS256.
+    pub fn hs256(kid: &str, secret: &[u8]) -> Self {
+        let mut keys = HashMap::new();
+        keys.insert(
+            kid.to_string(),
+            VerifyingKey { alg: Algorithm::HS256, decoding: DecodingKey::from_secret(secret), jwk: None },
+        );
+        TokenService { kid: kid.to_string(), alg: Algorithm::HS256, encoding: EncodingKey::from_secret(secret), keys }
+    }
+
+    /// Lit la configuration :
+    /// - `OPENSTUDIO_JWT_CONFIG` : fichier JSON décrivant les clés (voir `TokenConfig`) ;
+    /// - sinon `OPENSTUDIO_JWT_SECRET` (et `OPENSTUDIO_JWT_KID`) : une clé HS256 ;
+    /// - sinon un secret aléatoire, valable le temps du processus.
+    pub fn from_env() -> Result<Self, String> {
+        if let Ok(path) = std::env::var("OPENSTUDIO_JWT_CONFIG") {
+            let raw = std::fs::read_to_string(&path).map_err(|e| format!("{}: {}", path, e))?;
+            let config: TokenConfig = serde_json::from_str(&raw).map_err(|e| format!("{}: {}", path, e))?;
+            let base = Path::new(&path).parent().unwrap_or(Path::new("."));
+            return Self::from_config(&config, base);
+        }
+        let kid = std::env::var("OPENSTUDIO_JWT_KID").unwrap_or_else(|_| "default".to_string());
+        match std::env::var("OPENSTUDIO_JWT_SECRET") {
+            Ok(secret) if !secret.is_empty() => Ok(Self::hs256(&kid, secret.as_bytes())),
+            _ => {
+                eprintln!("OPENSTUDIO_JWT_SECRET is not set: tokens will not survive a restart");
+                let secret: [u8; 32] = rand::random();
+                Ok(Self::hs256(&kid, &secret))
+            },
+        }
+    }
+
+    /// Les chemins relatifs des clés sont résolus depuis `base`.
+    pub fn from_config(config: &TokenConfig, base: &Path) -> Result<Self, String> {
+        let mut keys = HashMap::new();
+        let mut encoding = None;
+        for key in &config.keys {
+            let (verifying, signing) = load_key(key, base).map_err(|e| format!("key {}: {}", key.kid, e))?;
+            if key.kid == config.active {
+                encoding = Some((key.alg, signing.ok_or(format!("key {}: the active key needs a secret or a private key", key.kid))?));
+            }
+            if keys.insert(key.kid.clone(), verifying).is_some() {
+                return Err(format!("key {}: duplicate kid", key.kid));
+            }
+        }
+        let (alg, encoding) = encoding.ok_or(format!("unknown active key {}", config.active))?;
+        Ok(TokenService { kid: config.active.clone(), alg, encoding, keys })
+    }
+
+    fn issue<T: Serialize>(&self, claims: &T) -> String {
+        let mut header = Header::new(self.alg);
+        header.kid = Some(self.kid.clone());
+        encode(&header, claims, &self.encoding).expect("JWT signing failed")
+    }
+
+    /// Vérifie signature et expiration avec la clé désignée par le `kid` du jeton.
+    fn verify<T: DeserializeOwned>(&self, token: &str) -> Option<T> {
+        let header = decode_header(token).ok()?;
+        let key = self.keys.get(header.kid.as_deref()?)?;
+        decode::<T>(token, &key.decoding, &Validation::new(key.alg)).ok().map(|data| data.claims)
+    }
+
+    pub fn issue_access(&self, user_id: Uuid, email: &str, session_id: Uuid) -> String {
+        let now = chrono::Utc::now().timestamp();
+        self.issue(&AccessClaims {
+            sub: user_id,
+            email: email.to_string(),
+            sid: session_id,
+            typ: TokenType::Access,
+            iat: now,
+            exp: now + ACCESS_TOKEN_SECONDS,
+        })
+    }
+
+    pub fn issue_refresh(&self, token: &RefreshToken) -> String {
+        self.issue(&RefreshClaims {
+            sub: token.user_id,
+            sid: token.family_id,
+            jti: token.id,
+            typ: TokenType::Refresh,
+            iat: token.created_at.timestamp(),
+            exp: token.expires_at.timestamp(),
+        })
+    }
+
+    /// Un refresh token n'est jamais accepté comme jeton d'accès, et inversement.
+    pub fn verify_access(&self, token: &str) -> Option<AccessClaims> {
+        self.verify::<AccessClaims>(token).filter(|c| c.typ == TokenType::Access)
+    }
+
+    pub fn verify_refresh(&self, token: &str) -> Option<RefreshClaims> {
+        self.verify::<RefreshClaims>(token).filter(|c| c.typ == TokenType::Refresh)
+    }
+
+    pub fn issue_email_verification(&self, user_id: Uuid, email: &str) -> String {
+        let now = chrono::Utc::now().timestamp();
+        self.issue(&EmailVerificationClaims {
+            sub: user_id,
+            email: email.to_string(),
+            typ: TokenType::EmailVerification,
+            iat: now,
+            exp: now + VERIFICATION_LINK_HOURS * 3600,
+        })
+    }
+
+    pub fn verify_email_verification(&self, token: &str) -> Option<EmailVerificationClaims> {
+        self.verify::<EmailVerificationClaims>(token).filter(|c| c.typ == TokenType::EmailVerification)
+    }
+
+    /// Clés publiques de vérification, au format JWKS.
+    pub fn jwks(&self) -> JwkSet {
+        let mut keys: Vec<Jwk> = self.keys.values().filter_map(|k| k.jwk.clone()).collect();
+        keys.sort_by(|a, b| a.common.key_id.cmp(&b.common.key_id));
+        JwkSet { keys }
+    }
+}
+
+fn load_key(key: &KeyConfig, base: &Path) -> Result<(VerifyingKey, Option<EncodingKey>), String> {
+    let read = |path: &PathBuf| std::fs::read(base.join(path)).map_err(|e| format!("{}: {}", path.display(), e));
+    match key.alg {
+        Algorithm::HS256 => {
+            let secret = key.secret.as_ref().ok_or("HS256 keys need a secret")?;
+            let verifying = VerifyingKey { alg: key.alg, decoding: DecodingKey::from_secret(secret.as_bytes()), jwk: None };
+            Ok((verifying, Some(EncodingKey::from_secret(secret.as_bytes()))))
+        },
+        Algorithm::RS256 | Algorithm::EdDSA => {
+            let (params, encoding) = match (&key.private_key, &key.public_key) {
+                (Some(path), _) => {
+                    let pem_bytes = read(path)?;
+                    let parsed = pem::parse(&pem_bytes).map_err(|e| e.to_string())?;
+                    if key.alg == Algorithm::RS256 {
+                        let encoding = EncodingKey::from_rsa_pem(&pem_bytes).map_err(|e| e.to_string())?;
+                        (rsa_private_params(&parsed)?, Some(encoding))
+                    } else {
+                        let encoding = EncodingKey::from_ed_pem(&pem_bytes).map_err(|e| e.to_string())?;
+                        (ed_private_params(&parsed)?, Some(encoding))
+                    }
+                },
+                (None, Some(path)) => {
+                    let parsed = pem::parse(read(path)?).map_err(|e| e.to_string())?;
+                    let params = if key.alg == Algorithm::RS256 { rsa_public_params(&parsed)? } else { ed_public_params(&parsed)? };
+                    (params, None)
+                },
+                (None, None) => return Err("asymmetric keys need a private_key or a public_key".to_string()),
+            };
+            let jwk = Jwk {
+                common: CommonParameters {
+                    public_key_use: Some(PublicKeyUse::Signature),
+                    key_algorithm: Some(if key.alg == Algorithm::RS256 { KeyAlgorithm::RS256 } else { KeyAlgorithm::EdDSA }),
+                    key_id: Some(key.kid.clone()),
+                    ..Default::default()
+                },
+                algorithm: params,
+            };
+            let decoding = DecodingKey::from_jwk(&jwk).map_err(|e| e.to_string())?;
+            Ok((VerifyingKey { alg: key.alg, decoding, jwk: Some(jwk) }, encoding))
+        },
+        other => Err(format!("unsupported algorithm {:?} (expected HS256, RS256 or EdDSA)", other)),
+    }
+}
+
+fn b64(bytes: &[u8]) -> String {
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+fn rsa_params(n: &[u8], e: &[u8]) -> AlgorithmParameters {
+    AlgorithmParameters::RSA(RSAKeyParameters { key_type: RSAKeyType::RSA, n: b64(n), e: b64(e) })
+}
+
+fn ed_params(x: &[u8]) -> AlgorithmParameters {
+    AlgorithmParameters::OctetKeyPair(OctetKeyPairParameters {
+        key_type: OctetKeyPairType::OctetKeyPair,
+        curve: EllipticCurve::Ed25519,
+        x: b64(x),
+    })
+}
+
+/// Composantes publiques d'une clé privée RSA, en PKCS#8 ou PKCS#1.
+fn rsa_private_params(parsed: &pem::Pem) -> Result<AlgorithmParameters, String> {
+    let pair = match parsed.tag() {
+        "PRIVATE KEY" => RsaKeyPair::from_pkcs8(parsed.contents()),
+        "RSA PRIVATE KEY" => RsaKeyPair::from_der(parsed.contents()),
+        tag => return Err(format!("unexpected PEM block {}", tag)),
+    }
+    .map_err(|e| e.to_string())?;
+    let components = RsaPublicKeyComponents::<Vec<u8>>::from(pair.public());
+    Ok(rsa_params(&components.n, &components.e))
+}
+
+fn ed_private_params(parsed: &pem::Pem) -> Result<AlgorithmParameters, String> {
+    let pair = Ed25519KeyPair::from_pkcs8_maybe_unchecked(parsed.contents()).map_err(|e| e.to_string())?;
+    Ok(ed_params(pair.public_key().as_ref()))
+}
+
+/// Clé publique RSA, en SubjectPublicKeyInfo (`PUBLIC KEY`) ou PKCS#1 (`RSA PUBLIC KEY`). Sans
+/// clé privée, ring ne sait pas la lire : le DER passe par simple_asn1, le décodeur sur lequel
+/// s'appuie jsonwebtoken.
+fn rsa_public_params(parsed: &pem::Pem) -> Result<AlgorithmParameters, String> {
+    let pkcs1 = match parsed.tag() {
+        "PUBLIC KEY" => spki_key_bits(parsed.contents())?,
+        "RSA PUBLIC KEY" => parsed.contents().to_vec(),
+        tag => return Err(format!("unexpected PEM block {}", tag)),
+    };
+    let invalid = || "invalid RSA public key".to_string();
+    let blocks = simple_asn1::from_der(&pkcs1).map_err(|e| e.to_string())?;
+    let [ASN1Block::Sequence(_, items)] = blocks.as_slice() else {
+        return Err(invalid());
+    };
+    let [ASN1Block::Integer(_, n), ASN1Block::Integer(_, e)] = items.as_slice() else {
+        return Err(invalid());
+    };
+    let (n, e) = (n.to_biguint().ok_or_else(invalid)?, e.to_biguint().ok_or_else(invalid)?);
+    Ok(rsa_params(&n.to_bytes_be(), &e.to_bytes_be()))
+}
+
+fn ed_public_params(parsed: &pem::Pem) -> Result<AlgorithmParameters, String> {
+    let key = spki_key_bits(parsed.contents())?;
+    if key.len() != 32 {
+        return Err("not an Ed25519 public key".to_string());
+    }
+    Ok(ed_params(&key))
+}
+
+/// Contenu du BIT STRING d'un SubjectPublicKeyInfo.
+fn spki_key_bits(der: &[u8]) -> Result<Vec<u8>, String> {
+    let invalid = || "invalid public key".to_string();
+    let blocks = simple_asn1::from_der(der).map_err(|e| e.to_string())?;
+    let [ASN1Block::Sequence(_, items)] = blocks.as_slice() else {
+        return Err(invalid());
+    };
+    match items.as_slice() {
+        [ASN1Block::Sequence(..), ASN1Block::BitString(_, bits, key)] if *bits == key.len() * 8 => Ok(key.clone()),
+        _ => Err(invalid()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/jwt");
+
+    fn rsa_components(params: AlgorithmParameters) -> (String, String) {
+        match params {
+            AlgorithmParameters::RSA(rsa) => (rsa.n, rsa.e),
+            _ => panic!("not an RSA key"),
+        }
+    }
+
+    #[test]
+    fn public_key_matches_private_key_components() {
+        let private = pem::parse(std::fs::read(format!("{}/rsa.pem", FIXTURES)).unwrap()).unwrap();
+        let public = pem::parse(std::fs::read(format!("{}/rsa.pub.pem", FIXTURES)).unwrap()).unwrap();
+        let (n, e) = rsa_components(rsa_public_params(&public).unwrap());
+        assert_eq!(rsa_components(rsa_private_params(&private).unwrap()), (n, e.clone()));
+        assert_eq!(e, "AQAB");
+    }
+
+    #[test]
+    fn malformed_public_keys_are_rejected() {
+        let public = pem::parse(std::fs::read(format!("{}/rsa.pub.pem", FIXTURES)).unwrap()).unwrap();
+        let der = public.contents();
+        let inputs: Vec<Vec<u8>> = vec![
+            Vec::new(),
+            vec![0x30],
+            // Longueur annoncée au-delà des données
+            vec![0x30, 0x82, 0xff, 0xff, 0x02, 0x01, 0x01],
+            // Longueur sur plus de quatre octets
+            vec![0x30, 0x85, 0, 0, 0, 0, 1, 0],
+            // Mauvais type à la racine
+            vec![0x02, 0x01, 0x01],
+            der[..der.len() / 2].to_vec(),
+        ];
+        for input in inputs {
+            for tag in ["PUBLIC KEY", "RSA PUBLIC KEY"] {
+                let parsed = pem::Pem::new(tag, input.clone());
+                assert!(rsa_public_params(&parsed).is_err(), "{} {:02x?}", tag, input);
+            }
+            assert!(ed_public_params(&pem::Pem::new("PUBLIC KEY", input.clone())).is_err());
+        }
+        assert!(rsa_public_params(&pem::Pem::new("CERTIFICATE", der.to_vec())).is_err());
+    }
+}
@@ -0,0 +1,66 @@
+mod common;
+
+use reqwest::Client;
+use serde_json::{json, Value};
+
+const BASE: &str = "http://127.0.0.1:3013";
+
+async fn create_token(client: &Client, jwt: &str, body: Value) -> Value {
+    let res = client.post(format!("{}/tokens", BASE)).bearer_auth(jwt).json(&body).send().await.unwrap();
+    assert_eq!(res.status(), 201);
+    res.json().await.unwrap()
+}
+
+#[tokio::test]
+async fn test_personal_access_tokens_scopes_and_project_restriction() {
+    let server = common::spawn_server("127.0.0.1:3013");
+    let client = Client::new();
+    let body: Value = client.post(format!("{}/register", BASE))
+        .json(&json!({"username": "ci", "email": "ci@example.com", "password": "cipass"}))
+        .send().await.unwrap().json().await.unwrap();
+    let jwt = body["access_token"].as_str().unwrap().to_string();
+    server.verify_email(&client, "ci@example.com").await;
+    for name in ["api", "docs"] {
+        client.post(format!("{}/projects", BASE)).bearer_auth(&jwt)
+            .json(&json!({"name": name, "description": ""}))
+            .send().await.unwrap();
+    }
+    let projects: Value = client.get(format!("{}/projects", BASE)).bearer_auth(&jwt).send().await.unwrap().json().await.unwrap();
+    let (api, docs) = (projects[0]["id"].as_str().unwrap().to_string(), projects[1]["id"].as_str().unwrap().to_string());
+
+    // Lecture seule des projets
+    let reader = create_token(&client, &jwt, json!({"name": "reader", "scopes": ["projects:read"]})).await;
+    let reader_token = reader["token"].as_str().unwrap();
+    assert!(reader_token.starts_with("osp_"));
+    assert!(reader.get("token_hash").is_none());
+    let res = client.get(format!("{}/projects/{}", BASE, api)).bearer_auth(reader_token).send().await.unwrap();
+    assert_eq!(res.status(), 200);
+    let res = client.put(format!("{}/projects/{}", BASE, api)).bearer_auth(reader_token)
+        .json(&json!({"name": "renamed"})).send().await.unwrap();
+    assert_eq!(res.status(), 403);
+    // Un jeton personnel ne gère ni les jetons ni le compte
+    let res = client.get(format!("{}/tokens", BASE)).bearer_auth(reader_token).send().await.unwrap();
+    assert_eq!(res.status(), 403);
+
+    // Écriture des issues, limitée au projet "api"
+    let ci = create_token(&client, &jwt, json!({"name": "ci", "scopes": ["issues:write"], "project_id": api, "expires_in_days": 30})).await;
+    let ci_token = ci["token"].as_str().unwrap();
+    let res = client.post(format!("{}/issues", BASE)).bearer_auth(ci_token)
+        .json(&json!({"project_id": api, "title": "Flaky test", "description": ""})).send().await.unwrap();
+    assert_eq!(res.status(), 201);
+    let res = client.post(format!("{}/issues", BASE)).bearer_auth(ci_token)
+        .json(&json!({"project_id": docs, "title": "Typo", "description": ""})).send().await.unwrap();
+    assert_eq!(res.status(), 403);
+    let res = client.get(format!("{}/issues?project_id={}", BASE, docs)).bearer_auth(ci_token).send().await.unwrap();
+    assert_eq!(res.status(), 403);
+    let res = client.get(format!("{}/issues?project_id={}", BASE, api)).bearer_auth(ci_token).send().await.unwrap();
+    assert_eq!(res.status(), 200);
+
+    // Dernière utilisation visible, puis révocation
+    let tokens: Value = client.get(format!("{}/tokens", BASE)).bearer_auth(&jwt).send().await.unwrap().json().await.unwrap();
+    assert!(!tokens[1]["last_used_at"].is_null());
+    let res = client.delete(format!("{}/tokens/{}", BASE, ci["id"].as_str().unwrap())).bearer_auth(&jwt).send().await.unwrap();
+    assert_eq!(res.status(), 200);
+    let res = client.get(format!("{}/issues?project_id={}", BASE, api)).bearer_auth(ci_token).send().await.unwrap();
+    assert_eq!(res.status(), 401);
+}
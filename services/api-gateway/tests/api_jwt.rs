@@ -1,107 +1,231 @@
+mod common;
 
 use reqwest::Client;
 use serde_json::json;
 
+const BASE: &str = "http://127.0.0.1:3019";
+
 #[tokio::test]
 async fn test_full_api_flow() {
+    let server = common::spawn_server("127.0.0.1:3019");
     let client = Client::new();
 
     // --- USERS ---
     // Register
-    let res = client.post("http://localhost:3000/register")
+    let res = client.post(format!("{}/register", BASE))
         .json(&json!({"username": "bob", "email": "bob@bob.com", "password": "bobpass"}))
         .send().await.unwrap();
     assert!(res.status().is_success());
+    let body: serde_json::Value = res.json().await.unwrap();
+    let bob_id = body["user"]["id"].as_str().unwrap().to_string();
+    server.verify_email(&client, "bob@bob.com").await;
 
     // Login
-    let res = client.post("http://localhost:3000/login")
+    let res = client.post(format!("{}/login", BASE))
         .json(&json!({"username": "bob", "email": "bob@bob.com", "password": "bobpass"}))
         .send().await.unwrap();
     assert_eq!(res.status(), 200);
     let body: serde_json::Value = res.json().await.unwrap();
     let access_token = body["access_token"].as_str().unwrap();
-    let refresh_token = body["refresh_token"].as_str().unwrap();
+    let _refresh_token = body["refresh_token"].as_str().unwrap();
 
     // List users (protégé)
-    let res = client.get("http://localhost:3000/users")
+    let res = client.get(format!("{}/users", BASE))
         .bearer_auth(access_token)
         .send().await.unwrap();
     assert_eq!(res.status(), 200);
 
     // --- PROJECTS ---
     // Create project SANS token (401)
-    let res = client.post("http://localhost:3000/projects")
+    let res = client.post(format!("{}/projects", BASE))
         .json(&json!({"name": "test", "description": "desc"}))
         .send().await.unwrap();
     assert_eq!(res.status(), 401);
     // Create project AVEC token
-    let res = client.post("http://localhost:3000/projects")
+    let res = client.post(format!("{}/projects", BASE))
         .bearer_auth(access_token)
         .json(&json!({"name": "test", "description": "desc"}))
         .send().await.unwrap();
     assert_eq!(res.status(), 201);
-    // List projects (public)
-    let res = client.get("http://localhost:3000/projects")
+    let created: serde_json::Value = res.json().await.unwrap();
+    assert_eq!((created["name"].as_str(), created["stars_count"].as_u64()), (Some("test"), Some(0)));
+    // List projects (un projet privé n'est listé que pour ses membres)
+    let res = client.get(format!("{}/projects", BASE))
+        .bearer_auth(access_token)
         .send().await.unwrap();
     assert_eq!(res.status(), 200);
     let projects: serde_json::Value = res.json().await.unwrap();
     let project_id = projects[0]["id"].as_str().unwrap();
     // Update project SANS token (401)
-    let res = client.put(&format!("http://localhost:3000/projects/{}", project_id))
+    let res = client.put(format!("{}/projects/{}", BASE, project_id))
         .json(&json!({"name": "newname"}))
         .send().await.unwrap();
     assert_eq!(res.status(), 401);
     // Update project AVEC token
-    let res = client.put(&format!("http://localhost:3000/projects/{}", project_id))
+    let res = client.put(format!("{}/projects/{}", BASE, project_id))
         .bearer_auth(access_token)
         .json(&json!({"name": "newname"}))
         .send().await.unwrap();
     assert_eq!(res.status(), 200);
-    // Delete project SANS token (401)
-    let res = client.delete(&format!("http://localhost:3000/projects/{}", project_id))
+    let updated: serde_json::Value = res.json().await.unwrap();
+    assert_eq!((updated["name"].as_str(), updated["stars_count"].as_u64()), (Some("newname"), Some(0)));
+    // Un autre utilisateur, non membre du projet
+    client.post(format!("{}/register", BASE))
+        .json(&json!({"username": "eve", "email": "eve@eve.com", "password": "evepass"}))
         .send().await.unwrap();
-    assert_eq!(res.status(), 401);
-    // Delete project AVEC token
-    let res = client.delete(&format!("http://localhost:3000/projects/{}", project_id))
+    let res = client.post(format!("{}/login", BASE))
+        .json(&json!({"username": "eve", "email": "eve@eve.com", "password": "evepass"}))
+        .send().await.unwrap();
+    let body: serde_json::Value = res.json().await.unwrap();
+    let other_token = body["access_token"].as_str().unwrap().to_string();
+
+    // Une étoile sur un projet privé n'est comptée que pour qui le voit dans les listes
+    let res = client.post(format!("{}/projects/{}/star", BASE, project_id))
         .bearer_auth(access_token)
         .send().await.unwrap();
-    assert_eq!(res.status(), 200);
+    assert!(res.status().is_success());
+    for (token, expected) in [(access_token, 1), (other_token.as_str(), 0)] {
+        let res = client.get(format!("{}/users/{}", BASE, bob_id)).bearer_auth(token).send().await.unwrap();
+        let user: serde_json::Value = res.json().await.unwrap();
+        assert_eq!(user["starred_count"], expected);
+        // Le hash du mot de passe n'est jamais renvoyé, l'adresse seulement au titulaire
+        assert!(user.get("password").is_none());
+        assert_eq!(user.get("email").is_some(), token == access_token);
+    }
 
     // --- ISSUES ---
-    // Create issue (public route)
-    let res = client.post("http://localhost:3000/issues")
+    // Create issue SANS token (401)
+    let res = client.post(format!("{}/issues", BASE))
+        .json(&json!({"project_id": project_id, "title": "bug", "description": "desc"}))
+        .send().await.unwrap();
+    assert_eq!(res.status(), 401);
+    // Create issue AVEC token
+    let res = client.post(format!("{}/issues", BASE))
+        .bearer_auth(access_token)
         .json(&json!({"project_id": project_id, "title": "bug", "description": "desc"}))
         .send().await.unwrap();
     assert_eq!(res.status(), 201);
+    // Un projet non listé n'apparaît pas dans la recherche globale, seulement si on le nomme
+    let res = client.put(format!("{}/projects/{}", BASE, project_id))
+        .bearer_auth(access_token)
+        .json(&json!({"visibility": "Unlisted"}))
+        .send().await.unwrap();
+    assert_eq!(res.status(), 200);
+    for (scope, expected) in [(String::new(), 0), (format!("&project_id={}", project_id), 1)] {
+        let res = client.get(format!("{}/search?q=bug{}", BASE, scope))
+            .bearer_auth(&other_token)
+            .send().await.unwrap();
+        let hits: serde_json::Value = res.json().await.unwrap();
+        assert_eq!(hits.as_array().unwrap().len(), expected);
+    }
+    // Une fois public et publié, le projet apparaît dans les tendances avec ses étoiles
+    let res = client.put(format!("{}/projects/{}", BASE, project_id))
+        .bearer_auth(access_token)
+        .json(&json!({"visibility": "Public"}))
+        .send().await.unwrap();
+    assert_eq!(res.status(), 200);
+    let res = client.post(format!("{}/projects/{}/publish", BASE, project_id))
+        .bearer_auth(access_token)
+        .send().await.unwrap();
+    assert!(res.status().is_success());
+    let res = client.get(format!("{}/explore", BASE)).send().await.unwrap();
+    let trending: serde_json::Value = res.json().await.unwrap();
+    assert_eq!(trending[0]["id"].as_str(), Some(project_id));
+    assert_eq!(trending[0]["stars_count"], 1);
+    let res = client.put(format!("{}/projects/{}", BASE, project_id))
+        .bearer_auth(access_token)
+        .json(&json!({"visibility": "Private"}))
+        .send().await.unwrap();
+    assert_eq!(res.status(), 200);
     // List issues by project
-    let res = client.get(&format!("http://localhost:3000/issues?project_id={}", project_id))
+    let res = client.get(format!("{}/issues?project_id={}", BASE, project_id))
+        .bearer_auth(access_token)
         .send().await.unwrap();
     assert_eq!(res.status(), 200);
     let issues: serde_json::Value = res.json().await.unwrap();
     let issue_id = issues[0]["id"].as_str().unwrap();
-    // Update issue (public route)
-    let res = client.put(&format!("http://localhost:3000/issues/{}", issue_id))
+    // Update issue SANS token (401)
+    let res = client.put(format!("{}/issues/{}", BASE, issue_id))
+        .json(&json!({"title": "fixed"}))
+        .send().await.unwrap();
+    assert_eq!(res.status(), 401);
+    // Update issue AVEC token
+    let res = client.put(format!("{}/issues/{}", BASE, issue_id))
+        .bearer_auth(access_token)
         .json(&json!({"title": "fixed"}))
         .send().await.unwrap();
     assert_eq!(res.status(), 200);
-    // Delete issue (public route)
-    let res = client.delete(&format!("http://localhost:3000/issues/{}", issue_id))
+    // Delete issue par un non-membre : le projet privé reste invisible (404)
+    let res = client.delete(format!("{}/issues/{}", BASE, issue_id))
+        .bearer_auth(&other_token)
+        .send().await.unwrap();
+    assert_eq!(res.status(), 404);
+    // Delete issue AVEC token
+    let res = client.delete(format!("{}/issues/{}", BASE, issue_id))
+        .bearer_auth(access_token)
         .send().await.unwrap();
     assert_eq!(res.status(), 200);
+    // Une issue qui survivra à la suppression du projet
+    let res = client.post(format!("{}/issues", BASE))
+        .bearer_auth(access_token)
+        .json(&json!({"project_id": project_id, "title": "orphan", "description": "desc"}))
+        .send().await.unwrap();
+    assert_eq!(res.status(), 201);
+    let res = client.get(format!("{}/issues?project_id={}", BASE, project_id))
+        .bearer_auth(access_token)
+        .send().await.unwrap();
+    let issues: serde_json::Value = res.json().await.unwrap();
+    let orphan_id = issues[0]["id"].as_str().unwrap().to_string();
 
     // --- MEMBERS ---
-    // Add member (public route)
-    let res = client.post("http://localhost:3000/members")
-        .json(&json!({"user_id": "dummy-user-id", "project_id": project_id, "role": "Member"}))
+    // Add member SANS token (401)
+    let res = client.post(format!("{}/members", BASE))
+        .json(&json!({"user_id": project_id, "project_id": project_id, "role": "Viewer"}))
         .send().await.unwrap();
-    // Peut échouer si user_id n'existe pas, donc pas d'assert strict ici
-    // List members (public route)
-    let res = client.get("http://localhost:3000/members?project_id=".to_string() + project_id)
+    assert_eq!(res.status(), 401);
+    // Add member : le compte doit exister
+    let res = client.post(format!("{}/members", BASE))
+        .bearer_auth(access_token)
+        .json(&json!({"user_id": project_id, "project_id": project_id, "role": "Viewer"}))
+        .send().await.unwrap();
+    assert_eq!(res.status(), 404);
+    // List members
+    let res = client.get(format!("{}/members?project_id={}", BASE, project_id))
+        .bearer_auth(access_token)
         .send().await.unwrap();
     assert!(res.status().is_success());
-    // Remove member (public route)
-    let res = client.delete("http://localhost:3000/members")
-        .json(&json!({"user_id": "dummy-user-id", "project_id": project_id}))
+    // Remove member : un non-membre ne voit pas le projet
+    let res = client.delete(format!("{}/members", BASE))
+        .bearer_auth(&other_token)
+        .json(&json!({"user_id": project_id, "project_id": project_id}))
+        .send().await.unwrap();
+    assert_eq!(res.status(), 404);
+
+    // Delete project SANS token (401)
+    let res = client.delete(format!("{}/projects/{}", BASE, project_id))
         .send().await.unwrap();
-    // Peut échouer si user_id n'existe pas
+    assert_eq!(res.status(), 401);
+    // Delete project par un non-membre (404)
+    let res = client.delete(format!("{}/projects/{}", BASE, project_id))
+        .bearer_auth(&other_token)
+        .send().await.unwrap();
+    assert_eq!(res.status(), 404);
+    // Delete project AVEC token
+    let res = client.delete(format!("{}/projects/{}", BASE, project_id))
+        .bearer_auth(access_token)
+        .send().await.unwrap();
+    assert_eq!(res.status(), 200);
+    // Les issues d'un projet privé supprimé ne sont plus lisibles par personne
+    for token in [access_token, other_token.as_str()] {
+        for path in [
+            format!("/issues/{}", orphan_id),
+            format!("/issues/{}/history", orphan_id),
+            format!("/issues/{}/comments", orphan_id),
+            format!("/issues/{}/mentions", orphan_id),
+            format!("/issues?project_id={}", project_id),
+        ] {
+            let res = client.get(format!("{}{}", BASE, path)).bearer_auth(token).send().await.unwrap();
+            assert_eq!(res.status(), 404, "{}", path);
+        }
+    }
 }
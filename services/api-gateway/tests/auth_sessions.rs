@@ -0,0 +1,64 @@
+mod common;
+
+use reqwest::Client;
+use serde_json::{json, Value};
+
+const BASE: &str = "http://127.0.0.1:3012";
+
+async fn login(client: &Client) -> (String, String) {
+    let res = client.post(format!("{}/login", BASE))
+        .json(&json!({"username": "lea", "email": "lea@example.com", "password": "leapass"}))
+        .send().await.unwrap();
+    let body: Value = res.json().await.unwrap();
+    (body["access_token"].as_str().unwrap().to_string(), body["refresh_token"].as_str().unwrap().to_string())
+}
+
+async fn refresh(client: &Client, token: &str) -> reqwest::Response {
+    client.post(format!("{}/refresh", BASE)).json(&json!({"refresh_token": token})).send().await.unwrap()
+}
+
+async fn users_status(client: &Client, token: &str) -> u16 {
+    client.get(format!("{}/users", BASE)).bearer_auth(token).send().await.unwrap().status().as_u16()
+}
+
+#[tokio::test]
+async fn test_refresh_rotation_reuse_and_logout() {
+    let _server = common::spawn_server("127.0.0.1:3012");
+    let client = Client::new();
+    client.post(format!("{}/register", BASE))
+        .json(&json!({"username": "lea", "email": "lea@example.com", "password": "leapass"}))
+        .send().await.unwrap();
+    let (access, first_refresh) = login(&client).await;
+
+    // Les deux types de jetons ne sont pas interchangeables
+    assert_eq!(users_status(&client, &first_refresh).await, 401);
+    assert_eq!(refresh(&client, &access).await.status(), 401);
+
+    // Rotation : le nouveau refresh token fonctionne, l'ancien ne sert qu'une fois
+    let res = refresh(&client, &first_refresh).await;
+    assert_eq!(res.status(), 200);
+    let body: Value = res.json().await.unwrap();
+    let second_refresh = body["refresh_token"].as_str().unwrap().to_string();
+    assert_eq!(users_status(&client, body["access_token"].as_str().unwrap()).await, 200);
+
+    // Rejouer l'ancien jeton révoque toute la session
+    assert_eq!(refresh(&client, &first_refresh).await.status(), 401);
+    assert_eq!(refresh(&client, &second_refresh).await.status(), 401);
+    assert_eq!(users_status(&client, &access).await, 401);
+
+    // Déconnexion d'une session, puis de toutes
+    let (laptop, laptop_refresh) = login(&client).await;
+    let (phone, _) = login(&client).await;
+    let res = client.post(format!("{}/logout", BASE)).bearer_auth(&laptop).send().await.unwrap();
+    assert_eq!(res.status(), 204);
+    assert_eq!(users_status(&client, &laptop).await, 401);
+    assert_eq!(refresh(&client, &laptop_refresh).await.status(), 401);
+    assert_eq!(users_status(&client, &phone).await, 200);
+
+    let res = client.post(format!("{}/logout/all", BASE)).bearer_auth(&phone).send().await.unwrap();
+    assert_eq!(res.status(), 200);
+    let body: Value = res.json().await.unwrap();
+    // Le téléphone et la session ouverte à l'inscription
+    assert_eq!(body["revoked_sessions"], 2);
+    assert_eq!(users_status(&client, &phone).await, 401);
+}
@@ -0,0 +1,97 @@
+use std::net::TcpStream;
+use std::path::PathBuf;
+use std::process::{Child, Command};
+use std::time::{Duration, Instant, SystemTime};
+
+/// Lance le binaire api-gateway sur `addr` et l'arrête quand le guard est droppé. Les emails
+/// sont écrits dans une boîte d'envoi propre au serveur.
+pub struct Server {
+    child: Child,
+    pub outbox: PathBuf,
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        let _ = std::fs::remove_dir_all(&self.outbox);
+    }
+}
+
+#[allow(dead_code)]
+pub fn spawn_server(addr: &str) -> Server {
+    spawn_server_with_env(addr, &[])
+}
+
+/// Comme `spawn_server`, avec des variables d'environnement supplémentaires.
+pub fn spawn_server_with_env(addr: &str, vars: &[(&str, &str)]) -> Server {
+    let port = addr.rsplit(':').next().unwrap_or(addr);
+    let outbox = std::env::temp_dir().join(format!("openstudio-outbox-{}-{}", std::process::id(), port));
+    let _ = std::fs::remove_dir_all(&outbox);
+    let child = Command::new(env!("CARGO_BIN_EXE_api-gateway"))
+        .env("OPENSTUDIO_ADDR", addr)
+        .env("OPENSTUDIO_MAIL_OUTBOX", &outbox)
+        .envs(vars.iter().copied())
+        .spawn()
+        .expect("failed to start api-gateway");
+    let server = Server { child, outbox };
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while TcpStream::connect(addr).is_err() {
+        assert!(Instant::now() < deadline, "api-gateway did not start on {}", addr);
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    server
+}
+
+#[allow(dead_code)]
+impl Server {
+    /// Emails envoyés à `to` dont le corps contient `needle`, du plus ancien au plus récent,
+    /// décodés du quoted-printable (lignes coupées, `=` échappés).
+    pub fn mails(&self, to: &str, needle: &str) -> Vec<String> {
+        let mut mails: Vec<(SystemTime, String)> = std::fs::read_dir(&self.outbox)
+            .ok()
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter(|e| e.path().extension().is_some_and(|x| x == "eml"))
+            .filter_map(|e| {
+                let modified = e.metadata().and_then(|m| m.modified()).ok()?;
+                let raw = std::fs::read_to_string(e.path()).ok()?;
+                Some((modified, raw.replace("=\r\n", "").replace("=0A", "\n").replace("=3D", "=")))
+            })
+            .filter(|(_, mail)| mail.contains(&format!("To: {}", to)) && mail.contains(needle))
+            .collect();
+        mails.sort_by_key(|(modified, _)| *modified);
+        mails.into_iter().map(|(_, mail)| mail).collect()
+    }
+
+    /// Attend un email à `to` contenant un lien `{path}?token=` et renvoie ce lien, tiré du plus récent.
+    pub fn wait_for_link(&self, to: &str, path: &str) -> String {
+        let needle = format!("{}?token=", path);
+        let deadline = Instant::now() + Duration::from_secs(5);
+        loop {
+            if let Some(mail) = self.mails(to, &needle).pop() {
+                let at = mail.find(&needle).unwrap();
+                let start = mail[..at].rfind("http").unwrap();
+                let end = at + needle.len();
+                let token: String = mail[end..].chars().take_while(|c| c.is_ascii_alphanumeric() || "-_.".contains(*c)).collect();
+                return format!("{}{}", &mail[start..end], token);
+            }
+            assert!(Instant::now() < deadline, "no email to {} in {}", to, self.outbox.display());
+            std::thread::sleep(Duration::from_millis(50));
+        }
+    }
+
+    /// Jeton du lien `{path}?token=` envoyé à `to`.
+    pub fn wait_for_token(&self, to: &str, path: &str) -> String {
+        let link = self.wait_for_link(to, path);
+        link[link.find("?token=").unwrap() + "?token=".len()..].to_string()
+    }
+
+    /// Suit le lien de vérification envoyé à l'inscription de `to`.
+    pub async fn verify_email(&self, client: &reqwest::Client, to: &str) {
+        let link = self.wait_for_link(to, "/email/verify");
+        let res = client.get(link).send().await.unwrap();
+        assert_eq!(res.status(), 200);
+    }
+}
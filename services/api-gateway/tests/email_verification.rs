@@ -0,0 +1,72 @@
+mod common;
+
+use reqwest::Client;
+use serde_json::{json, Value};
+
+const BASE: &str = "http://127.0.0.1:3018";
+
+#[tokio::test]
+async fn test_registration_requires_email_verification() {
+    let server = common::spawn_server("127.0.0.1:3018");
+    let client = Client::new();
+    // Pas d'autre porte d'entrée que /register, qui valide l'adresse
+    let res = client.post(format!("{}/users", BASE))
+        .json(&json!({"username": "zoe", "email": "not-an-email", "password": "zoepass"}))
+        .send().await.unwrap();
+    assert_eq!(res.status(), 405);
+    let res = client.post(format!("{}/register", BASE))
+        .json(&json!({"username": "zoe", "email": "not-an-email", "password": "zoepass"}))
+        .send().await.unwrap();
+    assert_eq!(res.status(), 400);
+    let res = client.post(format!("{}/register", BASE))
+        .json(&json!({"username": "zoe", "email": " zoe@example.com ", "password": "zoepass"}))
+        .send().await.unwrap();
+    assert_eq!(res.status(), 201);
+    let body: Value = res.json().await.unwrap();
+    assert_eq!(body["user"]["email"], "zoe@example.com");
+    assert_eq!(body["user"]["email_verified"], false);
+    let jwt = body["access_token"].as_str().unwrap().to_string();
+
+    // Pas de projet ni d'organisation tant que l'adresse n'est pas confirmée
+    let res = client.post(format!("{}/projects", BASE)).bearer_auth(&jwt)
+        .json(&json!({"name": "demo", "description": ""}))
+        .send().await.unwrap();
+    assert_eq!(res.status(), 403);
+    let res = client.post(format!("{}/orgs", BASE)).bearer_auth(&jwt)
+        .json(&json!({"slug": "zoe-org", "name": "Zoe"}))
+        .send().await.unwrap();
+    assert_eq!(res.status(), 403);
+
+    // Le lien vient d'être envoyé : le renvoi immédiat est refusé
+    let res = client.post(format!("{}/email/verify/resend", BASE)).bearer_auth(&jwt).send().await.unwrap();
+    assert_eq!(res.status(), 429);
+    assert!(res.headers().get("retry-after").is_some());
+
+    // Un jeton d'accès ne vaut pas lien de vérification
+    let res = client.get(format!("{}/email/verify", BASE)).query(&[("token", &jwt)]).send().await.unwrap();
+    assert_eq!(res.status(), 400);
+    server.verify_email(&client, "zoe@example.com").await;
+
+    let res = client.post(format!("{}/projects", BASE)).bearer_auth(&jwt)
+        .json(&json!({"name": "demo", "description": ""}))
+        .send().await.unwrap();
+    assert_eq!(res.status(), 201);
+    let res = client.post(format!("{}/email/verify/resend", BASE)).bearer_auth(&jwt).send().await.unwrap();
+    assert_eq!(res.status(), 409);
+
+    // Le slug d'une organisation n'est pas disponible comme nom d'utilisateur
+    let res = client.post(format!("{}/orgs", BASE)).bearer_auth(&jwt)
+        .json(&json!({"slug": "zoe-org", "name": "Zoe"}))
+        .send().await.unwrap();
+    assert_eq!(res.status(), 201);
+    let res = client.post(format!("{}/register", BASE))
+        .json(&json!({"username": "zoe-org", "email": "squat@example.com", "password": "squatpass"}))
+        .send().await.unwrap();
+    assert_eq!(res.status(), 409);
+
+    // L'adresse est déjà prise, quelle que soit la casse
+    let res = client.post(format!("{}/register", BASE))
+        .json(&json!({"username": "other", "email": "ZOE@example.com", "password": "otherpass"}))
+        .send().await.unwrap();
+    assert_eq!(res.status(), 409);
+}
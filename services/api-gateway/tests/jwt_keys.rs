@@ -0,0 +1,55 @@
+mod common;
+
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{decode, decode_header, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use reqwest::Client;
+use serde_json::json;
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/jwt");
+
+#[tokio::test]
+async fn test_tokens_carry_kid_and_verify_against_jwks() {
+    let config = format!("{}/keys.json", FIXTURES);
+    let _server = common::spawn_server_with_env("127.0.0.1:3011", &[("OPENSTUDIO_JWT_CONFIG", &config)]);
+    let client = Client::new();
+    let base = "http://127.0.0.1:3011";
+
+    client.post(format!("{}/register", base))
+        .json(&json!({"username": "kim", "email": "kim@example.com", "password": "kimpass"}))
+        .send().await.unwrap();
+    let res = client.post(format!("{}/login", base))
+        .json(&json!({"username": "kim", "email": "kim@example.com", "password": "kimpass"}))
+        .send().await.unwrap();
+    let body: serde_json::Value = res.json().await.unwrap();
+    let access_token = body["access_token"].as_str().unwrap().to_string();
+
+    // Le jeton est signé par la clé active et porte son kid
+    let header = decode_header(&access_token).unwrap();
+    assert_eq!(header.alg, Algorithm::EdDSA);
+    assert_eq!(header.kid.as_deref(), Some("ed-2026"));
+
+    // Le JWKS publie les deux clés, et suffit à vérifier le jeton
+    let jwks: JwkSet = client.get(format!("{}/.well-known/jwks.json", base)).send().await.unwrap().json().await.unwrap();
+    assert_eq!(jwks.keys.len(), 2);
+    let jwk = jwks.find("ed-2026").unwrap();
+    let claims = decode::<serde_json::Value>(&access_token, &DecodingKey::from_jwk(jwk).unwrap(), &Validation::new(Algorithm::EdDSA)).unwrap().claims;
+    assert_eq!(claims["email"], "kim@example.com");
+
+    // Un jeton signé par l'ancienne clé RSA reste accepté pendant la rotation
+    let mut old_header = Header::new(Algorithm::RS256);
+    old_header.kid = Some("rsa-2025".to_string());
+    let old_claims = claims.clone();
+    let rsa = std::fs::read(format!("{}/rsa.pem", FIXTURES)).unwrap();
+    let old_token = encode(&old_header, &old_claims, &EncodingKey::from_rsa_pem(&rsa).unwrap()).unwrap();
+    let res = client.get(format!("{}/users", base)).bearer_auth(&old_token).send().await.unwrap();
+    assert_eq!(res.status(), 200);
+
+    // Sans kid, ou avec un kid inconnu, le jeton est refusé
+    for kid in [None, Some("rsa-2024".to_string())] {
+        let mut header = Header::new(Algorithm::RS256);
+        header.kid = kid;
+        let token = encode(&header, &old_claims, &EncodingKey::from_rsa_pem(&rsa).unwrap()).unwrap();
+        let res = client.get(format!("{}/users", base)).bearer_auth(&token).send().await.unwrap();
+        assert_eq!(res.status(), 401);
+    }
+}
@@ -0,0 +1,139 @@
+mod common;
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use axum::extract::{Form, Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Redirect, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use reqwest::Client;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+const GATEWAY: &str = "http://127.0.0.1:3014";
+const PROVIDER: &str = "http://127.0.0.1:3016";
+
+struct IssuedCode {
+    challenge: String,
+    redirect_uri: String,
+    profile: Value,
+}
+
+/// Fournisseur OIDC minimal : il « connecte » d'office l'utilisateur `profile`.
+#[derive(Clone, Default)]
+struct MockProvider {
+    profile: Arc<Mutex<Value>>,
+    codes: Arc<Mutex<HashMap<String, IssuedCode>>>,
+    tokens: Arc<Mutex<HashMap<String, Value>>>,
+}
+
+async fn discovery() -> Json<Value> {
+    Json(json!({
+        "issuer": PROVIDER,
+        "authorization_endpoint": format!("{}/authorize", PROVIDER),
+        "token_endpoint": format!("{}/token", PROVIDER),
+        "userinfo_endpoint": format!("{}/userinfo", PROVIDER),
+    }))
+}
+
+async fn authorize(State(mock): State<MockProvider>, Query(params): Query<HashMap<String, String>>) -> Response {
+    if params.get("client_id").map(String::as_str) != Some("openstudio") || params.get("code_challenge_method").map(String::as_str) != Some("S256") {
+        return StatusCode::BAD_REQUEST.into_response();
+    }
+    let code = uuid::Uuid::new_v4().to_string();
+    let profile = mock.profile.lock().unwrap().clone();
+    let issued = IssuedCode { challenge: params["code_challenge"].clone(), redirect_uri: params["redirect_uri"].clone(), profile };
+    mock.codes.lock().unwrap().insert(code.clone(), issued);
+    Redirect::to(&format!("{}?code={}&state={}", params["redirect_uri"], code, params["state"])).into_response()
+}
+
+async fn token(State(mock): State<MockProvider>, Form(form): Form<HashMap<String, String>>) -> Response {
+    let Some(issued) = mock.codes.lock().unwrap().remove(&form["code"]) else {
+        return StatusCode::BAD_REQUEST.into_response();
+    };
+    let verified = URL_SAFE_NO_PAD.encode(Sha256::digest(form["code_verifier"].as_bytes())) == issued.challenge;
+    if !verified || form["client_secret"] != "s3cret" || form["redirect_uri"] != issued.redirect_uri {
+        return StatusCode::BAD_REQUEST.into_response();
+    }
+    let access_token = uuid::Uuid::new_v4().to_string();
+    mock.tokens.lock().unwrap().insert(access_token.clone(), issued.profile);
+    Json(json!({"access_token": access_token, "token_type": "Bearer"})).into_response()
+}
+
+async fn userinfo(State(mock): State<MockProvider>, headers: HeaderMap) -> Response {
+    let token = headers.get("authorization").and_then(|h| h.to_str().ok()).and_then(|h| h.strip_prefix("Bearer "));
+    match token.and_then(|t| mock.tokens.lock().unwrap().get(t).cloned()) {
+        Some(profile) => Json(profile).into_response(),
+        None => StatusCode::UNAUTHORIZED.into_response(),
+    }
+}
+
+/// Parcours complet dans le navigateur : gateway -> fournisseur -> callback.
+async fn sign_in(client: &Client, mock: &MockProvider, profile: Value) -> reqwest::Response {
+    *mock.profile.lock().unwrap() = profile;
+    let res = client.get(format!("{}/auth/mock/authorize", GATEWAY)).send().await.unwrap();
+    assert_eq!(res.status(), 303);
+    let provider_url = res.headers()["location"].to_str().unwrap().to_string();
+    assert!(provider_url.starts_with(&format!("{}/authorize?", PROVIDER)));
+    let res = client.get(provider_url).send().await.unwrap();
+    let callback = res.headers()["location"].to_str().unwrap().to_string();
+    client.get(callback).send().await.unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_oidc_login_creates_and_links_accounts() {
+    let mock = MockProvider::default();
+    let app = Router::new()
+        .route("/.well-known/openid-configuration", get(discovery))
+        .route("/authorize", get(authorize))
+        .route("/token", post(token))
+        .route("/userinfo", get(userinfo))
+        .with_state(mock.clone());
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3016").await.unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    let config = std::env::temp_dir().join(format!("openstudio-oauth-{}.json", std::process::id()));
+    let providers = json!({"providers": [{"name": "mock", "kind": "oidc", "client_id": "openstudio", "client_secret": "s3cret", "issuer": PROVIDER}]});
+    std::fs::write(&config, providers.to_string()).unwrap();
+    let server = common::spawn_server_with_env("127.0.0.1:3014", &[("OPENSTUDIO_OAUTH_CONFIG", config.to_str().unwrap())]);
+    let client = Client::builder().redirect(reqwest::redirect::Policy::none()).build().unwrap();
+
+    let providers: Value = client.get(format!("{}/auth/providers", GATEWAY)).send().await.unwrap().json().await.unwrap();
+    assert_eq!(providers[0]["name"], "mock");
+
+    // Première connexion : création du compte, puis même compte aux connexions suivantes
+    let nina = json!({"sub": "u-1", "email": "nina@example.com", "email_verified": true, "preferred_username": "nina"});
+    let res = sign_in(&client, &mock, nina.clone()).await;
+    assert_eq!(res.status(), 201);
+    let body: Value = res.json().await.unwrap();
+    assert_eq!(body["user"]["username"], "nina");
+    let res = client.get(format!("{}/users", GATEWAY)).bearer_auth(body["access_token"].as_str().unwrap()).send().await.unwrap();
+    assert_eq!(res.status(), 200);
+    let again: Value = sign_in(&client, &mock, nina).await.json().await.unwrap();
+    assert_eq!(again["created"], false);
+    assert_eq!(again["user"]["id"], body["user"]["id"]);
+
+    // Un compte à mot de passe n'est rattaché que si l'adresse est vérifiée des deux côtés
+    let res = client.post(format!("{}/register", GATEWAY))
+        .json(&json!({"username": "omar", "email": "omar@example.com", "password": "omarpass"}))
+        .send().await.unwrap();
+    let omar: Value = res.json().await.unwrap();
+    let unverified = json!({"sub": "u-2", "email": "omar@example.com", "email_verified": false});
+    assert_eq!(sign_in(&client, &mock, unverified).await.status(), 409);
+    let verified = json!({"sub": "u-2", "email": "omar@example.com", "email_verified": true});
+    assert_eq!(sign_in(&client, &mock, verified.clone()).await.status(), 409);
+    server.verify_email(&client, "omar@example.com").await;
+    let res = sign_in(&client, &mock, verified).await;
+    assert_eq!(res.status(), 200);
+    let linked: Value = res.json().await.unwrap();
+    assert_eq!(linked["user"]["id"], omar["user"]["id"]);
+
+    // Un state inconnu (ou déjà consommé) est refusé
+    let res = client.get(format!("{}/auth/mock/callback?code=abc&state=forged", GATEWAY)).send().await.unwrap();
+    assert_eq!(res.status(), 400);
+    let _ = std::fs::remove_file(config);
+}
@@ -0,0 +1,59 @@
+mod common;
+
+use reqwest::Client;
+use serde_json::{json, Value};
+
+const BASE: &str = "http://127.0.0.1:3017";
+
+/// Valeur du premier attribut `name` de la page.
+fn attribute(page: &str, name: &str) -> String {
+    let start = page.find(&format!("{}=\"", name)).unwrap() + name.len() + 2;
+    page[start..].chars().take_while(|c| *c != '"').collect()
+}
+
+#[tokio::test]
+async fn test_password_reset_through_outbox() {
+    let server = common::spawn_server("127.0.0.1:3017");
+    let client = Client::new();
+    let res = client.post(format!("{}/register", BASE))
+        .json(&json!({"username": "paul", "email": "paul@example.com", "password": "oldpassword"}))
+        .send().await.unwrap();
+    let session: Value = res.json().await.unwrap();
+
+    // Même réponse, que l'adresse existe ou non ; seul le compte existant reçoit un email
+    let res = client.post(format!("{}/password/forgot", BASE)).json(&json!({"email": "nobody@example.com"})).send().await.unwrap();
+    assert_eq!(res.status(), 202);
+    let unknown_body = res.text().await.unwrap();
+    let res = client.post(format!("{}/password/forgot", BASE)).json(&json!({"email": "Paul@example.com"})).send().await.unwrap();
+    assert_eq!(res.status(), 202);
+    assert_eq!(res.text().await.unwrap(), unknown_body);
+
+    let link = server.wait_for_link("paul@example.com", "/password/reset");
+    // Un seul email de réinitialisation, tous destinataires confondus
+    assert_eq!(server.mails("", "/password/reset?token=").len(), 1);
+
+    // Le lien ouvre un formulaire qui porte le jeton
+    let res = client.get(&link).send().await.unwrap();
+    assert_eq!(res.status(), 200);
+    assert!(res.headers()["content-type"].to_str().unwrap().starts_with("text/html"));
+    let page = res.text().await.unwrap();
+    let (action, token) = (attribute(&page, "action"), attribute(&page, "value"));
+    assert!(link.ends_with(&token));
+
+    let res = client.post(format!("{}/password/reset", BASE)).json(&json!({"token": token, "password": "short"})).send().await.unwrap();
+    assert_eq!(res.status(), 400);
+    let res = client.post(action).form(&[("token", token.as_str()), ("password", "newpassword")]).send().await.unwrap();
+    assert_eq!(res.status(), 200);
+    // Le jeton ne sert qu'une fois, et les sessions ouvertes sont fermées
+    let res = client.post(format!("{}/password/reset", BASE)).json(&json!({"token": token, "password": "otherpassword"})).send().await.unwrap();
+    assert_eq!(res.status(), 400);
+    let res = client.get(format!("{}/users", BASE)).bearer_auth(session["access_token"].as_str().unwrap()).send().await.unwrap();
+    assert_eq!(res.status(), 401);
+
+    for (password, status) in [("oldpassword", 401), ("newpassword", 200)] {
+        let res = client.post(format!("{}/login", BASE))
+            .json(&json!({"username": "paul", "email": "paul@example.com", "password": password}))
+            .send().await.unwrap();
+        assert_eq!(res.status(), status);
+    }
+}